@@ -0,0 +1,51 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use sight::color::{convert_slice, BGR8, HSL, RGB32, RGB8};
+
+const WIDTH: usize = 1920;
+const HEIGHT: usize = 1080;
+
+fn rgb8() -> Vec<RGB8> {
+    (0..WIDTH * HEIGHT)
+        .map(|i| RGB8::from([i as u8, (i >> 8) as u8, (i >> 16) as u8]))
+        .collect()
+}
+
+fn bench<A, B>(c: &mut Criterion, name: &str, src: &[A])
+where
+    A: Copy + Into<B> + 'static,
+    B: Copy + Default + 'static,
+{
+    let mut group = c.benchmark_group(name);
+    group.sample_size(20);
+
+    group.bench_function(BenchmarkId::new("per_pixel", WIDTH * HEIGHT), |b| {
+        b.iter(|| {
+            let dst: Vec<B> = black_box(src).iter().map(|x| (*x).into()).collect();
+            dst
+        })
+    });
+
+    let mut dst = vec![B::default(); src.len()];
+    group.bench_function(BenchmarkId::new("convert_slice", WIDTH * HEIGHT), |b| {
+        b.iter(|| convert_slice(black_box(src), &mut dst).unwrap())
+    });
+
+    group.finish();
+}
+
+fn conversions(c: &mut Criterion) {
+    let src = rgb8();
+    bench::<RGB8, RGB32>(c, "rgb8_to_rgb32", &src);
+    bench::<RGB8, BGR8>(c, "rgb8_to_bgr8", &src);
+    bench::<RGB8, HSL>(c, "rgb8_to_hsl", &src);
+    bench::<RGB8, u8>(c, "rgb8_to_gray", &src);
+
+    let src32: Vec<RGB32> = src.iter().map(|x| (*x).into()).collect();
+    bench::<RGB32, RGB8>(c, "rgb32_to_rgb8", &src32);
+
+    let hsl: Vec<HSL> = src.iter().map(|x| (*x).into()).collect();
+    bench::<HSL, RGB8>(c, "hsl_to_rgb8", &hsl);
+}
+
+criterion_group!(benches, conversions);
+criterion_main!(benches);
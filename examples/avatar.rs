@@ -17,8 +17,8 @@ fn main() {
     let buffer: [u8; 9] = name[0..9].try_into().unwrap();
     let mut color: [u8; 3] = [name[9], name[10], name[11]];
 
-    for i in 0..3 {
-        color[i] = (color[i] as f32 * 0.8) as u8;
+    for c in color.iter_mut() {
+        *c = (*c as f32 * 0.8) as u8;
     }
 
     draw(&mut img, buffer, RGB8::from(color));
@@ -26,13 +26,11 @@ fn mandelbrot_red_black(x: u32, y: u32, width: u32, height: u32) -> RGB8 {
 }
 
 fn main() {
-    let mut img: Image<RGB8> = Image::new(571, 600, RGB8::from([255, 0, 0])).unwrap();
-
-    for y in 0..img.height() {
-        for x in 0..img.width() {
-            *img.pixel_mut(x, y) = mandelbrot_red_black(x, y, img.width(), img.height());
-        }
-    }
+    let (width, height) = (571, 600);
+    let mut img: Image<RGB8> = Image::from_fn(width, height, |x, y| {
+        mandelbrot_red_black(x, y, width, height)
+    })
+    .unwrap();
 
     img.rotate90();
 
@@ -1,6 +1,6 @@
 use sight::{
     color::RGB8,
-    display::{ComponentsRaw, Frame, Image},
+    display::{ComponentsRaw, Frame, FrameMut, Image},
     geometry::{Vec2, Vec3},
 };
 
@@ -47,7 +47,7 @@ fn barycentric(a: Vec2<i32>, b: Vec2<i32>, c: Vec2<i32>, p: Vec2<i32>) -> Vec3<f
     }
 }
 
-fn triangle_interpolation<T: Frame<Pixel = RGB8>>(
+fn triangle_interpolation<T: FrameMut<Pixel = RGB8>>(
     frame: &mut T,
     points: [Vec2<i32>; 3],
     colors: [RGB8; 3],
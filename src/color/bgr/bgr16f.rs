@@ -0,0 +1,112 @@
+use super::{BGR16F, BGR32, BGR64, BGR8};
+use crate::color::{rgb_hsl::hsl_to_rgb, Convert, HSL, RGB16F, RGB32, RGB64, RGB8};
+
+impl Convert for BGR16F {
+    fn from_bgr16f(src: BGR16F) -> Self {
+        src
+    }
+
+    fn from_rgb8(src: RGB8) -> Self {
+        src.to_bgr()
+    }
+
+    fn from_rgb16f(src: RGB16F) -> Self {
+        src.to_bgr()
+    }
+
+    fn from_rgb32(src: RGB32) -> Self {
+        src.to_bgr()
+    }
+
+    fn from_rgb64(src: RGB64) -> Self {
+        src.to_bgr()
+    }
+
+    fn from_bgr8(src: BGR8) -> Self {
+        src.convert()
+    }
+
+    fn from_bgr32(src: BGR32) -> Self {
+        src.convert()
+    }
+
+    fn from_bgr64(src: BGR64) -> Self {
+        src.convert()
+    }
+
+    fn from_hsl(src: HSL) -> Self {
+        hsl_to_rgb(src).to_bgr()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::f16;
+
+    fn bgr16f(src: [f32; 3]) -> BGR16F {
+        BGR16F::from([
+            f16::from_f32(src[0]),
+            f16::from_f32(src[1]),
+            f16::from_f32(src[2]),
+        ])
+    }
+
+    #[test]
+    fn from_bgr16f() {
+        let bgr = bgr16f([0.5, 2.0, -1.0]);
+        assert_eq!(BGR16F::from_bgr16f(bgr), bgr);
+    }
+
+    #[test]
+    fn from_rgb8() {
+        let bgr = BGR16F::from_rgb8(RGB8::from([0, 0, 255]));
+        assert_eq!(bgr, bgr16f([1.0, 0.0, 0.0]));
+
+        let bgr = BGR16F::from_rgb8(RGB8::from([255, 255, 0]));
+        assert_eq!(bgr, bgr16f([0.0, 1.0, 1.0]));
+    }
+
+    #[test]
+    fn from_rgb16f() {
+        let rgb = RGB16F::from([f16::ONE, f16::ZERO, f16::ZERO]);
+        let bgr = BGR16F::from_rgb16f(rgb);
+        assert_eq!(bgr, bgr16f([0.0, 0.0, 1.0]));
+    }
+
+    #[test]
+    fn from_rgb32() {
+        let bgr = BGR16F::from_rgb32(RGB32::from([1.0, 0.5, 0.0]));
+        assert_eq!(bgr, bgr16f([0.0, 0.5, 1.0]));
+    }
+
+    #[test]
+    fn from_rgb64() {
+        let bgr = BGR16F::from_rgb64(RGB64::from([2.0, 0.0, 0.125]));
+        assert_eq!(bgr, bgr16f([0.125, 0.0, 2.0]));
+    }
+
+    #[test]
+    fn from_bgr8() {
+        let bgr = BGR16F::from_bgr8(BGR8::from([255, 0, 0]));
+        assert_eq!(bgr, bgr16f([1.0, 0.0, 0.0]));
+    }
+
+    #[test]
+    fn from_bgr32() {
+        let bgr = BGR16F::from_bgr32(BGR32::from([0.0, 0.5, 1.0]));
+        assert_eq!(bgr, bgr16f([0.0, 0.5, 1.0]));
+    }
+
+    #[test]
+    fn from_bgr64() {
+        let bgr = BGR16F::from_bgr64(BGR64::from([0.0, 0.25, 4.0]));
+        assert_eq!(bgr, bgr16f([0.0, 0.25, 4.0]));
+    }
+
+    #[test]
+    fn from_hsl() {
+        let bgr = BGR16F::from_hsl(HSL::from([0.0, 1.0, 0.5]));
+        assert_eq!(bgr, bgr16f([0.0, 0.0, 1.0]));
+    }
+}
@@ -1,5 +1,5 @@
-use super::{BGR32, BGR64, BGR8};
-use crate::color::{rgb_hsl::hsl_to_rgb, Convert, HSL, RGB32, RGB64, RGB8};
+use super::{BGR16F, BGR32, BGR64, BGR8};
+use crate::color::{rgb_hsl::hsl_to_rgb, Convert, HSL, RGB16F, RGB32, RGB64, RGB8};
 
 impl Convert for BGR32 {
     fn from_bgr32(_: BGR32) -> Self {
@@ -7,47 +7,35 @@ impl Convert for BGR32 {
     }
 
     fn from_rgb8(src: RGB8) -> Self {
-        Self {
-            r: Self::byte_to_percent32(src.r),
-            g: Self::byte_to_percent32(src.g),
-            b: Self::byte_to_percent32(src.b),
-        }
+        src.to_bgr()
     }
 
     fn from_rgb32(src: RGB32) -> Self {
-        Self {
-            r: src.r,
-            g: src.g,
-            b: src.b,
-        }
+        src.to_bgr()
     }
 
     fn from_rgb64(src: RGB64) -> Self {
-        Self {
-            r: src.r as f32,
-            g: src.g as f32,
-            b: src.b as f32,
-        }
+        src.to_bgr()
     }
 
     fn from_bgr8(src: BGR8) -> Self {
-        Self {
-            r: Self::byte_to_percent32(src.r),
-            g: Self::byte_to_percent32(src.g),
-            b: Self::byte_to_percent32(src.b),
-        }
+        src.convert()
     }
 
     fn from_bgr64(src: BGR64) -> Self {
-        Self {
-            r: src.r as f32,
-            g: src.g as f32,
-            b: src.b as f32,
-        }
+        src.convert()
+    }
+
+    fn from_rgb16f(src: RGB16F) -> Self {
+        src.to_bgr()
+    }
+
+    fn from_bgr16f(src: BGR16F) -> Self {
+        src.convert()
     }
 
     fn from_hsl(src: HSL) -> Self {
-        Self::from_rgb64(hsl_to_rgb(src))
+        hsl_to_rgb(src).to_bgr()
     }
 }
 
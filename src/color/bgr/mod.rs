@@ -1,14 +1,19 @@
+mod bgr16f;
 mod bgr32;
 mod bgr64;
 mod bgr8;
 
-use super::ComponentsCount;
+use super::{f16, rgb::RGB, Channel, ComponentsCount};
+use std::ops::{Add, Div, Mul, Sub};
 
 pub type BGR8 = BGR<u8>;
+pub type BGR16 = BGR<u16>;
+pub type BGR16F = BGR<f16>;
 pub type BGR32 = BGR<f32>;
 pub type BGR64 = BGR<f64>;
 
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 pub struct BGR<T> {
     /// Blue
@@ -21,19 +26,182 @@ pub struct BGR<T> {
 
 impl<T> BGR<T> {
     pub fn percent32_to_byte(percent: f32) -> u8 {
-        (percent * 255_f32).round() as u8
+        u8::from_f32(percent)
     }
 
     pub fn percent64_to_byte(percent: f64) -> u8 {
-        (percent * 255_f64).round() as u8
+        u8::from_f64(percent)
     }
 
     pub fn byte_to_percent32(byte: u8) -> f32 {
-        byte as f32 / 255_f32
+        byte.to_f32()
     }
 
     pub fn byte_to_percent64(byte: u8) -> f64 {
-        byte as f64 / 255_f64
+        byte.to_f64()
+    }
+}
+
+impl<T: Channel> BGR<T> {
+    /// Rescale every component to another channel depth
+    pub fn convert<U: Channel>(self) -> BGR<U> {
+        BGR {
+            b: U::from_channel(self.b),
+            g: U::from_channel(self.g),
+            r: U::from_channel(self.r),
+        }
+    }
+
+    pub fn to_rgb<U: Channel>(self) -> RGB<U> {
+        RGB {
+            r: U::from_channel(self.r),
+            g: U::from_channel(self.g),
+            b: U::from_channel(self.b),
+        }
+    }
+
+    pub fn clamp(self) -> Self {
+        Self {
+            b: Channel::clamp(self.b),
+            g: Channel::clamp(self.g),
+            r: Channel::clamp(self.r),
+        }
+    }
+
+    pub fn saturating_add(self, rhs: Self) -> Self {
+        Self {
+            b: self.b.saturating_add(rhs.b),
+            g: self.g.saturating_add(rhs.g),
+            r: self.r.saturating_add(rhs.r),
+        }
+    }
+
+    pub fn saturating_sub(self, rhs: Self) -> Self {
+        Self {
+            b: self.b.saturating_sub(rhs.b),
+            g: self.g.saturating_sub(rhs.g),
+            r: self.r.saturating_sub(rhs.r),
+        }
+    }
+
+    pub fn saturating_mul(self, rhs: Self) -> Self {
+        Self {
+            b: self.b.saturating_mul(rhs.b),
+            g: self.g.saturating_mul(rhs.g),
+            r: self.r.saturating_mul(rhs.r),
+        }
+    }
+}
+
+impl<T> Add for BGR<T>
+where
+    T: Copy + Add<Output = T>,
+{
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::Output {
+            b: self.b + rhs.b,
+            g: self.g + rhs.g,
+            r: self.r + rhs.r,
+        }
+    }
+}
+
+impl<T> Add<T> for BGR<T>
+where
+    T: Copy + Add<Output = T>,
+{
+    type Output = Self;
+    fn add(self, rhs: T) -> Self::Output {
+        Self::Output {
+            b: self.b + rhs,
+            g: self.g + rhs,
+            r: self.r + rhs,
+        }
+    }
+}
+
+impl<T> Sub for BGR<T>
+where
+    T: Copy + Sub<Output = T>,
+{
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::Output {
+            b: self.b - rhs.b,
+            g: self.g - rhs.g,
+            r: self.r - rhs.r,
+        }
+    }
+}
+
+impl<T> Sub<T> for BGR<T>
+where
+    T: Copy + Sub<Output = T>,
+{
+    type Output = Self;
+    fn sub(self, rhs: T) -> Self::Output {
+        Self::Output {
+            b: self.b - rhs,
+            g: self.g - rhs,
+            r: self.r - rhs,
+        }
+    }
+}
+
+impl<T> Mul for BGR<T>
+where
+    T: Copy + Mul<Output = T>,
+{
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self::Output {
+            b: self.b * rhs.b,
+            g: self.g * rhs.g,
+            r: self.r * rhs.r,
+        }
+    }
+}
+
+impl<T> Mul<T> for BGR<T>
+where
+    T: Copy + Mul<Output = T>,
+{
+    type Output = Self;
+    fn mul(self, rhs: T) -> Self::Output {
+        Self::Output {
+            b: self.b * rhs,
+            g: self.g * rhs,
+            r: self.r * rhs,
+        }
+    }
+}
+
+impl<T> Div for BGR<T>
+where
+    T: Copy + Div<Output = T>,
+{
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self::Output {
+        Self::Output {
+            b: self.b / rhs.b,
+            g: self.g / rhs.g,
+            r: self.r / rhs.r,
+        }
+    }
+}
+
+impl<T> Div<T> for BGR<T>
+where
+    T: Copy + Div<Output = T>,
+{
+    type Output = Self;
+    fn div(self, rhs: T) -> Self::Output {
+        Self::Output {
+            b: self.b / rhs,
+            g: self.g / rhs,
+            r: self.r / rhs,
+        }
     }
 }
 
@@ -87,6 +255,37 @@ mod tests {
         assert_eq!(percent, 0.0);
     }
 
+    #[test]
+    fn arithmetic() {
+        let bgr: BGR<u8> = BGR { b: 1, g: 5, r: 0 };
+        let rth: BGR<u8> = BGR { b: 4, g: 10, r: 2 };
+        assert_eq!(bgr + rth, BGR { b: 5, g: 15, r: 2 });
+        assert_eq!(rth - bgr, BGR { b: 3, g: 5, r: 2 });
+        assert_eq!(bgr * rth, BGR { b: 4, g: 50, r: 0 });
+        assert_eq!(rth / 2, BGR { b: 2, g: 5, r: 1 });
+        assert_eq!(bgr + 1, BGR { b: 2, g: 6, r: 1 });
+    }
+
+    #[test]
+    fn convert() {
+        let bgr = BGR8::from([0, 128, 255]);
+        assert_eq!(bgr.convert::<u16>(), BGR16::from([0, 32896, 65535]));
+        assert_eq!(bgr.convert::<f32>().convert::<u8>(), bgr);
+        assert_eq!(bgr.to_rgb::<u8>(), RGB::from([255, 128, 0]));
+    }
+
+    #[test]
+    fn saturating() {
+        let bgr = BGR8::from([250, 10, 100]);
+        let rth = BGR8::from([10, 20, 100]);
+        assert_eq!(bgr.saturating_add(rth), BGR8::from([255, 30, 200]));
+        assert_eq!(bgr.saturating_sub(rth), BGR8::from([240, 0, 0]));
+        assert_eq!(
+            BGR32::from([1.5, 0.0, -2.0]).clamp(),
+            BGR32::from([1.0, 0.0, 0.0])
+        );
+    }
+
     #[test]
     fn partial_eq() {
         let bgr1 = BGR::from([1.0, 1.0, 1.0]);
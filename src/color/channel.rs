@@ -0,0 +1,255 @@
+use super::f16;
+use std::any::Any;
+
+/// A single color component with a normalized range of [0, MAX]
+///
+/// Integer channels map [0, MAX] onto [0.0, 1.0] and saturate. Float
+/// channels are already normalized and are left unbounded so HDR values
+/// survive conversions; `clamp` and the saturating operations limit them to
+/// [0.0, 1.0].
+pub trait Channel: Copy + PartialOrd + Default + 'static {
+    /// Full intensity
+    const MAX: Self;
+
+    /// No intensity
+    const MIN: Self;
+
+    fn to_f32(self) -> f32;
+
+    fn to_f64(self) -> f64;
+
+    fn from_f32(value: f32) -> Self;
+
+    fn from_f64(value: f64) -> Self;
+
+    /// Sum clamped to [`MIN`, `MAX`]
+    fn saturating_add(self, rhs: Self) -> Self;
+
+    /// Difference clamped to [`MIN`, `MAX`]
+    fn saturating_sub(self, rhs: Self) -> Self;
+
+    /// Product of the raw values, not of the normalized ones, clamped to
+    /// [`MIN`, `MAX`], so `100_u8 * 3` is 255 and `0.5 * 3.0` is 1.0
+    fn saturating_mul(self, rhs: Self) -> Self;
+
+    /// Same intensity in another channel type, a plain copy for the same type
+    #[inline]
+    fn from_channel<C: Channel>(value: C) -> Self {
+        match (&value as &dyn Any).downcast_ref::<Self>() {
+            Some(same) => *same,
+            None => Self::from_f64(value.to_f64()),
+        }
+    }
+
+    #[inline]
+    fn clamp(self) -> Self {
+        if self < Self::MIN {
+            Self::MIN
+        } else if self > Self::MAX {
+            Self::MAX
+        } else {
+            self
+        }
+    }
+}
+
+macro_rules! channel_int {
+    ($($t:ty),*) => {
+        $(
+            impl Channel for $t {
+                const MAX: Self = <$t>::MAX;
+                const MIN: Self = 0;
+
+                #[inline]
+                fn to_f32(self) -> f32 {
+                    self as f32 / <$t>::MAX as f32
+                }
+
+                #[inline]
+                fn to_f64(self) -> f64 {
+                    self as f64 / <$t>::MAX as f64
+                }
+
+                #[inline]
+                fn from_f32(value: f32) -> Self {
+                    // `as` saturates and maps NaN to 0
+                    (value * <$t>::MAX as f32).round() as $t
+                }
+
+                #[inline]
+                fn from_f64(value: f64) -> Self {
+                    (value * <$t>::MAX as f64).round() as $t
+                }
+
+                #[inline]
+                fn saturating_add(self, rhs: Self) -> Self {
+                    <$t>::saturating_add(self, rhs)
+                }
+
+                #[inline]
+                fn saturating_sub(self, rhs: Self) -> Self {
+                    <$t>::saturating_sub(self, rhs)
+                }
+
+                #[inline]
+                fn saturating_mul(self, rhs: Self) -> Self {
+                    <$t>::saturating_mul(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+channel_int! {u8, u16, u32}
+
+macro_rules! channel_float {
+    ($($t:ty),*) => {
+        $(
+            impl Channel for $t {
+                const MAX: Self = 1.0;
+                const MIN: Self = 0.0;
+
+                #[inline]
+                fn to_f32(self) -> f32 {
+                    self as f32
+                }
+
+                #[inline]
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+
+                #[inline]
+                fn from_f32(value: f32) -> Self {
+                    value as $t
+                }
+
+                #[inline]
+                fn from_f64(value: f64) -> Self {
+                    value as $t
+                }
+
+                #[inline]
+                fn saturating_add(self, rhs: Self) -> Self {
+                    Channel::clamp(self + rhs)
+                }
+
+                #[inline]
+                fn saturating_sub(self, rhs: Self) -> Self {
+                    Channel::clamp(self - rhs)
+                }
+
+                #[inline]
+                fn saturating_mul(self, rhs: Self) -> Self {
+                    Channel::clamp(self * rhs)
+                }
+            }
+        )*
+    };
+}
+
+channel_float! {f32, f64}
+
+impl Channel for f16 {
+    const MAX: Self = f16::ONE;
+    const MIN: Self = f16::ZERO;
+
+    #[inline]
+    fn to_f32(self) -> f32 {
+        f16::to_f32(self)
+    }
+
+    #[inline]
+    fn to_f64(self) -> f64 {
+        f16::to_f64(self)
+    }
+
+    #[inline]
+    fn from_f32(value: f32) -> Self {
+        f16::from_f32(value)
+    }
+
+    #[inline]
+    fn from_f64(value: f64) -> Self {
+        f16::from_f64(value)
+    }
+
+    #[inline]
+    fn saturating_add(self, rhs: Self) -> Self {
+        Channel::clamp(self + rhs)
+    }
+
+    #[inline]
+    fn saturating_sub(self, rhs: Self) -> Self {
+        Channel::clamp(self - rhs)
+    }
+
+    #[inline]
+    fn saturating_mul(self, rhs: Self) -> Self {
+        Channel::clamp(self * rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize() {
+        assert_eq!(255_u8.to_f32(), 1.0);
+        assert_eq!(0_u8.to_f64(), 0.0);
+        assert_eq!(u16::MAX.to_f64(), 1.0);
+        assert_eq!(u32::MAX.to_f64(), 1.0);
+        assert_eq!(0.25_f32.to_f64(), 0.25);
+        assert_eq!(f16::ONE.to_f32(), 1.0);
+    }
+
+    #[test]
+    fn from_normalized() {
+        assert_eq!(u8::from_f32(0.5), 128);
+        assert_eq!(u8::from_f64(0.25), 64);
+        assert_eq!(u8::from_f32(2.0), 255);
+        assert_eq!(u8::from_f32(-1.0), 0);
+        assert_eq!(u8::from_f32(f32::NAN), 0);
+        assert_eq!(u16::from_f64(1.0), u16::MAX);
+        assert_eq!(u32::from_f64(0.5), 2_147_483_648);
+        assert_eq!(f32::from_f64(4.0), 4.0);
+        assert_eq!(f16::from_f32(0.5), f16::from_f32(0.5));
+    }
+
+    #[test]
+    fn from_channel() {
+        for x in 0..=255_u8 {
+            assert_eq!(u16::from_channel(x), x as u16 * 257);
+            assert_eq!(u8::from_channel(u16::from_channel(x)), x);
+            assert_eq!(u8::from_channel(f32::from_channel(x)), x);
+            assert_eq!(u8::from_channel(f16::from_channel(x)), x);
+        }
+        assert_eq!(u8::from_channel(u32::MAX), 255);
+        assert_eq!(f64::from_channel(u16::MAX), 1.0);
+        // same types are copied, out of range floats included
+        assert_eq!(u32::from_channel(u32::MAX - 1), u32::MAX - 1);
+        assert_eq!(f32::from_channel(-2.5_f32), -2.5);
+    }
+
+    #[test]
+    fn clamp() {
+        assert_eq!(Channel::clamp(1.5_f32), 1.0);
+        assert_eq!(Channel::clamp(-0.5_f64), 0.0);
+        assert_eq!(Channel::clamp(0.5_f64), 0.5);
+        assert_eq!(Channel::clamp(f16::from_f32(3.0)), f16::ONE);
+        assert_eq!(Channel::clamp(200_u8), 200);
+    }
+
+    #[test]
+    fn saturating() {
+        assert_eq!(Channel::saturating_add(200_u8, 100), 255);
+        assert_eq!(Channel::saturating_sub(100_u16, 200), 0);
+        assert_eq!(Channel::saturating_mul(100_u8, 3), 255);
+        assert_eq!(Channel::saturating_mul(0.5_f32, 3.0), 1.0);
+        assert_eq!(Channel::saturating_mul(0.5_f64, 0.5), 0.25);
+        assert_eq!(Channel::saturating_add(0.75_f32, 0.5), 1.0);
+        assert_eq!(Channel::saturating_sub(0.25_f64, 0.5), 0.0);
+        assert_eq!(Channel::saturating_sub(f16::ONE, f16::MAX), f16::ZERO);
+    }
+}
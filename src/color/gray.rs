@@ -0,0 +1,97 @@
+//! Single channel BT.601 luma of RGB/BGR pixels
+
+use super::{bgr::BGR, f16, rgb::RGB, Channel};
+use super::{BGR16, BGR32, BGR64, BGR8, RGB16, RGB32, RGB64, RGB8};
+
+/// Normalized BT.601 luma, single channel pixels are their own luma
+pub trait Luma: Copy {
+    fn luma(&self) -> f64;
+}
+
+macro_rules! luma_three {
+    ($($t:ident),*) => {
+        $(
+            impl<T: Channel> Luma for $t<T> {
+                #[inline]
+                fn luma(&self) -> f64 {
+                    0.299 * self.r.to_f64() + 0.587 * self.g.to_f64() + 0.114 * self.b.to_f64()
+                }
+            }
+        )*
+    };
+}
+
+luma_three!(RGB, BGR);
+
+macro_rules! luma_scalar {
+    ($($t:ty),*) => {
+        $(
+            impl Luma for $t {
+                #[inline]
+                fn luma(&self) -> f64 {
+                    self.to_f64()
+                }
+            }
+        )*
+    };
+}
+
+luma_scalar!(u8, u16, u32, f16, f32, f64);
+
+#[inline]
+pub(crate) fn luma8(r: u8, g: u8, b: u8) -> u8 {
+    ((77 * r as u32 + 150 * g as u32 + 29 * b as u32 + 128) >> 8) as u8
+}
+
+#[inline]
+fn luma16(r: u16, g: u16, b: u16) -> u16 {
+    ((19595 * r as u64 + 38470 * g as u64 + 7471 * b as u64 + 32768) >> 16) as u16
+}
+
+macro_rules! luma {
+    ($($src:ident => $dst:ty: $f:expr),*) => {
+        $(
+            impl From<$src> for $dst {
+                #[inline]
+                fn from(src: $src) -> Self {
+                    $f(src.r, src.g, src.b)
+                }
+            }
+        )*
+    };
+}
+
+luma!(
+    RGB8 => u8: luma8,
+    BGR8 => u8: luma8,
+    RGB16 => u16: luma16,
+    BGR16 => u16: luma16,
+    RGB32 => f32: |r: f32, g: f32, b: f32| 0.299 * r + 0.587 * g + 0.114 * b,
+    BGR32 => f32: |r: f32, g: f32, b: f32| 0.299 * r + 0.587 * g + 0.114 * b,
+    RGB64 => f64: |r: f64, g: f64, b: f64| 0.299 * r + 0.587 * g + 0.114 * b,
+    BGR64 => f64: |r: f64, g: f64, b: f64| 0.299 * r + 0.587 * g + 0.114 * b
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn luma() {
+        assert_eq!(u8::from(RGB8::from([255, 255, 255])), 255);
+        assert_eq!(u8::from(BGR8::from([0, 0, 255])), 77);
+        assert_eq!(u16::from(RGB16::from([65535, 65535, 65535])), 65535);
+        assert_eq!(u16::from(RGB16::from([0, 65535, 0])), 38469);
+        assert!((f32::from(RGB32::from([1.0, 1.0, 1.0])) - 1.0).abs() < 1e-6);
+        assert!((f64::from(BGR64::from([1.0, 0.0, 0.0])) - 0.114).abs() < 1e-12);
+    }
+
+    #[test]
+    fn normalized() {
+        assert!((RGB8::from([255, 255, 255]).luma() - 1.0).abs() < 1e-12);
+        assert!((BGR8::from([0, 0, 255]).luma() - 0.299).abs() < 1e-12);
+        assert!((RGB16::from([0, 65535, 0]).luma() - 0.587).abs() < 1e-12);
+        assert_eq!(51_u8.luma(), 0.2);
+        assert_eq!(0.75_f32.luma(), 0.75);
+    }
+}
@@ -0,0 +1,326 @@
+use std::{
+    cmp::Ordering,
+    fmt,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+/// IEEE 754 half-precision (binary16) floating point number
+#[allow(non_camel_case_types)]
+#[derive(Default, Clone, Copy)]
+#[repr(transparent)]
+pub struct f16(u16);
+
+impl f16 {
+    pub const ZERO: f16 = f16(0x0000);
+    pub const ONE: f16 = f16(0x3C00);
+    pub const MAX: f16 = f16(0x7BFF);
+    pub const MIN: f16 = f16(0xFBFF);
+    pub const MIN_POSITIVE: f16 = f16(0x0400);
+    pub const EPSILON: f16 = f16(0x1400);
+    pub const INFINITY: f16 = f16(0x7C00);
+    pub const NEG_INFINITY: f16 = f16(0xFC00);
+    pub const NAN: f16 = f16(0x7E00);
+
+    #[inline]
+    pub const fn from_bits(bits: u16) -> Self {
+        Self(bits)
+    }
+
+    #[inline]
+    pub const fn to_bits(self) -> u16 {
+        self.0
+    }
+
+    /// Round to nearest, ties to even
+    pub fn from_f32(value: f32) -> Self {
+        let bits = value.to_bits();
+        let sign = ((bits >> 16) & 0x8000) as u16;
+        let exp = ((bits >> 23) & 0xFF) as i32;
+        let man = (bits & 0x007F_FFFF) as u64;
+
+        if exp == 0xFF {
+            return Self(sign | nan_or_infinity((man >> 13) as u16, man != 0));
+        }
+        Self(sign | pack(exp - 127, man | 0x0080_0000, 23, exp == 0))
+    }
+
+    /// Round to nearest, ties to even
+    pub fn from_f64(value: f64) -> Self {
+        let bits = value.to_bits();
+        let sign = ((bits >> 48) & 0x8000) as u16;
+        let exp = ((bits >> 52) & 0x7FF) as i32;
+        let man = bits & 0x000F_FFFF_FFFF_FFFF;
+
+        if exp == 0x7FF {
+            return Self(sign | nan_or_infinity((man >> 42) as u16, man != 0));
+        }
+        Self(sign | pack(exp - 1023, man | 0x0010_0000_0000_0000, 52, exp == 0))
+    }
+
+    pub fn to_f32(self) -> f32 {
+        let sign = ((self.0 & 0x8000) as u32) << 16;
+        let exp = ((self.0 >> 10) & 0x1F) as u32;
+        let man = (self.0 & 0x03FF) as u32;
+
+        let bits = match exp {
+            0 if man == 0 => sign,
+            0 => {
+                // subnormal, renormalize for f32
+                let shift = man.leading_zeros() - 21;
+                let man = (man << shift) & 0x03FF;
+                sign | ((127 - 15 + 1 - shift) << 23) | (man << 13)
+            }
+            0x1F if man == 0 => sign | 0x7F80_0000,
+            0x1F => sign | 0x7FC0_0000 | (man << 13),
+            _ => sign | ((exp + 127 - 15) << 23) | (man << 13),
+        };
+        f32::from_bits(bits)
+    }
+
+    #[inline]
+    pub fn to_f64(self) -> f64 {
+        self.to_f32() as f64
+    }
+
+    #[inline]
+    pub fn is_nan(self) -> bool {
+        self.0 & 0x7C00 == 0x7C00 && self.0 & 0x03FF != 0
+    }
+
+    #[inline]
+    pub fn is_infinite(self) -> bool {
+        self.0 & 0x7FFF == 0x7C00
+    }
+
+    #[inline]
+    pub fn is_finite(self) -> bool {
+        self.0 & 0x7C00 != 0x7C00
+    }
+
+    /// Reinterpret packed halves as their bit patterns
+    pub fn slice_to_bits(src: &[f16]) -> &[u16] {
+        // f16 is #[repr(transparent)] over u16
+        unsafe { std::slice::from_raw_parts(src.as_ptr() as *const u16, src.len()) }
+    }
+}
+
+fn nan_or_infinity(man: u16, nan: bool) -> u16 {
+    if nan {
+        0x7E00 | man
+    } else {
+        0x7C00
+    }
+}
+
+/// `man` carries the implicit leading bit at position `man_bits`
+fn pack(exp: i32, man: u64, man_bits: u32, zero_exp: bool) -> u16 {
+    if zero_exp {
+        // source subnormals are far below the half range
+        return 0;
+    }
+
+    let half_exp = exp + 15;
+    if half_exp >= 0x1F {
+        return 0x7C00;
+    }
+
+    let (shift, half_exp) = if half_exp <= 0 {
+        (man_bits - 10 + (1 - half_exp) as u32, 0)
+    } else {
+        (man_bits - 10, half_exp as u64)
+    };
+    if shift > man_bits + 1 {
+        return 0;
+    }
+
+    let mut half = man >> shift;
+    if half_exp > 0 {
+        half = (half & 0x03FF) | (half_exp << 10);
+    }
+
+    let round_bit = 1_u64 << (shift - 1);
+    if man & round_bit != 0 && man & (3 * round_bit - 1) != 0 {
+        // carry may spill into the exponent, which is the correct result
+        half += 1;
+    }
+    half as u16
+}
+
+impl From<f16> for f32 {
+    #[inline]
+    fn from(src: f16) -> Self {
+        src.to_f32()
+    }
+}
+
+impl From<f16> for f64 {
+    #[inline]
+    fn from(src: f16) -> Self {
+        src.to_f64()
+    }
+}
+
+impl PartialEq for f16 {
+    fn eq(&self, other: &Self) -> bool {
+        self.to_f32() == other.to_f32()
+    }
+}
+
+impl PartialOrd for f16 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.to_f32().partial_cmp(&other.to_f32())
+    }
+}
+
+impl fmt::Debug for f16 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.to_f32(), f)
+    }
+}
+
+impl fmt::Display for f16 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.to_f32(), f)
+    }
+}
+
+macro_rules! f16_op {
+    ($($tr:ident $f:ident $op:tt),*) => {
+        $(
+            impl $tr for f16 {
+                type Output = Self;
+                #[inline]
+                fn $f(self, rhs: Self) -> Self::Output {
+                    Self::from_f32(self.to_f32() $op rhs.to_f32())
+                }
+            }
+        )*
+    };
+}
+
+f16_op! {Add add +, Sub sub -, Mul mul *, Div div /}
+
+impl Neg for f16 {
+    type Output = Self;
+    #[inline]
+    fn neg(self) -> Self::Output {
+        Self(self.0 ^ 0x8000)
+    }
+}
+
+// Readable formats such as JSON get the value, binary ones the raw bits
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for f16 {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_f32(self.to_f32())
+        } else {
+            serializer.serialize_u16(self.0)
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for f16 {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            f32::deserialize(deserializer).map(Self::from_f32)
+        } else {
+            u16::deserialize(deserializer).map(Self)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn constants() {
+        assert_eq!(f16::ZERO.to_f32(), 0.0);
+        assert_eq!(f16::ONE.to_f32(), 1.0);
+        assert_eq!(f16::MAX.to_f32(), 65504.0);
+        assert_eq!(f16::MIN.to_f32(), -65504.0);
+        assert_eq!(f16::MIN_POSITIVE.to_f32(), 6.103_515_6e-5);
+        assert_eq!(f16::EPSILON.to_f32(), 0.000_976_562_5);
+        assert_eq!(f16::INFINITY.to_f32(), f32::INFINITY);
+        assert_eq!(f16::NEG_INFINITY.to_f32(), f32::NEG_INFINITY);
+        assert!(f16::NAN.to_f32().is_nan());
+    }
+
+    #[test]
+    fn from_f32() {
+        assert_eq!(f16::from_f32(0.0).to_bits(), 0x0000);
+        assert_eq!(f16::from_f32(-0.0).to_bits(), 0x8000);
+        assert_eq!(f16::from_f32(1.0).to_bits(), 0x3C00);
+        assert_eq!(f16::from_f32(-2.0).to_bits(), 0xC000);
+        assert_eq!(f16::from_f32(0.5).to_bits(), 0x3800);
+        assert_eq!(f16::from_f32(65504.0).to_bits(), 0x7BFF);
+        assert_eq!(f16::from_f32(1.0e6).to_bits(), 0x7C00);
+        assert_eq!(f16::from_f32(-1.0e6).to_bits(), 0xFC00);
+        assert_eq!(f16::from_f32(f32::INFINITY).to_bits(), 0x7C00);
+        assert!(f16::from_f32(f32::NAN).is_nan());
+
+        // smallest subnormal and underflow
+        assert_eq!(f16::from_f32(5.960_464_5e-8).to_bits(), 0x0001);
+        assert_eq!(f16::from_f32(1.0e-10).to_bits(), 0x0000);
+    }
+
+    #[test]
+    fn from_f32_rounding() {
+        // 1 + 2^-11 is halfway between 1.0 and the next half, ties to even
+        assert_eq!(f16::from_f32(1.0 + 0.000_488_281_25).to_bits(), 0x3C00);
+        // 1 + 3 * 2^-11 is halfway between two halves, the even one is above
+        assert_eq!(f16::from_f32(1.0 + 0.001_464_843_8).to_bits(), 0x3C02);
+        // rounding overflows the mantissa into the exponent
+        assert_eq!(f16::from_f32(1.999_9).to_bits(), 0x4000);
+        // the largest finite half is reached by rounding
+        assert_eq!(f16::from_f32(65519.0).to_bits(), 0x7BFF);
+        assert_eq!(f16::from_f32(65520.0).to_bits(), 0x7C00);
+    }
+
+    #[test]
+    fn from_f64() {
+        assert_eq!(f16::from_f64(1.0).to_bits(), 0x3C00);
+        assert_eq!(f16::from_f64(-0.25).to_bits(), 0xB400);
+        assert_eq!(f16::from_f64(65504.0).to_bits(), 0x7BFF);
+        assert_eq!(f16::from_f64(1.0e300).to_bits(), 0x7C00);
+        assert_eq!(f16::from_f64(5.960_464_477_539_063e-8).to_bits(), 0x0001);
+        assert!(f16::from_f64(f64::NAN).is_nan());
+    }
+
+    #[test]
+    fn round_trip() {
+        for bits in 0..=u16::MAX {
+            let half = f16::from_bits(bits);
+            if half.is_nan() {
+                assert!(f16::from_f32(half.to_f32()).is_nan());
+                assert!(f16::from_f64(half.to_f64()).is_nan());
+                continue;
+            }
+            assert_eq!(f16::from_f32(half.to_f32()).to_bits(), bits);
+            assert_eq!(f16::from_f64(half.to_f64()).to_bits(), bits);
+        }
+    }
+
+    #[test]
+    fn ops() {
+        let a = f16::from_f32(1.5);
+        let b = f16::from_f32(0.5);
+        assert_eq!(a + b, f16::from_f32(2.0));
+        assert_eq!(a - b, f16::ONE);
+        assert_eq!(a * b, f16::from_f32(0.75));
+        assert_eq!(a / b, f16::from_f32(3.0));
+        assert_eq!(-a, f16::from_f32(-1.5));
+        assert!(a > b);
+        assert_eq!(f16::ZERO, -f16::ZERO);
+        assert_ne!(f16::NAN, f16::NAN);
+    }
+
+    #[test]
+    fn slice_to_bits() {
+        let halves = [f16::ONE, f16::ZERO, f16::INFINITY];
+        assert_eq!(f16::slice_to_bits(&halves), &[0x3C00, 0x0000, 0x7C00]);
+    }
+}
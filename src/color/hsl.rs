@@ -1,6 +1,10 @@
-use super::{rgb_hsl::rgb_to_hsl, Convert, BGR32, BGR64, BGR8, RGB32, RGB64, RGB8};
+use super::{
+    rgb_hsl::rgb_to_hsl, Convert, Normalized, BGR16F, BGR32, BGR64, BGR8, RGB16F, RGB32, RGB64,
+    RGB8,
+};
 
 #[derive(Default, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HSL {
     /// Hue
     pub h: f64,
@@ -20,6 +24,11 @@ impl Convert for HSL {
         rgb_to_hsl(rgb.r, rgb.g, rgb.b)
     }
 
+    fn from_rgb16f(src: RGB16F) -> Self {
+        let rgb = RGB64::from_rgb16f(src);
+        rgb_to_hsl(rgb.r, rgb.g, rgb.b)
+    }
+
     fn from_rgb32(src: RGB32) -> Self {
         let rgb = RGB64::from_rgb32(src);
         rgb_to_hsl(rgb.r, rgb.g, rgb.b)
@@ -30,6 +39,11 @@ impl Convert for HSL {
         rgb_to_hsl(rgb.r, rgb.g, rgb.b)
     }
 
+    fn from_bgr16f(src: BGR16F) -> Self {
+        let rgb = RGB64::from_bgr16f(src);
+        rgb_to_hsl(rgb.r, rgb.g, rgb.b)
+    }
+
     fn from_bgr32(src: BGR32) -> Self {
         let rgb = RGB64::from_bgr32(src);
         rgb_to_hsl(rgb.r, rgb.g, rgb.b)
@@ -44,6 +58,25 @@ impl Convert for HSL {
     }
 }
 
+impl Normalized for HSL {
+    const CHANNELS: usize = 3;
+    const HUE: Option<usize> = Some(0);
+
+    #[inline]
+    fn normalize(&self, out: &mut [f64]) {
+        out[0] = self.h / 360.0;
+        out[1] = self.s;
+        out[2] = self.l;
+    }
+
+    #[inline]
+    fn denormalize(&mut self, values: &[f64]) {
+        self.h = values[0] * 360.0;
+        self.s = values[1];
+        self.l = values[2];
+    }
+}
+
 impl From<[f64; 3]> for HSL {
     fn from(src: [f64; 3]) -> Self {
         Self {
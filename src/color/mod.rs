@@ -1,14 +1,37 @@
 pub(crate) mod rgb_hsl;
 
 pub(crate) mod bgr;
-pub use bgr::{BGR32, BGR64, BGR8};
+pub use bgr::{BGR16, BGR16F, BGR32, BGR64, BGR8};
 
 pub(crate) mod rgb;
-pub use rgb::{RGB32, RGB64, RGB8};
+pub use rgb::{RGB16, RGB16F, RGB32, RGB64, RGB8};
+
+mod channel;
+pub use channel::Channel;
+
+mod gray;
+pub use gray::Luma;
+
+mod pixel;
+pub use pixel::{Normalized, Pixel};
+
+mod pod;
+pub(crate) use pod::cast_vec;
+pub use pod::{cast_slice, cast_slice_mut, Pod};
+
+mod slice;
+pub use slice::convert_slice;
+pub(crate) use slice::convert_vec;
+
+mod half;
+pub use half::f16;
 
 mod hsl;
 pub use hsl::HSL;
 
+pub mod packed;
+pub use packed::{ARGB1555, ARGB4444, BGR565, RGB332, RGB555, RGB565};
+
 pub trait ComponentsCount {
     type Component;
 
@@ -18,12 +41,16 @@ pub trait ComponentsCount {
 pub trait Convert {
     fn from_rgb8(_: RGB8) -> Self;
 
+    fn from_rgb16f(_: RGB16F) -> Self;
+
     fn from_rgb32(_: RGB32) -> Self;
 
     fn from_rgb64(_: RGB64) -> Self;
 
     fn from_bgr8(_: BGR8) -> Self;
 
+    fn from_bgr16f(_: BGR16F) -> Self;
+
     fn from_bgr32(_: BGR32) -> Self;
 
     fn from_bgr64(_: BGR64) -> Self;
@@ -36,6 +63,10 @@ macro_rules! convert {
         Self::from_rgb8
     );
 
+    (RGB16F) => (
+        Self::from_rgb16f
+    );
+
     (RGB32) => (
         Self::from_rgb32
     );
@@ -48,6 +79,10 @@ macro_rules! convert {
         Self::from_bgr8
     );
 
+    (BGR16F) => (
+        Self::from_bgr16f
+    );
+
     (BGR32) => (
         Self::from_bgr32
     );
@@ -71,12 +106,21 @@ macro_rules! convert {
     };
 }
 
-convert!(RGB8, RGB32, RGB64, BGR8, BGR32, BGR64, HSL);
-convert!(RGB32, RGB8, RGB64, BGR8, BGR32, BGR64, HSL);
-convert!(RGB64, RGB8, RGB32, BGR8, BGR32, BGR64, HSL);
+convert!(RGB8, RGB16F, RGB32, RGB64, BGR8, BGR16F, BGR32, BGR64, HSL);
+convert!(RGB16F, RGB8, RGB32, RGB64, BGR8, BGR16F, BGR32, BGR64, HSL);
+convert!(RGB32, RGB8, RGB16F, RGB64, BGR8, BGR16F, BGR32, BGR64, HSL);
+convert!(RGB64, RGB8, RGB16F, RGB32, BGR8, BGR16F, BGR32, BGR64, HSL);
+
+convert!(BGR8, RGB8, RGB16F, RGB32, RGB64, BGR16F, BGR32, BGR64, HSL);
+convert!(BGR16F, RGB8, RGB16F, RGB32, RGB64, BGR8, BGR32, BGR64, HSL);
+convert!(BGR32, RGB8, RGB16F, RGB32, RGB64, BGR8, BGR16F, BGR64, HSL);
+convert!(BGR64, RGB8, RGB16F, RGB32, RGB64, BGR8, BGR16F, BGR32, HSL);
 
-convert!(BGR8, RGB8, RGB32, RGB64, BGR32, BGR64, HSL);
-convert!(BGR32, RGB8, RGB32, RGB64, BGR8, BGR64, HSL);
-convert!(BGR64, RGB8, RGB32, RGB64, BGR8, BGR32, HSL);
+convert!(HSL, RGB8, RGB16F, RGB32, RGB64, BGR8, BGR16F, BGR32, BGR64);
 
-convert!(HSL, RGB8, RGB32, RGB64, BGR8, BGR32, BGR64);
+convert!(RGB565, RGB8, RGB16F, RGB32, RGB64, BGR8, BGR16F, BGR32, BGR64, HSL);
+convert!(BGR565, RGB8, RGB16F, RGB32, RGB64, BGR8, BGR16F, BGR32, BGR64, HSL);
+convert!(RGB555, RGB8, RGB16F, RGB32, RGB64, BGR8, BGR16F, BGR32, BGR64, HSL);
+convert!(ARGB1555, RGB8, RGB16F, RGB32, RGB64, BGR8, BGR16F, BGR32, BGR64, HSL);
+convert!(ARGB4444, RGB8, RGB16F, RGB32, RGB64, BGR8, BGR16F, BGR32, BGR64, HSL);
+convert!(RGB332, RGB8, RGB16F, RGB32, RGB64, BGR8, BGR16F, BGR32, BGR64, HSL);
@@ -0,0 +1,478 @@
+use super::{
+    rgb_hsl::hsl_to_rgb, ComponentsCount, Convert, Normalized, BGR16F, BGR32, BGR64, BGR8, HSL,
+    RGB16F, RGB32, RGB64, RGB8,
+};
+use crate::error::IncorrectData;
+
+/// Pixel formats packed into a single integer word
+pub trait Packed: Copy {
+    /// Size of the packed word in bytes
+    const SIZE: usize;
+
+    fn write_le(self, dst: &mut Vec<u8>);
+
+    fn write_be(self, dst: &mut Vec<u8>);
+
+    /// `src` is exactly `SIZE` bytes long
+    fn read_le(src: &[u8]) -> Self;
+
+    /// `src` is exactly `SIZE` bytes long
+    fn read_be(src: &[u8]) -> Self;
+}
+
+pub fn to_le_bytes<T: Packed>(src: &[T]) -> Vec<u8> {
+    let mut dst = Vec::with_capacity(src.len() * T::SIZE);
+    src.iter().for_each(|x| x.write_le(&mut dst));
+    dst
+}
+
+pub fn to_be_bytes<T: Packed>(src: &[T]) -> Vec<u8> {
+    let mut dst = Vec::with_capacity(src.len() * T::SIZE);
+    src.iter().for_each(|x| x.write_be(&mut dst));
+    dst
+}
+
+pub fn from_le_bytes<T: Packed>(src: &[u8]) -> Result<Vec<T>, IncorrectData> {
+    check_len::<T>(src)?;
+    Ok(src.chunks_exact(T::SIZE).map(T::read_le).collect())
+}
+
+pub fn from_be_bytes<T: Packed>(src: &[u8]) -> Result<Vec<T>, IncorrectData> {
+    check_len::<T>(src)?;
+    Ok(src.chunks_exact(T::SIZE).map(T::read_be).collect())
+}
+
+fn check_len<T: Packed>(src: &[u8]) -> Result<(), IncorrectData> {
+    if !src.len().is_multiple_of(T::SIZE) {
+        return Err(IncorrectData::Size {
+            expected: src.len() - src.len() % T::SIZE + T::SIZE,
+            got: src.len(),
+        });
+    }
+    Ok(())
+}
+
+#[inline]
+const fn mask(width: u32) -> u32 {
+    (1 << width) - 1
+}
+
+/// Scale a `width` bit value to 8 bits by repeating its bit pattern
+#[inline]
+fn expand(value: u32, width: u32) -> u8 {
+    if width == 0 {
+        return 0;
+    }
+    let mut x = value << (8 - width);
+    let mut filled = width;
+    while filled < 8 {
+        x |= x >> filled;
+        filled *= 2;
+    }
+    x as u8
+}
+
+#[inline]
+fn quantize_byte(value: u8, width: u32) -> u32 {
+    (value as u32 * mask(width) + 127) / 255
+}
+
+#[inline]
+fn quantize_percent(value: f64, width: u32) -> u32 {
+    (value.clamp(0.0, 1.0) * mask(width) as f64).round() as u32
+}
+
+macro_rules! packed {
+    (
+        $(#[$meta:meta])*
+        $name:ident($bits:ty),
+        r: ($rs:expr, $rw:expr),
+        g: ($gs:expr, $gw:expr),
+        b: ($bs:expr, $bw:expr),
+        a: ($as:expr, $aw:expr)
+    ) => {
+        $(#[$meta])*
+        #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+        #[cfg_attr(feature = "serde", serde(transparent))]
+        #[repr(transparent)]
+        pub struct $name(pub $bits);
+
+        impl $name {
+            /// Channels at the native depth of the format, alpha is opaque
+            pub const fn new(r: u8, g: u8, b: u8) -> Self {
+                Self(
+                    (((r as u32 & mask($rw)) << $rs)
+                        | ((g as u32 & mask($gw)) << $gs)
+                        | ((b as u32 & mask($bw)) << $bs)
+                        | (mask($aw) << $as)) as $bits,
+                )
+            }
+
+            /// Red at the native depth of the format
+            #[inline]
+            pub const fn r(self) -> u8 {
+                ((self.0 as u32 >> $rs) & mask($rw)) as u8
+            }
+
+            /// Green at the native depth of the format
+            #[inline]
+            pub const fn g(self) -> u8 {
+                ((self.0 as u32 >> $gs) & mask($gw)) as u8
+            }
+
+            /// Blue at the native depth of the format
+            #[inline]
+            pub const fn b(self) -> u8 {
+                ((self.0 as u32 >> $bs) & mask($bw)) as u8
+            }
+
+            fn from_bytes(r: u8, g: u8, b: u8) -> Self {
+                Self::new(
+                    quantize_byte(r, $rw) as u8,
+                    quantize_byte(g, $gw) as u8,
+                    quantize_byte(b, $bw) as u8,
+                )
+            }
+
+            fn from_percents(r: f64, g: f64, b: f64) -> Self {
+                Self::new(
+                    quantize_percent(r, $rw) as u8,
+                    quantize_percent(g, $gw) as u8,
+                    quantize_percent(b, $bw) as u8,
+                )
+            }
+
+            fn to_bytes(self) -> [u8; 3] {
+                [
+                    expand(self.r() as u32, $rw),
+                    expand(self.g() as u32, $gw),
+                    expand(self.b() as u32, $bw),
+                ]
+            }
+
+            fn to_percents(self) -> [f64; 3] {
+                [
+                    self.r() as f64 / mask($rw) as f64,
+                    self.g() as f64 / mask($gw) as f64,
+                    self.b() as f64 / mask($bw) as f64,
+                ]
+            }
+        }
+
+        impl Packed for $name {
+            const SIZE: usize = std::mem::size_of::<$bits>();
+
+            #[inline]
+            fn write_le(self, dst: &mut Vec<u8>) {
+                dst.extend_from_slice(&self.0.to_le_bytes())
+            }
+
+            #[inline]
+            fn write_be(self, dst: &mut Vec<u8>) {
+                dst.extend_from_slice(&self.0.to_be_bytes())
+            }
+
+            #[inline]
+            fn read_le(src: &[u8]) -> Self {
+                let mut bytes = [0; std::mem::size_of::<$bits>()];
+                bytes.copy_from_slice(src);
+                Self(<$bits>::from_le_bytes(bytes))
+            }
+
+            #[inline]
+            fn read_be(src: &[u8]) -> Self {
+                let mut bytes = [0; std::mem::size_of::<$bits>()];
+                bytes.copy_from_slice(src);
+                Self(<$bits>::from_be_bytes(bytes))
+            }
+        }
+
+        impl ComponentsCount for $name {
+            type Component = $bits;
+
+            fn components_count() -> usize {
+                1
+            }
+        }
+
+        impl Normalized for $name {
+            const CHANNELS: usize = if $aw > 0 { 4 } else { 3 };
+            const ALPHA: Option<usize> = if $aw > 0 { Some(3) } else { None };
+
+            #[inline]
+            fn normalize(&self, out: &mut [f64]) {
+                out[..3].copy_from_slice(&self.to_percents());
+                if $aw > 0 {
+                    out[3] = ((self.0 as u32 >> $as) & mask($aw)) as f64 / mask($aw) as f64;
+                }
+            }
+
+            #[inline]
+            fn denormalize(&mut self, values: &[f64]) {
+                // `from_percents` makes the pixel opaque
+                let mut bits = Self::from_percents(values[0], values[1], values[2]).0 as u32;
+                if $aw > 0 {
+                    bits &= !(mask($aw) << $as);
+                    bits |= quantize_percent(values[3], $aw) << $as;
+                }
+                self.0 = bits as $bits;
+            }
+        }
+
+        impl Convert for $name {
+            fn from_rgb8(src: RGB8) -> Self {
+                Self::from_bytes(src.r, src.g, src.b)
+            }
+
+            fn from_rgb16f(src: RGB16F) -> Self {
+                Self::from_percents(src.r.to_f64(), src.g.to_f64(), src.b.to_f64())
+            }
+
+            fn from_rgb32(src: RGB32) -> Self {
+                Self::from_percents(src.r as f64, src.g as f64, src.b as f64)
+            }
+
+            fn from_rgb64(src: RGB64) -> Self {
+                Self::from_percents(src.r, src.g, src.b)
+            }
+
+            fn from_bgr8(src: BGR8) -> Self {
+                Self::from_bytes(src.r, src.g, src.b)
+            }
+
+            fn from_bgr16f(src: BGR16F) -> Self {
+                Self::from_percents(src.r.to_f64(), src.g.to_f64(), src.b.to_f64())
+            }
+
+            fn from_bgr32(src: BGR32) -> Self {
+                Self::from_percents(src.r as f64, src.g as f64, src.b as f64)
+            }
+
+            fn from_bgr64(src: BGR64) -> Self {
+                Self::from_percents(src.r, src.g, src.b)
+            }
+
+            fn from_hsl(src: HSL) -> Self {
+                Self::from_rgb64(hsl_to_rgb(src))
+            }
+        }
+
+        impl From<$name> for RGB8 {
+            fn from(src: $name) -> Self {
+                Self::from(src.to_bytes())
+            }
+        }
+
+        impl From<$name> for BGR8 {
+            fn from(src: $name) -> Self {
+                Self::from_rgb8(RGB8::from(src))
+            }
+        }
+
+        impl From<$name> for RGB64 {
+            fn from(src: $name) -> Self {
+                Self::from(src.to_percents())
+            }
+        }
+
+        impl From<$name> for HSL {
+            fn from(src: $name) -> Self {
+                Self::from_rgb64(RGB64::from(src))
+            }
+        }
+
+        packed_into!($name, RGB16F, RGB32, BGR16F, BGR32, BGR64);
+    };
+}
+
+macro_rules! packed_into {
+    ($name:ident, $($t:ident),*) => {
+        $(
+            impl From<$name> for $t {
+                fn from(src: $name) -> Self {
+                    Self::from_rgb64(RGB64::from(src))
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! packed_alpha {
+    ($($name:ident: ($as:expr, $aw:expr)),*) => {
+        $(
+            impl $name {
+                /// Alpha at the native depth of the format
+                #[inline]
+                pub const fn a(self) -> u8 {
+                    ((self.0 as u32 >> $as) & mask($aw)) as u8
+                }
+
+                pub const fn with_alpha(self, a: u8) -> Self {
+                    Self(
+                        ((self.0 as u32 & !(mask($aw) << $as)) | ((a as u32 & mask($aw)) << $as))
+                            as _,
+                    )
+                }
+            }
+        )*
+    };
+}
+
+packed! {
+    /// 5-6-5 bits, red in the most significant bits
+    RGB565(u16), r: (11, 5), g: (5, 6), b: (0, 5), a: (0, 0)
+}
+
+packed! {
+    /// 5-6-5 bits, blue in the most significant bits
+    BGR565(u16), r: (0, 5), g: (5, 6), b: (11, 5), a: (0, 0)
+}
+
+packed! {
+    /// 5-5-5 bits, the most significant bit is unused
+    RGB555(u16), r: (10, 5), g: (5, 5), b: (0, 5), a: (0, 0)
+}
+
+packed! {
+    /// 1-5-5-5 bits, alpha in the most significant bit
+    ARGB1555(u16), r: (10, 5), g: (5, 5), b: (0, 5), a: (15, 1)
+}
+
+packed! {
+    /// 4-4-4-4 bits, alpha in the most significant bits
+    ARGB4444(u16), r: (8, 4), g: (4, 4), b: (0, 4), a: (12, 4)
+}
+
+packed! {
+    /// 3-3-2 bits, red in the most significant bits
+    RGB332(u8), r: (5, 3), g: (2, 3), b: (0, 2), a: (0, 0)
+}
+
+packed_alpha!(ARGB1555: (15, 1), ARGB4444: (12, 4));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::display::{Buffer, ComponentsRaw, FrameMut};
+
+    #[test]
+    fn expand() {
+        for width in 1..=8 {
+            for value in 0..=mask(width) {
+                let byte = super::expand(value, width);
+                assert_eq!(quantize_byte(byte, width), value);
+            }
+            assert_eq!(super::expand(0, width), 0);
+            assert_eq!(super::expand(mask(width), width), 255);
+        }
+        assert_eq!(super::expand(0b10000, 5), 0b10000100);
+        assert_eq!(super::expand(0b100000, 6), 0b10000010);
+        assert_eq!(super::expand(0b100, 3), 0b10010010);
+        assert_eq!(super::expand(0b01, 2), 0x55);
+    }
+
+    #[test]
+    fn rgb565() {
+        assert_eq!(RGB565::from(RGB8::from([255, 255, 255])).0, 0xFFFF);
+        assert_eq!(RGB565::from(RGB8::from([255, 0, 0])).0, 0xF800);
+        assert_eq!(RGB565::from(RGB8::from([0, 255, 0])).0, 0x07E0);
+        assert_eq!(RGB565::from(RGB8::from([0, 0, 255])).0, 0x001F);
+        assert_eq!(RGB565::from(RGB32::from([0.0, 0.0, 1.0])).0, 0x001F);
+
+        let rgb = RGB8::from(RGB565(0xF800));
+        assert_eq!(rgb, RGB8::from([255, 0, 0]));
+        let rgb = RGB8::from(RGB565::new(16, 32, 1));
+        assert_eq!(rgb, RGB8::from([132, 130, 8]));
+    }
+
+    #[test]
+    fn bgr565() {
+        assert_eq!(BGR565::from(RGB8::from([255, 0, 0])).0, 0x001F);
+        assert_eq!(BGR565::from(BGR8::from([255, 0, 0])).0, 0xF800);
+        assert_eq!(BGR8::from(BGR565(0x001F)), BGR8::from([0, 0, 255]));
+    }
+
+    #[test]
+    fn rgb555() {
+        let rgb = RGB555::from(RGB8::from([255, 255, 255]));
+        assert_eq!(rgb.0, 0x7FFF);
+        assert_eq!(
+            RGB64::from(RGB555::new(31, 0, 0)),
+            RGB64::from([1.0, 0.0, 0.0])
+        );
+    }
+
+    #[test]
+    fn argb1555() {
+        let argb = ARGB1555::from(RGB8::from([0, 0, 0]));
+        assert_eq!(argb.0, 0x8000);
+        assert_eq!(argb.a(), 1);
+
+        let argb = argb.with_alpha(0);
+        assert_eq!(argb.0, 0x0000);
+        assert_eq!(RGB8::from(ARGB1555(0x7C00)), RGB8::from([255, 0, 0]));
+    }
+
+    #[test]
+    fn argb4444() {
+        let argb = ARGB4444::from(RGB8::from([255, 136, 0]));
+        assert_eq!(argb.0, 0xFF80);
+        assert_eq!(argb.with_alpha(7).a(), 7);
+        assert_eq!(RGB8::from(argb), RGB8::from([255, 136, 0]));
+    }
+
+    #[test]
+    fn rgb332() {
+        assert_eq!(RGB332::from(RGB8::from([255, 255, 255])).0, 0xFF);
+        assert_eq!(RGB332::from(RGB8::from([0, 0, 255])).0, 0x03);
+        assert_eq!(RGB8::from(RGB332(0xE0)), RGB8::from([255, 0, 0]));
+        assert_eq!(RGB8::from(RGB332(0x01)), RGB8::from([0, 0, 85]));
+    }
+
+    #[test]
+    fn convert() {
+        let hsl = HSL::from([120.0, 1.0, 0.5]);
+        assert_eq!(RGB565::from(hsl).0, 0x07E0);
+
+        let rgb: RGB32 = RGB565(0x07E0).into();
+        assert_eq!(rgb, RGB32::from([0.0, 1.0, 0.0]));
+
+        let bgr: BGR64 = RGB332(0x1C).into();
+        assert_eq!(bgr, BGR64::from([0.0, 1.0, 0.0]));
+    }
+
+    #[test]
+    fn bytes() {
+        let pixels = [RGB565(0x1234), RGB565(0xABCD)];
+        assert_eq!(to_le_bytes(&pixels), vec![0x34, 0x12, 0xCD, 0xAB]);
+        assert_eq!(to_be_bytes(&pixels), vec![0x12, 0x34, 0xAB, 0xCD]);
+
+        let le: Vec<RGB565> = from_le_bytes(&[0x34, 0x12, 0xCD, 0xAB]).unwrap();
+        assert_eq!(le, pixels);
+        let be: Vec<RGB565> = from_be_bytes(&[0x12, 0x34, 0xAB, 0xCD]).unwrap();
+        assert_eq!(be, pixels);
+
+        let odd = from_le_bytes::<RGB565>(&[0x34, 0x12, 0xCD]);
+        assert!(matches!(
+            odd,
+            Err(IncorrectData::Size {
+                expected: 4,
+                got: 3
+            })
+        ));
+
+        assert_eq!(to_be_bytes(&[RGB332(0xE3)]), vec![0xE3]);
+    }
+
+    #[test]
+    fn buffer() {
+        let mut buffer = Buffer::new(4, 2, RGB565::default()).unwrap();
+        *buffer.pixel_mut(1, 1) = RGB565::from(RGB8::from([255, 0, 0]));
+
+        let raw: &[u16] = buffer.raw();
+        assert_eq!(raw.len(), 8);
+        assert_eq!(raw[5], 0xF800);
+        assert_eq!(to_be_bytes(&buffer)[10..12], [0xF8, 0x00]);
+    }
+}
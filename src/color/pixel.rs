@@ -0,0 +1,165 @@
+use super::{bgr::BGR, f16, rgb::RGB, Channel, ComponentsCount};
+
+/// A pixel made of `CHANNELS` components of the same `Channel` type
+pub trait Pixel: Copy {
+    type Channel: Channel;
+
+    const CHANNELS: usize;
+
+    /// Index of the alpha channel, filters weight colors by it
+    const ALPHA: Option<usize> = None;
+
+    fn channels(&self) -> &[Self::Channel];
+
+    fn channels_mut(&mut self) -> &mut [Self::Channel];
+
+    /// Panics if `channels` is shorter than `CHANNELS`
+    fn from_channels(channels: &[Self::Channel]) -> Self;
+
+    fn map_channels<F: FnMut(Self::Channel) -> Self::Channel>(mut self, mut f: F) -> Self {
+        self.channels_mut().iter_mut().for_each(|c| *c = f(*c));
+        self
+    }
+}
+
+/// A pixel read and written as channels normalized to [0, 1]
+///
+/// Every `Pixel` is one, formats that cannot hand out their channels as a
+/// slice, like packed words or HSL, implement it on their own.
+pub trait Normalized: Copy {
+    const CHANNELS: usize;
+
+    /// Index of the alpha channel, filters weight colors by it
+    const ALPHA: Option<usize> = None;
+
+    /// Index of a channel holding an angle in turns, filters blend it on the
+    /// circle so 0 and 1 are the same value
+    const HUE: Option<usize> = None;
+
+    /// Writes the first `CHANNELS` values of `out`
+    fn normalize(&self, out: &mut [f64]);
+
+    /// Overwrites the channels with the first `CHANNELS` of `values`
+    fn denormalize(&mut self, values: &[f64]);
+}
+
+impl<P: Pixel> Normalized for P {
+    const CHANNELS: usize = <P as Pixel>::CHANNELS;
+    const ALPHA: Option<usize> = <P as Pixel>::ALPHA;
+
+    #[inline]
+    fn normalize(&self, out: &mut [f64]) {
+        for (o, c) in out.iter_mut().zip(self.channels()) {
+            *o = c.to_f64();
+        }
+    }
+
+    #[inline]
+    fn denormalize(&mut self, values: &[f64]) {
+        for (c, v) in self.channels_mut().iter_mut().zip(values) {
+            *c = P::Channel::from_f64(*v);
+        }
+    }
+}
+
+macro_rules! pixel_three {
+    ($($t:ident),*) => {
+        $(
+            impl<T: Channel> Pixel for $t<T> {
+                type Channel = T;
+
+                const CHANNELS: usize = 3;
+
+                #[inline]
+                fn channels(&self) -> &[T] {
+                    // #[repr(C)] with three fields of the same type
+                    unsafe { &*(self as *const Self as *const [T; 3]) }
+                }
+
+                #[inline]
+                fn channels_mut(&mut self) -> &mut [T] {
+                    unsafe { &mut *(self as *mut Self as *mut [T; 3]) }
+                }
+
+                #[inline]
+                fn from_channels(channels: &[T]) -> Self {
+                    Self::from([channels[0], channels[1], channels[2]])
+                }
+            }
+        )*
+    };
+}
+
+pixel_three!(RGB, BGR);
+
+macro_rules! pixel_scalar {
+    ($($t:ty),*) => {
+        $(
+            impl Pixel for $t {
+                type Channel = $t;
+
+                const CHANNELS: usize = 1;
+
+                #[inline]
+                fn channels(&self) -> &[$t] {
+                    std::slice::from_ref(self)
+                }
+
+                #[inline]
+                fn channels_mut(&mut self) -> &mut [$t] {
+                    std::slice::from_mut(self)
+                }
+
+                #[inline]
+                fn from_channels(channels: &[$t]) -> Self {
+                    channels[0]
+                }
+            }
+
+            impl ComponentsCount for $t {
+                type Component = $t;
+
+                fn components_count() -> usize {
+                    1
+                }
+            }
+        )*
+    };
+}
+
+pixel_scalar!(u8, u16, u32, f16, f32, f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::{BGR8, RGB32, RGB8};
+
+    #[test]
+    fn channels() {
+        let mut rgb = RGB8::from([1, 2, 3]);
+        assert_eq!(rgb.channels(), &[1, 2, 3]);
+        rgb.channels_mut()[0] = 7;
+        assert_eq!(rgb, RGB8::from([7, 2, 3]));
+
+        let bgr = BGR8::from([1, 2, 3]);
+        assert_eq!(bgr.channels(), &[1, 2, 3]);
+        assert_eq!(bgr.b, 1);
+
+        assert_eq!(5_u8.channels(), &[5]);
+    }
+
+    #[test]
+    fn from_channels() {
+        assert_eq!(
+            RGB32::from_channels(&[0.5, 1.0, 0.0]),
+            RGB32::from([0.5, 1.0, 0.0])
+        );
+        assert_eq!(f32::from_channels(&[0.5]), 0.5);
+    }
+
+    #[test]
+    fn map_channels() {
+        let rgb = RGB8::from([1, 2, 3]).map_channels(|c| c * 2);
+        assert_eq!(rgb, RGB8::from([2, 4, 6]));
+    }
+}
@@ -0,0 +1,168 @@
+use super::{bgr::BGR, f16, packed::*, rgb::RGB};
+use crate::error::IncorrectData;
+use std::{mem, slice};
+
+/// Plain old data, safe to reinterpret from and to bytes
+///
+/// # Safety
+///
+/// The type must have no padding, no invalid bit patterns and no pointers,
+/// which holds for the primitive numbers and for `#[repr(C)]` or
+/// `#[repr(transparent)]` structs made only of them.
+pub unsafe trait Pod: Copy + 'static {}
+
+macro_rules! pod {
+    ($($t:ty),*) => {
+        $(unsafe impl Pod for $t {})*
+    };
+}
+
+pod!(u8, u16, u32, u64, i8, i16, i32, i64, f32, f64, f16);
+pod!(RGB565, BGR565, RGB555, ARGB1555, ARGB4444, RGB332);
+
+unsafe impl<T: Pod> Pod for RGB<T> {}
+unsafe impl<T: Pod> Pod for BGR<T> {}
+
+/// Reinterpret `src` as a slice of `B` without copying
+///
+/// Fails with `IncorrectData::Size` when the bytes do not split into whole
+/// `B` values and with `IncorrectData::Alignment` when `src` is not aligned
+/// for `B`.
+pub fn cast_slice<A: Pod, B: Pod>(src: &[A]) -> Result<&[B], IncorrectData> {
+    let len = check_cast::<B>(src.as_ptr() as usize, mem::size_of_val(src))?;
+    // checked above, both types are Pod
+    Ok(unsafe { slice::from_raw_parts(src.as_ptr() as *const B, len) })
+}
+
+/// Mutable version of `cast_slice`
+pub fn cast_slice_mut<A: Pod, B: Pod>(src: &mut [A]) -> Result<&mut [B], IncorrectData> {
+    let len = check_cast::<B>(src.as_ptr() as usize, mem::size_of_val(src))?;
+    // checked above, both types are Pod
+    Ok(unsafe { slice::from_raw_parts_mut(src.as_mut_ptr() as *mut B, len) })
+}
+
+fn check_cast<B>(address: usize, bytes: usize) -> Result<usize, IncorrectData> {
+    let size = mem::size_of::<B>();
+    if !bytes.is_multiple_of(size) {
+        return Err(IncorrectData::Size {
+            expected: bytes - bytes % size,
+            got: bytes,
+        });
+    }
+
+    let align = mem::align_of::<B>();
+    if !address.is_multiple_of(align) {
+        return Err(IncorrectData::Alignment { expected: align });
+    }
+    Ok(bytes / size)
+}
+
+/// Reinterpret a `Vec` of `A` as a `Vec` of `B`, reusing the allocation when
+/// the layout allows it and copying otherwise
+///
+/// Fails with `IncorrectData::Size` when the bytes do not split into whole
+/// `B` values.
+pub(crate) fn cast_vec<A: Pod, B: Pod>(src: Vec<A>) -> Result<Vec<B>, IncorrectData> {
+    let bytes = mem::size_of_val(&src[..]);
+    let size = mem::size_of::<B>();
+    if size == 0 || !bytes.is_multiple_of(size) {
+        return Err(IncorrectData::Size {
+            expected: bytes - bytes % size.max(1),
+            got: bytes,
+        });
+    }
+
+    let len = bytes / size;
+    let cap = src.capacity() * mem::size_of::<A>();
+    if mem::align_of::<A>() == mem::align_of::<B>() && cap.is_multiple_of(size) {
+        let mut src = mem::ManuallyDrop::new(src);
+        // same alignment and the allocation holds a whole number of B
+        return Ok(unsafe { Vec::from_raw_parts(src.as_mut_ptr() as *mut B, len, cap / size) });
+    }
+
+    let mut dst: Vec<B> = Vec::with_capacity(len);
+    // every bit pattern is a valid B
+    unsafe {
+        std::ptr::copy_nonoverlapping(
+            src.as_ptr() as *const u8,
+            dst.as_mut_ptr() as *mut u8,
+            bytes,
+        );
+        dst.set_len(len);
+    }
+    Ok(dst)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::{BGR8, RGB32, RGB8};
+
+    #[test]
+    fn cast() {
+        let bytes = [1_u8, 2, 3, 4, 5, 6];
+        let rgb: &[RGB8] = cast_slice(&bytes).unwrap();
+        assert_eq!(rgb, &[RGB8::from([1, 2, 3]), RGB8::from([4, 5, 6])]);
+
+        let back: &[u8] = cast_slice(rgb).unwrap();
+        assert_eq!(back, &bytes);
+
+        let bgr: &[BGR8] = cast_slice(rgb).unwrap();
+        assert_eq!(bgr[0].b, 1);
+    }
+
+    #[test]
+    fn cast_mut() {
+        let mut bytes = [0_u8; 6];
+        let rgb: &mut [RGB8] = cast_slice_mut(&mut bytes).unwrap();
+        rgb[1].g = 9;
+        assert_eq!(bytes, [0, 0, 0, 0, 9, 0]);
+    }
+
+    #[test]
+    fn cast_size() {
+        let bytes = [0_u8; 7];
+        assert!(matches!(
+            cast_slice::<u8, RGB8>(&bytes),
+            Err(IncorrectData::Size {
+                expected: 6,
+                got: 7
+            })
+        ));
+    }
+
+    #[test]
+    fn cast_alignment() {
+        let floats = [RGB32::default(); 2];
+        let bytes: &[u8] = cast_slice(&floats).unwrap();
+        assert!(cast_slice::<u8, RGB32>(&bytes[..12]).is_ok());
+        assert!(matches!(
+            cast_slice::<u8, f32>(&bytes[1..5]),
+            Err(IncorrectData::Alignment { expected: 4 })
+        ));
+    }
+
+    #[test]
+    fn vec() {
+        let bytes = vec![1_u8, 2, 3, 4, 5, 6];
+        let ptr = bytes.as_ptr();
+        let rgb: Vec<RGB8> = cast_vec(bytes).unwrap();
+        assert_eq!(rgb, vec![RGB8::from([1, 2, 3]), RGB8::from([4, 5, 6])]);
+        assert_eq!(rgb.as_ptr() as *const u8, ptr);
+
+        let bytes = 1.5_f32.to_ne_bytes().to_vec();
+        let floats: Vec<f32> = cast_vec(bytes).unwrap();
+        assert_eq!(floats, vec![1.5]);
+
+        let floats: Vec<f32> = cast_vec(vec![RGB32::from([0.5, 1.0, 2.0])]).unwrap();
+        assert_eq!(floats, vec![0.5, 1.0, 2.0]);
+
+        assert!(matches!(
+            cast_vec::<u8, RGB8>(vec![0; 7]),
+            Err(IncorrectData::Size {
+                expected: 6,
+                got: 7
+            })
+        ));
+    }
+}
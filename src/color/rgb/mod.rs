@@ -1,15 +1,19 @@
+mod rgb16f;
 mod rgb32;
 mod rgb64;
 mod rgb8;
 
-use super::ComponentsCount;
-use std::ops::{Add, Div, Mul};
+use super::{bgr::BGR, f16, Channel, ComponentsCount};
+use std::ops::{Add, Div, Mul, Sub};
 
 pub type RGB8 = RGB<u8>;
+pub type RGB16 = RGB<u16>;
+pub type RGB16F = RGB<f16>;
 pub type RGB32 = RGB<f32>;
 pub type RGB64 = RGB<f64>;
 
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 pub struct RGB<T: Copy> {
     /// Red
@@ -22,19 +26,70 @@ pub struct RGB<T: Copy> {
 
 impl<T: Copy> RGB<T> {
     pub fn percent32_to_byte(percent: f32) -> u8 {
-        (percent * 255_f32).round() as u8
+        u8::from_f32(percent)
     }
 
     pub fn percent64_to_byte(percent: f64) -> u8 {
-        (percent * 255_f64).round() as u8
+        u8::from_f64(percent)
     }
 
     pub fn byte_to_percent32(byte: u8) -> f32 {
-        byte as f32 / 255_f32
+        byte.to_f32()
     }
 
     pub fn byte_to_percent64(byte: u8) -> f64 {
-        byte as f64 / 255_f64
+        byte.to_f64()
+    }
+}
+
+impl<T: Channel> RGB<T> {
+    /// Rescale every component to another channel depth
+    pub fn convert<U: Channel>(self) -> RGB<U> {
+        RGB {
+            r: U::from_channel(self.r),
+            g: U::from_channel(self.g),
+            b: U::from_channel(self.b),
+        }
+    }
+
+    pub fn to_bgr<U: Channel>(self) -> BGR<U> {
+        BGR {
+            b: U::from_channel(self.b),
+            g: U::from_channel(self.g),
+            r: U::from_channel(self.r),
+        }
+    }
+
+    pub fn clamp(self) -> Self {
+        Self {
+            r: Channel::clamp(self.r),
+            g: Channel::clamp(self.g),
+            b: Channel::clamp(self.b),
+        }
+    }
+
+    pub fn saturating_add(self, rhs: Self) -> Self {
+        Self {
+            r: self.r.saturating_add(rhs.r),
+            g: self.g.saturating_add(rhs.g),
+            b: self.b.saturating_add(rhs.b),
+        }
+    }
+
+    pub fn saturating_sub(self, rhs: Self) -> Self {
+        Self {
+            r: self.r.saturating_sub(rhs.r),
+            g: self.g.saturating_sub(rhs.g),
+            b: self.b.saturating_sub(rhs.b),
+        }
+    }
+
+    pub fn saturating_mul(self, rhs: Self) -> Self {
+        Self {
+            r: self.r.saturating_mul(rhs.r),
+            g: self.g.saturating_mul(rhs.g),
+            b: self.b.saturating_mul(rhs.b),
+        }
     }
 }
 
@@ -66,6 +121,34 @@ where
     }
 }
 
+impl<T> Sub for RGB<T>
+where
+    T: Copy + Sub<Output = T>,
+{
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::Output {
+            r: self.r - rhs.r,
+            g: self.g - rhs.g,
+            b: self.b - rhs.b,
+        }
+    }
+}
+
+impl<T> Sub<T> for RGB<T>
+where
+    T: Copy + Sub<Output = T>,
+{
+    type Output = Self;
+    fn sub(self, rhs: T) -> Self::Output {
+        Self::Output {
+            r: self.r - rhs,
+            g: self.g - rhs,
+            b: self.b - rhs,
+        }
+    }
+}
+
 impl<T> Mul for RGB<T>
 where
     T: Copy + Mul<Output = T>,
@@ -143,6 +226,7 @@ impl<T: Copy> From<[T; 3]> for RGB<T> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::color::Convert;
 
     #[test]
     fn percent32_to_byte() {
@@ -219,6 +303,60 @@ mod tests {
         assert_eq!(rgb.add(2), RGB { r: 2, g: 4, b: 6 });
     }
 
+    #[test]
+    fn sub() {
+        let rgb: RGB<i32> = RGB { r: 5, g: 0, b: 9 };
+        let rth: RGB<i32> = RGB { r: 2, g: 4, b: 9 };
+        assert_eq!(rgb.sub(rth), RGB { r: 3, g: -4, b: 0 });
+
+        let rgb: RGB<f32> = RGB {
+            r: 1.0,
+            g: 0.5,
+            b: 0.0,
+        };
+        assert_eq!(
+            rgb.sub(0.5),
+            RGB {
+                r: 0.5,
+                g: 0.0,
+                b: -0.5
+            }
+        );
+    }
+
+    #[test]
+    fn convert() {
+        let rgb = RGB8::from([255, 128, 0]);
+        assert_eq!(rgb.convert::<u16>(), RGB16::from([65535, 32896, 0]));
+        assert_eq!(rgb.convert::<u16>().convert::<u8>(), rgb);
+        assert_eq!(rgb.convert::<f64>(), RGB64::from_rgb8(rgb));
+
+        let rgb = RGB32::from([2.0, 0.5, -1.0]);
+        assert_eq!(rgb.convert::<u8>(), RGB8::from([255, 128, 0]));
+        assert_eq!(rgb.convert::<f64>(), RGB64::from([2.0, 0.5, -1.0]));
+    }
+
+    #[test]
+    fn to_bgr() {
+        let rgb = RGB16::from([65535, 0, 0]);
+        assert_eq!(rgb.to_bgr::<u8>(), BGR::from([0, 0, 255]));
+    }
+
+    #[test]
+    fn clamp() {
+        let rgb = RGB32::from([2.0, 0.5, -1.0]);
+        assert_eq!(rgb.clamp(), RGB32::from([1.0, 0.5, 0.0]));
+    }
+
+    #[test]
+    fn saturating() {
+        let rgb = RGB8::from([250, 10, 100]);
+        let rth = RGB8::from([10, 20, 100]);
+        assert_eq!(rgb.saturating_add(rth), RGB8::from([255, 30, 200]));
+        assert_eq!(rgb.saturating_sub(rth), RGB8::from([240, 0, 0]));
+        assert_eq!(rgb.saturating_mul(rth), RGB8::from([255, 200, 255]));
+    }
+
     #[test]
     fn mul() {
         let rgb: RGB<u8> = RGB { r: 0, g: 0, b: 0 };
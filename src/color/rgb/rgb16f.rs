@@ -0,0 +1,127 @@
+use super::{RGB16F, RGB32, RGB64, RGB8};
+use crate::color::{rgb_hsl::hsl_to_rgb, Convert, BGR16F, BGR32, BGR64, BGR8, HSL};
+
+impl Convert for RGB16F {
+    fn from_rgb16f(src: RGB16F) -> Self {
+        src
+    }
+
+    fn from_rgb8(src: RGB8) -> Self {
+        src.convert()
+    }
+
+    fn from_rgb32(src: RGB32) -> Self {
+        src.convert()
+    }
+
+    fn from_rgb64(src: RGB64) -> Self {
+        src.convert()
+    }
+
+    fn from_bgr8(src: BGR8) -> Self {
+        src.to_rgb()
+    }
+
+    fn from_bgr16f(src: BGR16F) -> Self {
+        src.to_rgb()
+    }
+
+    fn from_bgr32(src: BGR32) -> Self {
+        src.to_rgb()
+    }
+
+    fn from_bgr64(src: BGR64) -> Self {
+        src.to_rgb()
+    }
+
+    fn from_hsl(src: HSL) -> Self {
+        hsl_to_rgb(src).convert()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::f16;
+
+    fn rgb16f(src: [f32; 3]) -> RGB16F {
+        RGB16F::from([
+            f16::from_f32(src[0]),
+            f16::from_f32(src[1]),
+            f16::from_f32(src[2]),
+        ])
+    }
+
+    #[test]
+    fn from_rgb16f() {
+        let rgb = rgb16f([0.5, 2.0, -1.0]);
+        assert_eq!(RGB16F::from_rgb16f(rgb), rgb);
+    }
+
+    #[test]
+    fn from_rgb8() {
+        let rgb = RGB16F::from_rgb8(RGB8::from([0, 0, 255]));
+        assert_eq!(rgb, rgb16f([0.0, 0.0, 1.0]));
+
+        let rgb = RGB16F::from_rgb8(RGB8::from([255, 255, 255]));
+        assert_eq!(rgb, rgb16f([1.0, 1.0, 1.0]));
+
+        let rgb = RGB16F::from_rgb8(RGB8::from([51, 0, 0]));
+        assert_eq!(rgb, rgb16f([0.2, 0.0, 0.0]));
+    }
+
+    #[test]
+    fn from_rgb32() {
+        let rgb = RGB16F::from_rgb32(RGB32::from([1.0, 0.5, 0.0]));
+        assert_eq!(rgb, rgb16f([1.0, 0.5, 0.0]));
+
+        let rgb = RGB16F::from_rgb32(RGB32::from([16.0, 1000.0, 0.25]));
+        assert_eq!(rgb, rgb16f([16.0, 1000.0, 0.25]));
+    }
+
+    #[test]
+    fn from_rgb64() {
+        let rgb = RGB16F::from_rgb64(RGB64::from([0.0, 1.0, 0.5]));
+        assert_eq!(rgb, rgb16f([0.0, 1.0, 0.5]));
+
+        let rgb = RGB16F::from_rgb64(RGB64::from([1.0e6, 0.0, 0.0]));
+        assert_eq!(rgb.r, f16::INFINITY);
+    }
+
+    #[test]
+    fn from_bgr8() {
+        let rgb = RGB16F::from_bgr8(BGR8::from([255, 0, 0]));
+        assert_eq!(rgb, rgb16f([0.0, 0.0, 1.0]));
+    }
+
+    #[test]
+    fn from_bgr16f() {
+        let bgr = BGR16F::from([f16::ONE, f16::ZERO, f16::ZERO]);
+        let rgb = RGB16F::from_bgr16f(bgr);
+        assert_eq!(rgb, rgb16f([0.0, 0.0, 1.0]));
+    }
+
+    #[test]
+    fn from_bgr32() {
+        let rgb = RGB16F::from_bgr32(BGR32::from([0.0, 0.5, 1.0]));
+        assert_eq!(rgb, rgb16f([1.0, 0.5, 0.0]));
+    }
+
+    #[test]
+    fn from_bgr64() {
+        let rgb = RGB16F::from_bgr64(BGR64::from([0.0, 0.25, 4.0]));
+        assert_eq!(rgb, rgb16f([4.0, 0.25, 0.0]));
+    }
+
+    #[test]
+    fn from_hsl() {
+        let rgb = RGB16F::from_hsl(HSL::from([180.0, 1.0, 0.5]));
+        assert_eq!(rgb, rgb16f([0.0, 1.0, 1.0]));
+    }
+
+    #[test]
+    fn into_rgb8() {
+        let rgb: RGB8 = rgb16f([1.0, 0.5, 0.0]).into();
+        assert_eq!(rgb, RGB8::from([255, 128, 0]));
+    }
+}
@@ -1,5 +1,5 @@
-use super::{RGB32, RGB64, RGB8};
-use crate::color::{rgb_hsl::hsl_to_rgb, Convert, BGR32, BGR64, BGR8, HSL};
+use super::{RGB16F, RGB32, RGB64, RGB8};
+use crate::color::{rgb_hsl::hsl_to_rgb, Convert, BGR16F, BGR32, BGR64, BGR8, HSL};
 
 impl Convert for RGB8 {
     fn from_rgb8(_: RGB8) -> Self {
@@ -7,47 +7,35 @@ impl Convert for RGB8 {
     }
 
     fn from_rgb32(src: RGB32) -> Self {
-        Self {
-            r: Self::percent32_to_byte(src.r),
-            g: Self::percent32_to_byte(src.g),
-            b: Self::percent32_to_byte(src.b),
-        }
+        src.convert()
     }
 
     fn from_rgb64(src: RGB64) -> Self {
-        Self {
-            r: Self::percent64_to_byte(src.r),
-            g: Self::percent64_to_byte(src.g),
-            b: Self::percent64_to_byte(src.b),
-        }
+        src.convert()
     }
 
     fn from_bgr8(src: BGR8) -> Self {
-        Self {
-            r: src.r,
-            g: src.g,
-            b: src.b,
-        }
+        src.to_rgb()
     }
 
     fn from_bgr32(src: BGR32) -> Self {
-        Self {
-            r: Self::percent32_to_byte(src.r),
-            g: Self::percent32_to_byte(src.g),
-            b: Self::percent32_to_byte(src.b),
-        }
+        src.to_rgb()
     }
 
     fn from_bgr64(src: BGR64) -> Self {
-        Self {
-            r: Self::percent64_to_byte(src.r),
-            g: Self::percent64_to_byte(src.g),
-            b: Self::percent64_to_byte(src.b),
-        }
+        src.to_rgb()
+    }
+
+    fn from_rgb16f(src: RGB16F) -> Self {
+        src.convert()
+    }
+
+    fn from_bgr16f(src: BGR16F) -> Self {
+        src.to_rgb()
     }
 
     fn from_hsl(src: HSL) -> Self {
-        Self::from_rgb64(hsl_to_rgb(src))
+        hsl_to_rgb(src).convert()
     }
 }
 
@@ -55,6 +43,16 @@ impl Convert for RGB8 {
 mod tests {
     use super::*;
 
+    #[test]
+    fn from_rgb16f() {
+        let half = |x: f32| crate::color::f16::from_f32(x);
+        let rgb = RGB8::from_rgb16f(RGB16F::from([half(1.0), half(0.5), half(0.0)]));
+        assert_eq!(rgb, RGB8::from([255, 128, 0]));
+
+        let rgb = RGB8::from_rgb16f(RGB16F::from([half(0.0), half(0.25), half(1.0)]));
+        assert_eq!(rgb, RGB8::from([0, 64, 255]));
+    }
+
     #[test]
     fn from_rgb32() {
         let rgb = RGB8::from_rgb32(RGB32::from([1.0, 0.5, 0.0]));
@@ -1,9 +1,9 @@
 use super::{HSL, RGB64};
 
 pub(crate) fn rgb_to_hsl(r: f64, g: f64, b: f64) -> HSL {
-    debug_assert!(r >= 0_f64 && r <= 1_f64);
-    debug_assert!(g >= 0_f64 && g <= 1_f64);
-    debug_assert!(b >= 0_f64 && b <= 1_f64);
+    debug_assert!((0_f64..=1_f64).contains(&r));
+    debug_assert!((0_f64..=1_f64).contains(&g));
+    debug_assert!((0_f64..=1_f64).contains(&b));
 
     let max = r.max(g.max(b));
     let min = r.min(g.min(b));
@@ -15,7 +15,7 @@ pub(crate) fn rgb_to_hsl(r: f64, g: f64, b: f64) -> HSL {
         return HSL {
             h: 0_f64,
             s: 0_f64,
-            l: l,
+            l,
         };
     }
 
@@ -43,11 +43,7 @@ pub(crate) fn rgb_to_hsl(r: f64, g: f64, b: f64) -> HSL {
 
     let h_degrees = (h * 360_f64 * 100_f64).round() / 100_f64;
 
-    HSL {
-        h: h_degrees,
-        s: s,
-        l: l,
-    }
+    HSL { h: h_degrees, s, l }
 }
 
 pub(crate) fn hsl_to_rgb(hsl: HSL) -> RGB64 {
@@ -107,8 +103,8 @@ mod tests {
 
     fn rgb_hsl_rgb(range: std::ops::RangeInclusive<u8>) {
         for r in range {
-            for g in 0..=255 as u8 {
-                for b in 0..=255 as u8 {
+            for g in 0..=255_u8 {
+                for b in 0..=255_u8 {
                     let rgb = RGB64::from([
                         RGB64::byte_to_percent64(r),
                         RGB64::byte_to_percent64(g),
@@ -0,0 +1,269 @@
+use super::{
+    gray::luma8,
+    rgb_hsl::{hsl_to_rgb, rgb_to_hsl},
+    BGR32, BGR8, HSL, RGB32, RGB8,
+};
+use crate::error::IncorrectData;
+use std::{any::TypeId, mem::ManuallyDrop, slice};
+
+/// Convert every pixel of `src` into `dst`
+///
+/// Results are identical to converting one pixel at a time with `Into`,
+/// common pairs are dispatched to kernels that the compiler can vectorize.
+pub fn convert_slice<A, B>(src: &[A], dst: &mut [B]) -> Result<(), IncorrectData>
+where
+    A: Copy + Into<B> + 'static,
+    B: 'static,
+{
+    if src.len() != dst.len() {
+        return Err(IncorrectData::Size {
+            expected: src.len(),
+            got: dst.len(),
+        });
+    }
+
+    if !convert_kernel(src, dst) {
+        for (d, s) in dst.iter_mut().zip(src) {
+            *d = (*s).into();
+        }
+    }
+    Ok(())
+}
+
+/// Same as `convert_slice` into a new vector
+pub(crate) fn convert_vec<A, B>(src: &[A]) -> Vec<B>
+where
+    A: Copy + Into<B> + 'static,
+    B: 'static,
+{
+    macro_rules! with_kernel {
+        ($($t:ty),*) => {
+            $(
+                if TypeId::of::<B>() == TypeId::of::<$t>() {
+                    let mut dst = vec![<$t>::default(); src.len()];
+                    if convert_kernel(src, &mut dst) {
+                        return cast_vec(dst);
+                    }
+                }
+            )*
+        };
+    }
+
+    with_kernel!(RGB8, RGB32, BGR8, BGR32, HSL, u8);
+    src.iter().map(|x| (*x).into()).collect()
+}
+
+/// Returns false when there is no dedicated kernel for the pair
+fn convert_kernel<A: 'static, B: 'static>(src: &[A], dst: &mut [B]) -> bool {
+    macro_rules! dispatch {
+        ($($a:ty => $b:ty: $kernel:expr),*) => {
+            $(
+                if let (Some(src), Some(dst)) = (cast::<A, $a>(src), cast_mut::<B, $b>(dst)) {
+                    $kernel(src, dst);
+                    return true;
+                }
+            )*
+        };
+    }
+
+    dispatch!(
+        RGB8 => RGB32: |s: &[RGB8], d: &mut [RGB32]| bytes_to_percents(flat(s), flat_mut(d)),
+        BGR8 => BGR32: |s: &[BGR8], d: &mut [BGR32]| bytes_to_percents(flat(s), flat_mut(d)),
+        RGB32 => RGB8: |s: &[RGB32], d: &mut [RGB8]| percents_to_bytes(flat(s), flat_mut(d)),
+        BGR32 => BGR8: |s: &[BGR32], d: &mut [BGR8]| percents_to_bytes(flat(s), flat_mut(d)),
+        RGB8 => BGR8: |s: &[RGB8], d: &mut [BGR8]| swap_bytes(flat(s), flat_mut(d)),
+        BGR8 => RGB8: |s: &[BGR8], d: &mut [RGB8]| swap_bytes(flat(s), flat_mut(d)),
+        RGB8 => HSL: rgb8_to_hsl,
+        HSL => RGB8: hsl_to_rgb8,
+        RGB8 => u8: rgb8_to_gray
+    );
+    false
+}
+
+#[inline]
+fn cast<A: 'static, X: 'static>(src: &[A]) -> Option<&[X]> {
+    if TypeId::of::<A>() == TypeId::of::<X>() {
+        // A and X are the same type
+        Some(unsafe { slice::from_raw_parts(src.as_ptr() as *const X, src.len()) })
+    } else {
+        None
+    }
+}
+
+#[inline]
+fn cast_mut<A: 'static, X: 'static>(src: &mut [A]) -> Option<&mut [X]> {
+    if TypeId::of::<A>() == TypeId::of::<X>() {
+        // A and X are the same type
+        Some(unsafe { slice::from_raw_parts_mut(src.as_mut_ptr() as *mut X, src.len()) })
+    } else {
+        None
+    }
+}
+
+fn cast_vec<X: 'static, B: 'static>(src: Vec<X>) -> Vec<B> {
+    assert_eq!(TypeId::of::<X>(), TypeId::of::<B>());
+    let mut src = ManuallyDrop::new(src);
+    // X and B are the same type
+    unsafe { Vec::from_raw_parts(src.as_mut_ptr() as *mut B, src.len(), src.capacity()) }
+}
+
+/// Components of #[repr(C)] three channel pixels
+#[inline]
+fn flat<T, C>(src: &[T]) -> &[C] {
+    debug_assert_eq!(std::mem::size_of::<T>(), 3 * std::mem::size_of::<C>());
+    unsafe { slice::from_raw_parts(src.as_ptr() as *const C, src.len() * 3) }
+}
+
+#[inline]
+fn flat_mut<T, C>(src: &mut [T]) -> &mut [C] {
+    debug_assert_eq!(std::mem::size_of::<T>(), 3 * std::mem::size_of::<C>());
+    unsafe { slice::from_raw_parts_mut(src.as_mut_ptr() as *mut C, src.len() * 3) }
+}
+
+fn bytes_to_percents(src: &[u8], dst: &mut [f32]) {
+    for (d, s) in dst.iter_mut().zip(src) {
+        *d = *s as f32 / 255.0;
+    }
+}
+
+fn percents_to_bytes(src: &[f32], dst: &mut [u8]) {
+    for (d, s) in dst.iter_mut().zip(src) {
+        // x * 255 is exact in f64, so adding 0.5 and truncating rounds
+        // half away from zero just like `f64::round`
+        *d = (*s as f64 * 255.0 + 0.5) as u8;
+    }
+}
+
+fn swap_bytes(src: &[u8], dst: &mut [u8]) {
+    for (d, s) in dst.chunks_exact_mut(3).zip(src.chunks_exact(3)) {
+        d[0] = s[2];
+        d[1] = s[1];
+        d[2] = s[0];
+    }
+}
+
+fn rgb8_to_hsl(src: &[RGB8], dst: &mut [HSL]) {
+    for (d, s) in dst.iter_mut().zip(src) {
+        *d = rgb_to_hsl(s.r as f64 / 255.0, s.g as f64 / 255.0, s.b as f64 / 255.0);
+    }
+}
+
+fn hsl_to_rgb8(src: &[HSL], dst: &mut [RGB8]) {
+    for (d, s) in dst.iter_mut().zip(src) {
+        let rgb = hsl_to_rgb(*s);
+        *d = RGB8 {
+            r: (rgb.r * 255.0).round() as u8,
+            g: (rgb.g * 255.0).round() as u8,
+            b: (rgb.b * 255.0).round() as u8,
+        };
+    }
+}
+
+fn rgb8_to_gray(src: &[RGB8], dst: &mut [u8]) {
+    let src: &[u8] = flat(src);
+    for (d, s) in dst.iter_mut().zip(src.chunks_exact(3)) {
+        *d = luma8(s[0], s[1], s[2]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::{f16, RGB16F, RGB64};
+
+    fn rgb8_samples() -> Vec<RGB8> {
+        (0..=255_u8)
+            .flat_map(|r| {
+                (0..=255_u8)
+                    .step_by(5)
+                    .map(move |g| RGB8::from([r, g, 255 - r]))
+            })
+            .collect()
+    }
+
+    fn check<A, B>(src: &[A])
+    where
+        A: Copy + Into<B> + 'static,
+        B: Copy + PartialEq + std::fmt::Debug + 'static,
+    {
+        let expected: Vec<B> = src.iter().map(|x| (*x).into()).collect();
+        assert_eq!(convert_vec::<A, B>(src), expected);
+
+        let mut dst: Vec<B> = expected.clone();
+        convert_slice(src, &mut dst).unwrap();
+        assert_eq!(dst, expected);
+    }
+
+    #[test]
+    fn rgb8_rgb32() {
+        let src = rgb8_samples();
+        check::<RGB8, RGB32>(&src);
+        check::<RGB8, BGR32>(&src);
+
+        let mut src: Vec<RGB32> = src.iter().map(|x| (*x).into()).collect();
+        src.push(RGB32::from([-0.001, 1.7, f32::NAN]));
+        src.push(RGB32::from([0.5 / 255.0, 1.5 / 255.0, 254.5 / 255.0]));
+        for i in 0..10_000 {
+            let x = i as f32 / 10_000.0;
+            src.push(RGB32::from([x, 1.0 - x, x * x]));
+        }
+        check::<RGB32, RGB8>(&src);
+        check::<RGB32, BGR8>(&src);
+    }
+
+    #[test]
+    fn bgr8_bgr32() {
+        let src: Vec<BGR8> = rgb8_samples().iter().map(|x| (*x).into()).collect();
+        check::<BGR8, BGR32>(&src);
+
+        let src: Vec<BGR32> = src.iter().map(|x| (*x).into()).collect();
+        check::<BGR32, BGR8>(&src);
+    }
+
+    #[test]
+    fn swizzle() {
+        let src = rgb8_samples();
+        check::<RGB8, BGR8>(&src);
+
+        let src: Vec<BGR8> = src.iter().map(|x| (*x).into()).collect();
+        check::<BGR8, RGB8>(&src);
+    }
+
+    #[test]
+    fn hsl() {
+        let src = rgb8_samples();
+        check::<RGB8, HSL>(&src);
+
+        let src: Vec<HSL> = src.iter().map(|x| (*x).into()).collect();
+        check::<HSL, RGB8>(&src);
+    }
+
+    #[test]
+    fn gray() {
+        let src = rgb8_samples();
+        check::<RGB8, u8>(&src);
+    }
+
+    #[test]
+    fn fallback() {
+        let src = rgb8_samples();
+        check::<RGB8, RGB64>(&src);
+        check::<RGB8, RGB16F>(&src);
+
+        let src = vec![f16::ONE; 7];
+        check::<f16, f32>(&src);
+    }
+
+    #[test]
+    fn size() {
+        let mut dst = vec![RGB32::default(); 2];
+        let result = convert_slice(&[RGB8::default(); 3], &mut dst);
+        assert!(matches!(
+            result,
+            Err(IncorrectData::Size {
+                expected: 3,
+                got: 2
+            })
+        ));
+    }
+}
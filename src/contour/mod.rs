@@ -0,0 +1,258 @@
+//! Contours of shapes in single channel frames
+//!
+//! Pixels not equal to `Default::default()` are foreground and join their 8
+//! neighbors. Points are pixel coordinates, outer borders run
+//! counter-clockwise on screen and hole borders clockwise.
+
+mod shape;
+
+pub use shape::{
+    approximate, area, bounding_rect, convex_hull, min_area_rect, perimeter, signed_area,
+    RotatedRect,
+};
+
+use crate::{display::Frame, geometry::Vec2};
+
+/// Border of a shape or of a hole in it
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Contour {
+    pub points: Vec<Vec2<i32>>,
+    /// Border of a hole rather than the outer border of a shape
+    pub hole: bool,
+    /// Index of the enclosing contour, a hole for outer borders and the
+    /// outer border for holes
+    pub parent: Option<usize>,
+}
+
+/// Neighbors clockwise on screen, starting on the right
+const DIRECTIONS: [(i32, i32); 8] = [
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+];
+
+fn direction(from: (i32, i32), to: (i32, i32)) -> usize {
+    let d = (to.0 - from.0, to.1 - from.1);
+    DIRECTIONS.iter().position(|x| *x == d).unwrap()
+}
+
+/// Padded copy of the frame with the foreground set to 1 and the border
+/// numbers written over it while tracing
+struct Marks {
+    width: i32,
+    data: Vec<i32>,
+}
+
+impl Marks {
+    fn at(&self, (x, y): (i32, i32)) -> i32 {
+        self.data[(y * self.width + x) as usize]
+    }
+
+    fn set(&mut self, (x, y): (i32, i32), value: i32) {
+        self.data[(y * self.width + x) as usize] = value;
+    }
+
+    /// Follows the border through `start`, entered from the background
+    /// pixel `from`, marking it with `number`
+    fn follow(&mut self, start: (i32, i32), from: (i32, i32), number: i32) -> Vec<Vec2<i32>> {
+        let point = |(x, y): (i32, i32)| Vec2 { x: x - 1, y: y - 1 };
+        let step = |p: (i32, i32), d: usize| (p.0 + DIRECTIONS[d].0, p.1 + DIRECTIONS[d].1);
+
+        let d = direction(start, from);
+        let first = (0..8)
+            .map(|i| (d + i) % 8)
+            .find(|d| self.at(step(start, *d)) != 0);
+        let first = match first {
+            Some(d) => step(start, d),
+            None => {
+                // an isolated pixel
+                self.set(start, -number);
+                return vec![point(start)];
+            }
+        };
+
+        let mut points = Vec::new();
+        let (mut previous, mut current) = (first, start);
+        loop {
+            let d = direction(current, previous);
+            let mut right_checked = false;
+            let mut next = current;
+            for i in 1..=8 {
+                let d = (d + 8 - i) % 8;
+                let p = step(current, d);
+                if self.at(p) != 0 {
+                    next = p;
+                    break;
+                }
+                if d == 0 {
+                    right_checked = true;
+                }
+            }
+
+            if right_checked {
+                self.set(current, -number);
+            } else if self.at(current) == 1 {
+                self.set(current, number);
+            }
+            points.push(point(current));
+
+            if next == start && current == first {
+                return points;
+            }
+            previous = current;
+            current = next;
+        }
+    }
+}
+
+/// Traces the borders of every shape and hole with Suzuki and Abe's border
+/// following, contours come in the order their first pixel appears row by
+/// row, so parents always come before their children
+pub fn find_contours<F>(frame: &F) -> Vec<Contour>
+where
+    F: Frame,
+    F::Pixel: Copy + PartialEq + Default,
+{
+    let (width, height) = (frame.width() as i32 + 2, frame.height() as i32 + 2);
+    let mut marks = Marks {
+        width,
+        data: vec![0; width as usize * height as usize],
+    };
+    for (y, row) in frame.rows().enumerate() {
+        for (x, p) in row.iter().enumerate() {
+            if *p != F::Pixel::default() {
+                marks.set((x as i32 + 1, y as i32 + 1), 1);
+            }
+        }
+    }
+
+    // border 1 is the frame, found contour `i` is border `i + 2`
+    let mut contours: Vec<Contour> = Vec::new();
+    let border = |contours: &[Contour], number: i32| -> (bool, Option<usize>) {
+        match number {
+            1 => (true, None),
+            n => {
+                let contour = &contours[n as usize - 2];
+                (contour.hole, Some(n as usize - 2))
+            }
+        }
+    };
+
+    for y in 1..height - 1 {
+        let mut last = 1;
+        for x in 1..width - 1 {
+            let value = marks.at((x, y));
+            let start = if value == 1 && marks.at((x - 1, y)) == 0 {
+                Some((false, (x - 1, y)))
+            } else if value >= 1 && marks.at((x + 1, y)) == 0 {
+                if value > 1 {
+                    last = value;
+                }
+                Some((true, (x + 1, y)))
+            } else {
+                None
+            };
+
+            if let Some((hole, from)) = start {
+                let number = contours.len() as i32 + 2;
+                let (last_hole, last_index) = border(&contours, last);
+                let parent = if hole == last_hole {
+                    last_index.and_then(|i| contours[i].parent)
+                } else {
+                    last_index
+                };
+                let points = marks.follow((x, y), from, number);
+                contours.push(Contour {
+                    points,
+                    hole,
+                    parent,
+                });
+            }
+
+            let value = marks.at((x, y));
+            if value != 1 && value != 0 {
+                last = value.abs();
+            }
+        }
+    }
+    contours
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::display::Image;
+    use crate::test_util::mask;
+
+    fn points(points: &[(i32, i32)]) -> Vec<Vec2<i32>> {
+        points.iter().map(|(x, y)| Vec2 { x: *x, y: *y }).collect()
+    }
+
+    #[test]
+    fn square() {
+        let img = mask(&["....", ".##.", ".##.", "...."]);
+        let contours = find_contours(&img);
+        assert_eq!(contours.len(), 1);
+        assert_eq!(
+            contours[0],
+            Contour {
+                points: points(&[(1, 1), (1, 2), (2, 2), (2, 1)]),
+                hole: false,
+                parent: None,
+            }
+        );
+
+        let single = find_contours(&mask(&["...", ".#.", "..."]));
+        assert_eq!(single[0].points, points(&[(1, 1)]));
+
+        let line = find_contours(&mask(&["###"]));
+        assert_eq!(line[0].points, points(&[(0, 0), (1, 0), (2, 0), (1, 0)]));
+    }
+
+    #[test]
+    fn hierarchy() {
+        let img = mask(&[
+            "#######..##",
+            "#.....#..##",
+            "#.###.#....",
+            "#.#.#.#....",
+            "#.###.#....",
+            "#.....#....",
+            "#######....",
+        ]);
+        let contours = find_contours(&img);
+        let summary: Vec<_> = contours.iter().map(|c| (c.hole, c.parent)).collect();
+        assert_eq!(
+            summary,
+            [
+                (false, None),
+                (false, None),
+                (true, Some(0)),
+                (false, Some(2)),
+                (true, Some(3)),
+            ]
+        );
+        assert_eq!(contours[0].points.len(), 24);
+        assert_eq!(contours[0].points[0], Vec2 { x: 0, y: 0 });
+        assert_eq!(contours[1].points[0], Vec2 { x: 9, y: 0 });
+        // the hole border runs over the ring, cutting its inner corners
+        assert_eq!(contours[2].points.len(), 20);
+        assert_eq!(contours[2].points[..3], points(&[(0, 1), (1, 0), (2, 0)]));
+        assert_eq!(
+            contours[4].points,
+            points(&[(2, 3), (3, 2), (4, 3), (3, 4)])
+        );
+    }
+
+    #[test]
+    fn empty() {
+        assert!(find_contours(&mask(&["...."])).is_empty());
+        assert!(find_contours(&Image::<u8>::new(0, 0, 0).unwrap()).is_empty());
+    }
+}
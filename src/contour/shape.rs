@@ -0,0 +1,321 @@
+use crate::geometry::{Rect, Vec2};
+
+/// Rectangle turned by `angle` radians around its center, `width` runs
+/// along the angle
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RotatedRect {
+    pub center: Vec2<f64>,
+    pub width: f64,
+    pub height: f64,
+    pub angle: f64,
+}
+
+impl RotatedRect {
+    /// Corners in turn, starting at the one before the width side
+    pub fn corners(&self) -> [Vec2<f64>; 4] {
+        let (sin, cos) = self.angle.sin_cos();
+        let (w, h) = (self.width / 2.0, self.height / 2.0);
+        let corner = |a: f64, b: f64| Vec2 {
+            x: self.center.x + a * cos - b * sin,
+            y: self.center.y + a * sin + b * cos,
+        };
+        [corner(-w, -h), corner(w, -h), corner(w, h), corner(-w, h)]
+    }
+}
+
+#[inline]
+fn cross(o: Vec2<i32>, a: Vec2<i32>, b: Vec2<i32>) -> i64 {
+    (a.x - o.x) as i64 * (b.y - o.y) as i64 - (a.y - o.y) as i64 * (b.x - o.x) as i64
+}
+
+fn distance(a: Vec2<i32>, b: Vec2<i32>) -> f64 {
+    ((b.x - a.x) as f64).hypot((b.y - a.y) as f64)
+}
+
+/// Distance of `p` from the segment from `a` to `b`
+fn segment_distance(p: Vec2<i32>, a: Vec2<i32>, b: Vec2<i32>) -> f64 {
+    let (dx, dy) = ((b.x - a.x) as f64, (b.y - a.y) as f64);
+    let length = dx * dx + dy * dy;
+    if length == 0.0 {
+        return distance(p, a);
+    }
+    let t = (((p.x - a.x) as f64 * dx + (p.y - a.y) as f64 * dy) / length).clamp(0.0, 1.0);
+    ((p.x - a.x) as f64 - t * dx).hypot((p.y - a.y) as f64 - t * dy)
+}
+
+/// Area enclosed by the polygon, positive when it runs clockwise on screen
+pub fn signed_area(points: &[Vec2<i32>]) -> f64 {
+    let n = points.len();
+    let twice: i64 = (0..n)
+        .map(|i| {
+            let (a, b) = (points[i], points[(i + 1) % n]);
+            a.x as i64 * b.y as i64 - b.x as i64 * a.y as i64
+        })
+        .sum();
+    twice as f64 / 2.0
+}
+
+/// Area enclosed by the polygon
+pub fn area(points: &[Vec2<i32>]) -> f64 {
+    signed_area(points).abs()
+}
+
+/// Length of the polyline, back to the first point when `closed`
+pub fn perimeter(points: &[Vec2<i32>], closed: bool) -> f64 {
+    let open: f64 = points.windows(2).map(|w| distance(w[0], w[1])).sum();
+    match (closed, points.first(), points.last()) {
+        (true, Some(first), Some(last)) => open + distance(*last, *first),
+        _ => open,
+    }
+}
+
+/// Marks the points of `points` Douglas-Peucker keeps between its ends
+fn simplify(points: &[Vec2<i32>], epsilon: f64, keep: &mut [bool]) {
+    let mut ranges = vec![(0, points.len() - 1)];
+    while let Some((start, end)) = ranges.pop() {
+        let farthest = (start + 1..end)
+            .map(|i| (i, segment_distance(points[i], points[start], points[end])))
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+        if let Some((i, d)) = farthest {
+            if d > epsilon {
+                keep[i] = true;
+                ranges.push((start, i));
+                ranges.push((i, end));
+            }
+        }
+    }
+}
+
+/// Douglas-Peucker simplification, keeps the points needed for the result
+/// to stay within `epsilon` of the polyline. A `closed` polygon is split at
+/// its first point and the point farthest from it.
+pub fn approximate(points: &[Vec2<i32>], epsilon: f64, closed: bool) -> Vec<Vec2<i32>> {
+    if points.len() < 3 {
+        return points.to_vec();
+    }
+
+    let mut path = points.to_vec();
+    let mut keep = vec![false; points.len() + 1];
+    keep[0] = true;
+    if closed {
+        let farthest = (1..points.len())
+            .max_by(|a, b| {
+                distance(points[0], points[*a]).total_cmp(&distance(points[0], points[*b]))
+            })
+            .unwrap();
+        keep[farthest] = true;
+        simplify(&points[..=farthest], epsilon, &mut keep[..=farthest]);
+        path.push(points[0]);
+        simplify(&path[farthest..], epsilon, &mut keep[farthest..]);
+        path.pop();
+    } else {
+        keep[points.len() - 1] = true;
+        simplify(points, epsilon, &mut keep);
+    }
+
+    path.into_iter()
+        .zip(keep)
+        .filter(|(_, k)| *k)
+        .map(|(p, _)| p)
+        .collect()
+}
+
+/// Smallest convex polygon holding every point, clockwise on screen from
+/// the leftmost top point. Collinear points are left out.
+pub fn convex_hull(points: &[Vec2<i32>]) -> Vec<Vec2<i32>> {
+    let mut sorted = points.to_vec();
+    sorted.sort_by_key(|p| (p.x, p.y));
+    sorted.dedup();
+    if sorted.len() < 3 {
+        return sorted;
+    }
+
+    // Andrew's monotone chain, the top and then the bottom half
+    let mut hull: Vec<Vec2<i32>> = Vec::with_capacity(sorted.len() + 1);
+    for p in &sorted {
+        while hull.len() >= 2 && cross(hull[hull.len() - 2], hull[hull.len() - 1], *p) <= 0 {
+            hull.pop();
+        }
+        hull.push(*p);
+    }
+    let top = hull.len() + 1;
+    for p in sorted.iter().rev().skip(1) {
+        while hull.len() >= top && cross(hull[hull.len() - 2], hull[hull.len() - 1], *p) <= 0 {
+            hull.pop();
+        }
+        hull.push(*p);
+    }
+    // the last point is the first one again
+    hull.pop();
+    hull
+}
+
+/// Smallest axis aligned rectangle covering the pixels of `points`
+pub fn bounding_rect(points: &[Vec2<i32>]) -> Rect<i32> {
+    let first = match points.first() {
+        Some(p) => *p,
+        None => return Rect::default(),
+    };
+    let (min, max) = points
+        .iter()
+        .fold((first, first), |(min, max), p| (min.min(*p), max.max(*p)));
+    Rect {
+        x: min.x,
+        y: min.y,
+        width: max.x - min.x + 1,
+        height: max.y - min.y + 1,
+    }
+}
+
+/// Smallest rectangle holding every point, one of its sides lies on an edge
+/// of the convex hull
+pub fn min_area_rect(points: &[Vec2<i32>]) -> RotatedRect {
+    let hull = convex_hull(points);
+    let as_f64 = |p: Vec2<i32>| Vec2 {
+        x: p.x as f64,
+        y: p.y as f64,
+    };
+    match hull.len() {
+        0 => return RotatedRect::default(),
+        1 => {
+            return RotatedRect {
+                center: as_f64(hull[0]),
+                ..RotatedRect::default()
+            }
+        }
+        _ => {}
+    }
+
+    let mut best = RotatedRect::default();
+    let mut best_area = f64::INFINITY;
+    for i in 0..hull.len() {
+        let (origin, next) = (as_f64(hull[i]), as_f64(hull[(i + 1) % hull.len()]));
+        let length = (next.x - origin.x).hypot(next.y - origin.y);
+        let (ux, uy) = ((next.x - origin.x) / length, (next.y - origin.y) / length);
+
+        let (mut a0, mut a1, mut b0, mut b1) = (0.0_f64, 0.0_f64, 0.0_f64, 0.0_f64);
+        for p in &hull {
+            let (dx, dy) = (p.x as f64 - origin.x, p.y as f64 - origin.y);
+            let (a, b) = (dx * ux + dy * uy, dy * ux - dx * uy);
+            a0 = a0.min(a);
+            a1 = a1.max(a);
+            b0 = b0.min(b);
+            b1 = b1.max(b);
+        }
+
+        let area = (a1 - a0) * (b1 - b0);
+        if area < best_area {
+            let (a, b) = ((a0 + a1) / 2.0, (b0 + b1) / 2.0);
+            best_area = area;
+            best = RotatedRect {
+                center: Vec2 {
+                    x: origin.x + a * ux - b * uy,
+                    y: origin.y + a * uy + b * ux,
+                },
+                width: a1 - a0,
+                height: b1 - b0,
+                angle: uy.atan2(ux),
+            };
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn points(points: &[(i32, i32)]) -> Vec<Vec2<i32>> {
+        points.iter().map(|(x, y)| Vec2 { x: *x, y: *y }).collect()
+    }
+
+    #[test]
+    fn measures() {
+        let square = points(&[(0, 0), (4, 0), (4, 3), (0, 3)]);
+        assert_eq!(signed_area(&square), 12.0);
+        let reversed: Vec<_> = square.iter().rev().copied().collect();
+        assert_eq!(signed_area(&reversed), -12.0);
+        assert_eq!(area(&reversed), 12.0);
+        assert_eq!(perimeter(&square, true), 14.0);
+        assert_eq!(perimeter(&square, false), 11.0);
+        assert_eq!(perimeter(&[], true), 0.0);
+
+        assert_eq!(
+            bounding_rect(&square),
+            Rect {
+                x: 0,
+                y: 0,
+                width: 5,
+                height: 4
+            }
+        );
+        assert_eq!(bounding_rect(&[]), Rect::default());
+    }
+
+    #[test]
+    fn simplification() {
+        let line = points(&[(0, 0), (1, 0), (2, 1), (3, 0), (4, 0), (5, 0)]);
+        assert_eq!(
+            approximate(&line, 0.7, false),
+            points(&[(0, 0), (2, 1), (5, 0)])
+        );
+        assert_eq!(approximate(&line, 2.0, false), points(&[(0, 0), (5, 0)]));
+
+        // the border of a square keeps its corners
+        let mut border = Vec::new();
+        border.extend((0..4).map(|i| (i, 0)));
+        border.extend((0..4).map(|i| (4, i)));
+        border.extend((0..4).map(|i| (4 - i, 4)));
+        border.extend((0..4).map(|i| (0, 4 - i)));
+        let corners = approximate(&points(&border), 0.5, true);
+        assert_eq!(corners, points(&[(0, 0), (4, 0), (4, 4), (0, 4)]));
+    }
+
+    #[test]
+    fn hull() {
+        let cloud = points(&[
+            (0, 0),
+            (2, 1),
+            (4, 0),
+            (4, 4),
+            (1, 2),
+            (0, 4),
+            (2, 0),
+            (0, 0),
+        ]);
+        assert_eq!(
+            convex_hull(&cloud),
+            points(&[(0, 0), (4, 0), (4, 4), (0, 4)])
+        );
+        assert_eq!(convex_hull(&points(&[(1, 1), (1, 1)])), points(&[(1, 1)]));
+    }
+
+    #[test]
+    fn rotated() {
+        // a diamond is a square turned by 45 degrees
+        let diamond = points(&[(2, 0), (4, 2), (2, 4), (0, 2), (2, 2)]);
+        let rect = min_area_rect(&diamond);
+        let side = 8_f64.sqrt();
+        assert!((rect.width - side).abs() < 1e-9);
+        assert!((rect.height - side).abs() < 1e-9);
+        assert!((rect.center.x - 2.0).abs() < 1e-9 && (rect.center.y - 2.0).abs() < 1e-9);
+        assert!(
+            (rect.angle.abs() % std::f64::consts::FRAC_PI_2 - std::f64::consts::FRAC_PI_4).abs()
+                < 1e-9
+        );
+        for corner in rect.corners() {
+            let found = diamond.iter().any(|p| {
+                (p.x as f64 - corner.x).abs() < 1e-9 && (p.y as f64 - corner.y).abs() < 1e-9
+            });
+            assert!(found, "{:?}", corner);
+        }
+
+        let segment = min_area_rect(&points(&[(0, 0), (3, 4)]));
+        assert!((segment.width - 5.0).abs() < 1e-9 && segment.height.abs() < 1e-9);
+        assert_eq!(
+            min_area_rect(&points(&[(5, 6)])).center,
+            Vec2 { x: 5.0, y: 6.0 }
+        );
+    }
+}
@@ -1,8 +1,11 @@
-use super::{ComponentsRaw, Frame};
-use crate::{color::ComponentsCount, error::IncorrectData, math::index2d_to_index};
+use super::{ComponentsRaw, Frame, FrameMut};
+use crate::{
+    color::{cast_slice, cast_slice_mut, cast_vec, ComponentsCount, Pod},
+    error::IncorrectData,
+    math::index2d_to_index,
+};
 use std::{
     ops::{Deref, Index, IndexMut},
-    slice,
     slice::SliceIndex,
 };
 
@@ -15,7 +18,7 @@ pub struct Buffer<T: Copy> {
 impl<T: Copy> Buffer<T> {
     pub fn new(width: u32, height: u32, color: T) -> Result<Self, IncorrectData> {
         let capacity = width as usize * height as usize;
-        if let None = capacity.checked_mul(std::mem::size_of::<T>()) {
+        if capacity.checked_mul(std::mem::size_of::<T>()).is_none() {
             return Err(IncorrectData::Overflow);
         }
         if capacity > isize::MAX as usize {
@@ -68,19 +71,21 @@ impl<T: Copy> Frame for Buffer<T> {
         &self.data[index2d_to_index(self.width, x, y)]
     }
 
-    #[inline]
-    fn pixel_mut(&mut self, x: u32, y: u32) -> &mut Self::Pixel {
-        debug_assert!(x < self.width);
-        debug_assert!(y < self.height);
-        &mut self.data[index2d_to_index(self.width, x, y)]
-    }
-
     #[inline]
     fn row(&self, row: u32) -> &[Self::Pixel] {
         debug_assert!(row < self.height);
         let start = self.width as usize * row as usize;
         &self.data[start..start + self.width as usize]
     }
+}
+
+impl<T: Copy> FrameMut for Buffer<T> {
+    #[inline]
+    fn pixel_mut(&mut self, x: u32, y: u32) -> &mut Self::Pixel {
+        debug_assert!(x < self.width);
+        debug_assert!(y < self.height);
+        &mut self.data[index2d_to_index(self.width, x, y)]
+    }
 
     #[inline]
     fn row_mut(&mut self, row: u32) -> &mut [Self::Pixel] {
@@ -121,28 +126,37 @@ impl<T: Copy, I: SliceIndex<[T]>> IndexMut<I> for Buffer<T> {
     }
 }
 
-impl<T: Copy + ComponentsCount> ComponentsRaw for Buffer<T> {
-    type Output = <T as ComponentsCount>::Component;
+impl<T> Buffer<T>
+where
+    T: Pod + ComponentsCount,
+    T::Component: Pod,
+{
+    /// Mutable version of `ComponentsRaw::raw`
+    pub fn raw_mut(&mut self) -> &mut [T::Component] {
+        cast_slice_mut(&mut self.data).expect("pixel is not made of whole components")
+    }
+}
+
+impl<T> ComponentsRaw for Buffer<T>
+where
+    T: Pod + ComponentsCount,
+    T::Component: Pod,
+{
+    type Output = T::Component;
 
     fn raw(&self) -> &[Self::Output] {
-        let ptr = self.data.as_ptr();
-        let len = self.data.len() * T::components_count();
-        unsafe { slice::from_raw_parts(ptr as *const _, len) }
+        cast_slice(&self.data).expect("pixel is not made of whole components")
     }
 
-    fn raw_into_vec(mut self) -> Vec<Self::Output> {
-        let ptr = self.data.as_mut_ptr();
-        let len = self.data.len() * T::components_count();
-        let cap = self.data.capacity() * T::components_count();
-        std::mem::forget(self.data);
-        unsafe { Vec::from_raw_parts(ptr as *mut _, len, cap) }
+    fn raw_into_vec(self) -> Vec<Self::Output> {
+        cast_vec(self.data).expect("pixel is not made of whole components")
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::color::{rgb::RGB, RGB8};
+    use crate::color::{f16, rgb::RGB, RGB16F, RGB8};
 
     #[test]
     fn new() {
@@ -169,12 +183,7 @@ mod tests {
         let buffer = Buffer::new(width, height, color);
         assert!(buffer.is_err());
 
-        if let Err(e) = buffer {
-            match e {
-                IncorrectData::Overflow => assert!(true),
-                _ => assert!(false),
-            }
-        }
+        assert!(matches!(buffer, Err(IncorrectData::Overflow)));
     }
 
     #[test]
@@ -278,6 +287,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn rgb16f_raw() {
+        let one = f16::ONE;
+        let buff: Buffer<RGB16F> = Buffer::new(4, 3, RGB::from([one, f16::ZERO, one])).unwrap();
+
+        let raw: &[f16] = buff.raw();
+        assert_eq!(raw.len(), 4 * 3 * 3);
+
+        let bits = f16::slice_to_bits(raw);
+        for x in bits.chunks(3) {
+            assert_eq!(x, [0x3C00, 0x0000, 0x3C00]);
+        }
+    }
+
     #[test]
     fn rgb_raw_into_vec() {
         type T = f64;
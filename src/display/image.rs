@@ -1,15 +1,17 @@
-use super::{Buffer, ComponentsRaw, Frame};
+use super::{Buffer, ComponentsRaw, Frame, FrameMut};
 use crate::{
+    color::{cast_vec, convert_vec, ComponentsCount, Pod},
     error::IncorrectData,
     math::transpose::{transpose, transpose_square},
 };
 use std::{
+    convert::TryFrom,
     ops::{Deref, Index, IndexMut},
     slice::SliceIndex,
 };
 
 pub struct Image<T: Copy> {
-    buffer: Buffer<T>,
+    pub(super) buffer: Buffer<T>,
 }
 
 impl<T: Copy> Image<T> {
@@ -27,33 +29,151 @@ impl<T: Copy> Image<T> {
         }
     }
 
+    /// Take ownership of raw components, e.g. a decoded `Vec<u8>` as
+    /// `Image<RGB8>`, the allocation is reused when the pixel layout allows it
+    pub fn from_raw_vec(width: u32, height: u32, data: Vec<u8>) -> Result<Self, IncorrectData>
+    where
+        T: Pod,
+    {
+        let expected = (width as usize * height as usize)
+            .checked_mul(std::mem::size_of::<T>())
+            .ok_or(IncorrectData::Overflow)?;
+        if expected != data.len() {
+            return Err(IncorrectData::Size {
+                expected,
+                got: data.len(),
+            });
+        }
+        Self::from_vec(width, height, cast_vec(data)?)
+    }
+
+    /// Image with every pixel produced by `f(x, y)`
+    pub fn from_fn<F: FnMut(u32, u32) -> T>(
+        width: u32,
+        height: u32,
+        mut f: F,
+    ) -> Result<Self, IncorrectData> {
+        let capacity = (width as usize)
+            .checked_mul(height as usize)
+            .filter(|x| x.checked_mul(std::mem::size_of::<T>()).is_some())
+            .ok_or(IncorrectData::Overflow)?;
+
+        let mut data = Vec::with_capacity(capacity);
+        for y in 0..height {
+            for x in 0..width {
+                data.push(f(x, y));
+            }
+        }
+        Self::from_vec(width, height, data)
+    }
+
+    /// New image of the same size with `f` applied to every pixel
+    pub fn map<U: Copy, F: FnMut(T) -> U>(&self, f: F) -> Image<U> {
+        let data = self.buffer.iter().copied().map(f).collect();
+        Image::from_vec(self.width(), self.height(), data).unwrap()
+    }
+
+    pub fn map_in_place<F: FnMut(T) -> T>(&mut self, mut f: F) {
+        for x in self.buffer.data.iter_mut() {
+            *x = f(*x);
+        }
+    }
+
+    /// Same as `map(Into::into)`, common conversions use vectorized kernels
+    pub fn convert<U>(&self) -> Image<U>
+    where
+        T: Into<U> + 'static,
+        U: Copy + 'static,
+    {
+        Image::from_vec(self.width(), self.height(), convert_vec(&self.buffer)).unwrap()
+    }
+
     #[inline]
     pub fn as_vec(self) -> Vec<T> {
         self.buffer.data
     }
 
+    /// Reverses every row, which mirrors left to right
+    #[deprecated(note = "mirrors left to right, use `mirror_left_right`")]
     #[inline]
     pub fn flip_vertically(&mut self) {
+        self.mirror_left_right()
+    }
+
+    /// Flips across the horizontal axis, which mirrors top to bottom
+    #[deprecated(note = "mirrors top to bottom, use `mirror_top_bottom`")]
+    #[inline]
+    pub fn flip_horizontally(&mut self) {
+        self.mirror_top_bottom()
+    }
+
+    /// Swaps the left and right sides
+    pub fn mirror_left_right(&mut self) {
         for y in 0..self.buffer.height {
             self.buffer.row_mut(y).reverse()
         }
     }
 
+    /// Swaps the top and bottom sides
+    pub fn mirror_top_bottom(&mut self) {
+        let width = self.buffer.width as usize;
+        let height = self.buffer.height as usize;
+        let (top, bottom) = self.buffer.data.split_at_mut(width * (height / 2));
+        let bottom = &mut bottom[width * (height % 2)..];
+        for (a, b) in top
+            .chunks_exact_mut(width.max(1))
+            .zip(bottom.chunks_exact_mut(width.max(1)).rev())
+        {
+            a.swap_with_slice(b);
+        }
+    }
+
+    /// Rotates clockwise by 90 degrees
     #[inline]
-    pub fn flip_horizontally(&mut self) {
+    pub fn rotate90(&mut self) {
+        self.transpose();
+        self.mirror_left_right();
+    }
+
+    #[inline]
+    pub fn rotate180(&mut self) {
         self.buffer.data.reverse();
-        self.flip_vertically();
     }
 
+    /// Rotates clockwise by 270 degrees
     #[inline]
-    pub fn rotate90(&mut self) {
-        if self.buffer.width == self.buffer.height {
+    pub fn rotate270(&mut self) {
+        self.transpose();
+        self.mirror_top_bottom();
+    }
+
+    /// Mirrors across the main diagonal, (x, y) becomes (y, x)
+    pub fn transpose(&mut self) {
+        if self.buffer.data.is_empty() {
+            // nothing to move
+        } else if self.buffer.width == self.buffer.height {
             transpose_square(self.buffer.width, &mut self.buffer.data)
         } else {
             transpose(self.buffer.width, self.buffer.height, &mut self.buffer.data);
-            std::mem::swap(&mut self.buffer.width, &mut self.buffer.height);
         }
-        self.flip_vertically();
+        std::mem::swap(&mut self.buffer.width, &mut self.buffer.height);
+    }
+
+    /// Mirrors across the anti-diagonal
+    #[inline]
+    pub fn transverse(&mut self) {
+        self.transpose();
+        self.rotate180();
+    }
+}
+
+/// Fails when `data` does not hold `width * height` pixels, as the fields of
+/// a `Buffer` can be set freely
+impl<T: Copy> TryFrom<Buffer<T>> for Image<T> {
+    type Error = IncorrectData;
+
+    fn try_from(buffer: Buffer<T>) -> Result<Self, Self::Error> {
+        Self::from_vec(buffer.width, buffer.height, buffer.data)
     }
 }
 
@@ -76,13 +196,15 @@ impl<T: Copy> Frame for Image<T> {
     }
 
     #[inline]
-    fn pixel_mut(&mut self, x: u32, y: u32) -> &mut Self::Pixel {
-        self.buffer.pixel_mut(x, y)
+    fn row(&self, row: u32) -> &[Self::Pixel] {
+        self.buffer.row(row)
     }
+}
 
+impl<T: Copy> FrameMut for Image<T> {
     #[inline]
-    fn row(&self, row: u32) -> &[Self::Pixel] {
-        self.buffer.row(row)
+    fn pixel_mut(&mut self, x: u32, y: u32) -> &mut Self::Pixel {
+        self.buffer.pixel_mut(x, y)
     }
 
     #[inline]
@@ -122,6 +244,18 @@ impl<T: Copy, I: SliceIndex<[T]>> IndexMut<I> for Image<T> {
     }
 }
 
+impl<T> Image<T>
+where
+    T: Pod + ComponentsCount,
+    T::Component: Pod,
+{
+    /// Mutable version of `ComponentsRaw::raw`
+    #[inline]
+    pub fn raw_mut(&mut self) -> &mut [T::Component] {
+        self.buffer.raw_mut()
+    }
+}
+
 impl<T: Copy> ComponentsRaw for Image<T>
 where
     Buffer<T>: ComponentsRaw,
@@ -139,22 +273,25 @@ where
     }
 }
 
-impl<T: Copy + Into<F>, F: Copy> Into<Vec<F>> for Image<T> {
-    fn into(self) -> Vec<F> {
-        self.buffer.into_iter().map(|x| x.into()).collect()
+impl<T: Copy + Into<F> + 'static, F: Copy + 'static> From<Image<T>> for Vec<F> {
+    fn from(src: Image<T>) -> Self {
+        convert_vec(&src.buffer)
     }
 }
 
-impl<T: Copy + Into<F>, F: Copy> Into<Vec<F>> for &Image<T> {
-    fn into(self) -> Vec<F> {
-        self.buffer.iter().map(|x| x.clone().into()).collect()
+impl<T: Copy + Into<F> + 'static, F: Copy + 'static> From<&Image<T>> for Vec<F> {
+    fn from(src: &Image<T>) -> Self {
+        convert_vec(&src.buffer)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{color::RGB8, math::index2d_to_index};
+    use crate::{
+        color::{RGB32, RGB8},
+        math::index2d_to_index,
+    };
 
     #[test]
     fn rotate90_square() {
@@ -208,6 +345,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(deprecated)]
     fn flip_vertically() {
         let mut buffer: Image<RGB8> = Image::new(21, 21, RGB8::from([0, 0, 0])).unwrap();
         let color1 = RGB8::from([255, 0, 0]);
@@ -235,6 +373,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(deprecated)]
     fn flip_horizontally() {
         let mut buffer: Image<RGB8> = Image::new(43, 43, RGB8::from([0, 0, 0])).unwrap();
         let color1 = RGB8::from([255, 0, 0]);
@@ -260,4 +399,144 @@ mod tests {
             color3
         );
     }
+
+    fn numbered(width: u32, height: u32) -> Image<u32> {
+        Image::from_fn(width, height, |x, y| 10 * y + x).unwrap()
+    }
+
+    #[test]
+    fn mirror() {
+        let mut img = numbered(3, 3);
+        img.mirror_left_right();
+        assert_eq!(&img[..], &[2, 1, 0, 12, 11, 10, 22, 21, 20]);
+
+        let mut img = numbered(2, 3);
+        img.mirror_top_bottom();
+        assert_eq!(&img[..], &[20, 21, 10, 11, 0, 1]);
+
+        let mut img = numbered(2, 2);
+        img.mirror_top_bottom();
+        assert_eq!(&img[..], &[10, 11, 0, 1]);
+    }
+
+    #[test]
+    fn rotate() {
+        //  0  1  2       10  0      12 11 10      2 12
+        // 10 11 12  -->  11  1  -->  2  1  0  -->  1 11
+        //                12  2                    0 10
+        let mut img = numbered(3, 2);
+        img.rotate90();
+        assert_eq!((img.width(), img.height()), (2, 3));
+        assert_eq!(&img[..], &[10, 0, 11, 1, 12, 2]);
+
+        let mut img = numbered(3, 2);
+        img.rotate180();
+        assert_eq!(&img[..], &[12, 11, 10, 2, 1, 0]);
+
+        let mut img = numbered(3, 2);
+        img.rotate270();
+        assert_eq!((img.width(), img.height()), (2, 3));
+        assert_eq!(&img[..], &[2, 12, 1, 11, 0, 10]);
+
+        let mut three = numbered(3, 2);
+        three.rotate90();
+        three.rotate90();
+        three.rotate90();
+        assert_eq!(&three[..], &img[..]);
+
+        let mut empty = numbered(0, 4);
+        empty.rotate90();
+        assert_eq!((empty.width(), empty.height()), (4, 0));
+    }
+
+    #[test]
+    fn transpose() {
+        let mut img = numbered(3, 2);
+        img.transpose();
+        assert_eq!((img.width(), img.height()), (2, 3));
+        assert_eq!(&img[..], &[0, 10, 1, 11, 2, 12]);
+
+        let mut img = numbered(3, 2);
+        img.transverse();
+        assert_eq!((img.width(), img.height()), (2, 3));
+        assert_eq!(&img[..], &[12, 2, 11, 1, 10, 0]);
+    }
+
+    #[test]
+    fn from_raw_vec() {
+        let bytes: Vec<u8> = (0..12).collect();
+        let img: Image<RGB8> = Image::from_raw_vec(2, 2, bytes).unwrap();
+        assert_eq!(*img.pixel(1, 1), RGB8::from([9, 10, 11]));
+
+        let floats: Vec<u8> = [0.5_f32, 1.0, 0.0]
+            .iter()
+            .flat_map(|x| x.to_ne_bytes().to_vec())
+            .collect();
+        let img: Image<RGB32> = Image::from_raw_vec(1, 1, floats).unwrap();
+        assert_eq!(img[0], RGB32::from([0.5, 1.0, 0.0]));
+
+        assert!(matches!(
+            Image::<RGB8>::from_raw_vec(2, 2, vec![0; 11]),
+            Err(IncorrectData::Size {
+                expected: 12,
+                got: 11
+            })
+        ));
+    }
+
+    #[test]
+    fn try_from_buffer() {
+        let buffer = Buffer::new(2, 3, 7_u8).unwrap();
+        let img = Image::try_from(buffer).unwrap();
+        assert_eq!((img.width(), img.height()), (2, 3));
+
+        let mut buffer = Buffer::new(2, 3, 7_u8).unwrap();
+        buffer.height = 4;
+        assert!(matches!(
+            Image::try_from(buffer),
+            Err(IncorrectData::Size {
+                expected: 8,
+                got: 6
+            })
+        ));
+    }
+
+    #[test]
+    fn raw_mut() {
+        let mut img: Image<RGB8> = Image::new(2, 1, RGB8::from([0, 0, 0])).unwrap();
+        img.raw_mut()[4] = 255;
+        assert_eq!(img[1], RGB8::from([0, 255, 0]));
+    }
+
+    #[test]
+    fn from_fn() {
+        let img = Image::from_fn(3, 2, |x, y| x + 10 * y).unwrap();
+        assert_eq!(&img[..], &[0, 1, 2, 10, 11, 12]);
+
+        assert!(matches!(
+            Image::from_fn(u32::MAX, u32::MAX, |_, _| RGB8::default()),
+            Err(IncorrectData::Overflow)
+        ));
+    }
+
+    #[test]
+    fn map() {
+        let mut img = Image::from_fn(2, 2, |x, y| RGB8::from([x as u8, y as u8, 0])).unwrap();
+        let red = img.map(|p| p.r);
+        assert_eq!((red.width(), red.height()), (2, 2));
+        assert_eq!(&red[..], &[0, 1, 0, 1]);
+
+        img.map_in_place(|p| RGB8::from([p.g, p.r, 255]));
+        assert_eq!(img[1], RGB8::from([0, 1, 255]));
+    }
+
+    #[test]
+    fn convert() {
+        let img =
+            Image::from_fn(4, 3, |x, y| RGB8::from([x as u8 * 60, y as u8 * 100, 7])).unwrap();
+        let float: Image<RGB32> = img.convert();
+        assert_eq!(&float[..], &img.map(RGB32::from)[..]);
+        let gray: Image<u8> = img.convert();
+        assert_eq!(gray.width(), 4);
+    }
 }
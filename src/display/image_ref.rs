@@ -0,0 +1,197 @@
+use super::{Frame, FrameMut, Image};
+use crate::{
+    color::{cast_slice, cast_slice_mut, Pod},
+    error::IncorrectData,
+    math::index2d_to_index,
+};
+use std::ops::{Deref, DerefMut};
+
+/// Image borrowed from a slice of pixels
+pub struct ImageRef<'a, T> {
+    width: u32,
+    height: u32,
+    data: &'a [T],
+}
+
+/// Mutable image borrowed from a slice of pixels
+pub struct ImageMut<'a, T> {
+    width: u32,
+    height: u32,
+    data: &'a mut [T],
+}
+
+fn check_size(width: u32, height: u32, got: usize) -> Result<(), IncorrectData> {
+    let expected = width as usize * height as usize;
+    if expected != got {
+        return Err(IncorrectData::Size { expected, got });
+    }
+    Ok(())
+}
+
+impl<'a, T: Copy> ImageRef<'a, T> {
+    pub fn new(width: u32, height: u32, data: &'a [T]) -> Result<Self, IncorrectData> {
+        check_size(width, height, data.len())?;
+        Ok(Self {
+            width,
+            height,
+            data,
+        })
+    }
+
+    pub fn to_image(&self) -> Image<T> {
+        Image::from_vec(self.width, self.height, self.data.to_vec()).unwrap()
+    }
+}
+
+impl<'a, T: Pod> ImageRef<'a, T> {
+    /// Borrow raw components, e.g. a decoded `&[u8]` as `ImageRef<RGB8>`
+    pub fn from_bytes(width: u32, height: u32, bytes: &'a [u8]) -> Result<Self, IncorrectData> {
+        Self::new(width, height, cast_slice(bytes)?)
+    }
+}
+
+impl<'a, T: Copy> ImageMut<'a, T> {
+    pub fn new(width: u32, height: u32, data: &'a mut [T]) -> Result<Self, IncorrectData> {
+        check_size(width, height, data.len())?;
+        Ok(Self {
+            width,
+            height,
+            data,
+        })
+    }
+
+    pub fn to_image(&self) -> Image<T> {
+        Image::from_vec(self.width, self.height, self.data.to_vec()).unwrap()
+    }
+}
+
+impl<'a, T: Pod> ImageMut<'a, T> {
+    pub fn from_bytes(width: u32, height: u32, bytes: &'a mut [u8]) -> Result<Self, IncorrectData> {
+        Self::new(width, height, cast_slice_mut(bytes)?)
+    }
+}
+
+macro_rules! frame {
+    ($name:ident) => {
+        impl<'a, T> Frame for $name<'a, T> {
+            type Pixel = T;
+
+            #[inline]
+            fn width(&self) -> u32 {
+                self.width
+            }
+
+            #[inline]
+            fn height(&self) -> u32 {
+                self.height
+            }
+
+            #[inline]
+            fn pixel(&self, x: u32, y: u32) -> &Self::Pixel {
+                debug_assert!(x < self.width);
+                debug_assert!(y < self.height);
+                &self.data[index2d_to_index(self.width, x, y)]
+            }
+
+            #[inline]
+            fn row(&self, row: u32) -> &[Self::Pixel] {
+                debug_assert!(row < self.height);
+                let start = self.width as usize * row as usize;
+                &self.data[start..start + self.width as usize]
+            }
+        }
+
+        impl<'a, T> Deref for $name<'a, T> {
+            type Target = [T];
+
+            fn deref(&self) -> &Self::Target {
+                self.data
+            }
+        }
+    };
+}
+
+frame!(ImageRef);
+frame!(ImageMut);
+
+impl<'a, T> FrameMut for ImageMut<'a, T> {
+    #[inline]
+    fn pixel_mut(&mut self, x: u32, y: u32) -> &mut Self::Pixel {
+        debug_assert!(x < self.width);
+        debug_assert!(y < self.height);
+        &mut self.data[index2d_to_index(self.width, x, y)]
+    }
+
+    #[inline]
+    fn row_mut(&mut self, row: u32) -> &mut [Self::Pixel] {
+        debug_assert!(row < self.height);
+        let start = self.width as usize * row as usize;
+        &mut self.data[start..start + self.width as usize]
+    }
+}
+
+impl<'a, T> DerefMut for ImageMut<'a, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::{RGB32, RGB8};
+
+    #[test]
+    fn from_bytes() {
+        let bytes: Vec<u8> = (0..24).collect();
+        let img = ImageRef::<RGB8>::from_bytes(4, 2, &bytes).unwrap();
+        assert_eq!(img.width(), 4);
+        assert_eq!(*img.pixel(1, 1), RGB8::from([15, 16, 17]));
+        assert_eq!(img.row(1)[0], RGB8::from([12, 13, 14]));
+
+        let owned = img.to_image();
+        assert_eq!(&owned[..], &img[..]);
+    }
+
+    #[test]
+    fn from_bytes_size() {
+        let bytes = [0_u8; 24];
+        assert!(matches!(
+            ImageRef::<RGB8>::from_bytes(3, 3, &bytes),
+            Err(IncorrectData::Size {
+                expected: 9,
+                got: 8
+            })
+        ));
+        assert!(matches!(
+            ImageRef::<RGB8>::from_bytes(2, 2, &bytes[..13]),
+            Err(IncorrectData::Size {
+                expected: 12,
+                got: 13
+            })
+        ));
+    }
+
+    #[test]
+    fn from_bytes_alignment() {
+        let floats = [0_f32; 7];
+        let bytes: &[u8] = cast_slice(&floats).unwrap();
+        assert!(ImageRef::<RGB32>::from_bytes(2, 1, &bytes[..24]).is_ok());
+        assert!(matches!(
+            ImageRef::<RGB32>::from_bytes(2, 1, &bytes[1..25]),
+            Err(IncorrectData::Alignment { expected: 4 })
+        ));
+    }
+
+    #[test]
+    fn image_mut() {
+        let mut bytes = vec![0_u8; 12];
+        {
+            let mut img = ImageMut::<RGB8>::from_bytes(2, 2, &mut bytes).unwrap();
+            *img.pixel_mut(1, 0) = RGB8::from([1, 2, 3]);
+            img.row_mut(1)[1].b = 9;
+            img[2].r = 7;
+        }
+        assert_eq!(bytes, [0, 0, 0, 1, 2, 3, 7, 0, 0, 0, 0, 9]);
+    }
+}
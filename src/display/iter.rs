@@ -0,0 +1,295 @@
+use super::{Buffer, Frame, FrameViewMut, Image, ImageMut};
+use std::{iter::FusedIterator, mem, slice};
+
+/// Rows of a frame from top to bottom
+pub struct Rows<'a, F: ?Sized> {
+    frame: &'a F,
+    front: u32,
+    back: u32,
+}
+
+impl<'a, F: Frame + ?Sized> Rows<'a, F> {
+    pub(crate) fn new(frame: &'a F) -> Self {
+        Self {
+            frame,
+            front: 0,
+            back: frame.height(),
+        }
+    }
+}
+
+impl<'a, F: Frame + ?Sized> Iterator for Rows<'a, F> {
+    type Item = &'a [F::Pixel];
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+        self.front += 1;
+        Some(self.frame.row(self.front - 1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.back - self.front) as usize;
+        (len, Some(len))
+    }
+}
+
+impl<'a, F: Frame + ?Sized> DoubleEndedIterator for Rows<'a, F> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(self.frame.row(self.back))
+    }
+}
+
+impl<'a, F: Frame + ?Sized> ExactSizeIterator for Rows<'a, F> {}
+impl<'a, F: Frame + ?Sized> FusedIterator for Rows<'a, F> {}
+
+/// Pixels of a frame with their coordinates, row by row
+pub struct Pixels<'a, F: Frame + ?Sized> {
+    rows: Rows<'a, F>,
+    row: slice::Iter<'a, F::Pixel>,
+    x: u32,
+    y: u32,
+}
+
+impl<'a, F: Frame + ?Sized> Pixels<'a, F> {
+    pub(crate) fn new(frame: &'a F) -> Self {
+        Self {
+            rows: Rows::new(frame),
+            row: [].iter(),
+            x: 0,
+            y: 0,
+        }
+    }
+}
+
+impl<'a, F: Frame + ?Sized> Iterator for Pixels<'a, F> {
+    type Item = (u32, u32, &'a F::Pixel);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(pixel) = self.row.next() {
+                self.x += 1;
+                return Some((self.x - 1, self.y - 1, pixel));
+            }
+            self.row = self.rows.next()?.iter();
+            self.x = 0;
+            self.y += 1;
+        }
+    }
+}
+
+impl<'a, F: Frame + ?Sized> FusedIterator for Pixels<'a, F> {}
+
+/// Mutable rows of a frame from top to bottom
+pub struct RowsMut<'a, T> {
+    data: &'a mut [T],
+    width: usize,
+    stride: usize,
+    len: usize,
+}
+
+impl<'a, T> RowsMut<'a, T> {
+    /// `height` rows of `width` pixels, `stride` apart in `data`
+    pub(crate) fn new(data: &'a mut [T], width: usize, stride: usize, height: u32) -> Self {
+        Self {
+            data,
+            width,
+            stride,
+            len: height as usize,
+        }
+    }
+}
+
+impl<'a, T> Iterator for RowsMut<'a, T> {
+    type Item = &'a mut [T];
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        let data = mem::take(&mut self.data);
+        // the last row of a view may end before the stride does
+        let (row, rest) = data.split_at_mut(self.stride.min(data.len()));
+        self.data = rest;
+        Some(&mut row[..self.width])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for RowsMut<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        let data = mem::take(&mut self.data);
+        let (rest, row) = data.split_at_mut((self.len * self.stride).min(data.len()));
+        self.data = rest;
+        Some(&mut row[..self.width])
+    }
+}
+
+impl<'a, T> ExactSizeIterator for RowsMut<'a, T> {}
+impl<'a, T> FusedIterator for RowsMut<'a, T> {}
+
+/// Mutable pixels of a frame with their coordinates, row by row
+pub struct PixelsMut<'a, T> {
+    rows: RowsMut<'a, T>,
+    row: slice::IterMut<'a, T>,
+    x: u32,
+    y: u32,
+}
+
+impl<'a, T> PixelsMut<'a, T> {
+    pub(crate) fn new(rows: RowsMut<'a, T>) -> Self {
+        Self {
+            rows,
+            row: [].iter_mut(),
+            x: 0,
+            y: 0,
+        }
+    }
+}
+
+impl<'a, T> Iterator for PixelsMut<'a, T> {
+    type Item = (u32, u32, &'a mut T);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(pixel) = self.row.next() {
+                self.x += 1;
+                return Some((self.x - 1, self.y - 1, pixel));
+            }
+            self.row = self.rows.next()?.iter_mut();
+            self.x = 0;
+            self.y += 1;
+        }
+    }
+}
+
+impl<'a, T> FusedIterator for PixelsMut<'a, T> {}
+
+macro_rules! iter_mut {
+    ($name:ident, $($lt:lifetime)?) => {
+        impl<$($lt,)? T: Copy> $name<$($lt,)? T> {
+            pub fn rows_mut(&mut self) -> RowsMut<'_, T> {
+                let (width, height) = (self.width(), self.height());
+                RowsMut::new(&mut self[..], width as usize, width as usize, height)
+            }
+
+            /// Mutable pixels with their `(x, y)` coordinates
+            pub fn pixels_mut(&mut self) -> PixelsMut<'_, T> {
+                PixelsMut::new(self.rows_mut())
+            }
+        }
+    };
+}
+
+iter_mut!(Buffer,);
+iter_mut!(Image,);
+iter_mut!(ImageMut, 'a);
+
+impl<'a, T> FrameViewMut<'a, T> {
+    pub fn rows_mut(&mut self) -> RowsMut<'_, T> {
+        let (width, height, stride) = (self.width(), self.height(), self.pixel_stride());
+        RowsMut::new(self.data_mut(), width as usize, stride, height)
+    }
+
+    pub fn pixels_mut(&mut self) -> PixelsMut<'_, T> {
+        PixelsMut::new(self.rows_mut())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::display::FrameView;
+
+    fn image() -> Image<u32> {
+        Image::from_vec(3, 2, (0..6).collect()).unwrap()
+    }
+
+    #[test]
+    fn rows() {
+        let img = image();
+        let rows: Vec<&[u32]> = img.rows().collect();
+        assert_eq!(rows, vec![&[0, 1, 2][..], &[3, 4, 5][..]]);
+        assert_eq!(img.rows().len(), 2);
+        assert_eq!(img.rows().next_back().unwrap(), &[3, 4, 5]);
+
+        let view = img.view(1, 0, 2, 2);
+        let rows: Vec<&[u32]> = view.rows().collect();
+        assert_eq!(rows, vec![&[1, 2][..], &[4, 5][..]]);
+    }
+
+    #[test]
+    fn pixels() {
+        let img = image();
+        let pixels: Vec<(u32, u32, u32)> = img.pixels().map(|(x, y, p)| (x, y, *p)).collect();
+        assert_eq!(
+            pixels,
+            vec![
+                (0, 0, 0),
+                (1, 0, 1),
+                (2, 0, 2),
+                (0, 1, 3),
+                (1, 1, 4),
+                (2, 1, 5)
+            ]
+        );
+
+        let empty = FrameView::<u32>::new(0, 3, 0, &[]).unwrap();
+        assert_eq!(empty.pixels().count(), 0);
+    }
+
+    #[test]
+    fn rows_mut() {
+        let mut img = image();
+        for (y, row) in img.rows_mut().enumerate() {
+            row[0] = 10 * y as u32;
+        }
+        assert_eq!(&img[..], &[0, 1, 2, 10, 4, 5]);
+
+        let mut view = img.view_mut(1, 0, 2, 2);
+        assert_eq!(view.rows_mut().len(), 2);
+        for row in view.rows_mut().rev() {
+            row[1] = 7;
+        }
+        assert_eq!(&img[..], &[0, 1, 7, 10, 4, 7]);
+
+        let mut empty = Image::<u32>::from_vec(0, 3, Vec::new()).unwrap();
+        assert_eq!(empty.rows().len(), 3);
+        assert_eq!(empty.rows_mut().len(), 3);
+        assert!(empty.rows_mut().rev().all(|x| x.is_empty()));
+    }
+
+    #[test]
+    fn pixels_mut() {
+        let mut img = image();
+        for (x, y, p) in img.pixels_mut() {
+            *p = x * 100 + y;
+        }
+        assert_eq!(&img[..], &[0, 100, 200, 1, 101, 201]);
+
+        let mut view = img.view_mut(0, 1, 2, 1);
+        for (x, y, p) in view.pixels_mut() {
+            *p = x + y;
+        }
+        assert_eq!(&img[..], &[0, 100, 200, 0, 1, 201]);
+    }
+}
@@ -1,8 +1,21 @@
 mod buffer;
 mod image;
+mod image_ref;
+mod iter;
+#[cfg(feature = "rayon")]
+mod par;
+mod planes;
+#[cfg(feature = "serde")]
+mod serialize;
+mod strided;
+mod view;
 
 pub use self::buffer::Buffer;
 pub use self::image::Image;
+pub use self::image_ref::{ImageMut, ImageRef};
+pub use self::iter::{Pixels, PixelsMut, Rows, RowsMut};
+pub use self::strided::{Storage, StorageMut, StridedFrame};
+pub use self::view::{FrameView, FrameViewMut};
 
 pub trait Frame {
     type Pixel;
@@ -13,10 +26,21 @@ pub trait Frame {
 
     fn pixel(&self, x: u32, y: u32) -> &Self::Pixel;
 
-    fn pixel_mut(&mut self, x: u32, y: u32) -> &mut Self::Pixel;
-
     fn row(&self, row: u32) -> &[Self::Pixel];
 
+    fn rows(&self) -> Rows<'_, Self> {
+        Rows::new(self)
+    }
+
+    /// Pixels with their `(x, y)` coordinates
+    fn pixels(&self) -> Pixels<'_, Self> {
+        Pixels::new(self)
+    }
+}
+
+pub trait FrameMut: Frame {
+    fn pixel_mut(&mut self, x: u32, y: u32) -> &mut Self::Pixel;
+
     fn row_mut(&mut self, row: u32) -> &mut [Self::Pixel];
 }
 
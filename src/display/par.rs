@@ -0,0 +1,263 @@
+use super::{Buffer, Frame, FrameView, FrameViewMut, Image};
+use crate::{color::convert_vec, error::IncorrectData};
+use rayon::{iter::Either, prelude::*};
+
+/// Pixels converted by one task of `par_convert`
+const CONVERT_CHUNK: usize = 16 * 1024;
+
+macro_rules! par_frame {
+    ($name:ident) => {
+        impl<T: Copy + Send + Sync> $name<T> {
+            pub fn par_rows(&self) -> impl IndexedParallelIterator<Item = &[T]> {
+                (0..self.height()).into_par_iter().map(move |y| self.row(y))
+            }
+
+            pub fn par_rows_mut(&mut self) -> impl IndexedParallelIterator<Item = &mut [T]> {
+                let (width, height) = (self.width() as usize, self.height());
+                // a zero-width frame still has `height` empty rows
+                match width {
+                    0 => Either::Left((0..height).into_par_iter().map(|_| <&mut [T]>::default())),
+                    _ => Either::Right(self[..].par_chunks_mut(width)),
+                }
+            }
+
+            /// Pixels with their `(x, y)` coordinates
+            pub fn par_pixels(&self) -> impl ParallelIterator<Item = (u32, u32, &T)> {
+                self.par_rows().enumerate().flat_map(|(y, row)| {
+                    row.par_iter()
+                        .enumerate()
+                        .map(move |(x, p)| (x as u32, y as u32, p))
+                })
+            }
+
+            pub fn par_pixels_mut(&mut self) -> impl ParallelIterator<Item = (u32, u32, &mut T)> {
+                self.par_rows_mut().enumerate().flat_map(|(y, row)| {
+                    row.par_iter_mut()
+                        .enumerate()
+                        .map(move |(x, p)| (x as u32, y as u32, p))
+                })
+            }
+
+            /// Windows of at most `width` x `height` pixels with the
+            /// coordinates of their top left corner
+            pub fn par_tiles(
+                &self,
+                width: u32,
+                height: u32,
+            ) -> impl ParallelIterator<Item = (u32, u32, FrameView<'_, T>)> {
+                assert!(width > 0 && height > 0);
+                let (w, h) = (self.width(), self.height());
+                let view = self.view(0, 0, w, h);
+                let tiles_x = w.div_ceil(width);
+                let tiles_y = h.div_ceil(height);
+
+                (0..tiles_x * tiles_y).into_par_iter().map(move |i| {
+                    let x = i % tiles_x * width;
+                    let y = i / tiles_x * height;
+                    let tile = view.view(x, y, width.min(w - x), height.min(h - y));
+                    (x, y, tile)
+                })
+            }
+
+            /// Disjoint bands of `rows` full rows with the `y` of their
+            /// first row
+            pub fn par_bands_mut(
+                &mut self,
+                rows: u32,
+            ) -> impl IndexedParallelIterator<Item = (u32, FrameViewMut<'_, T>)> {
+                let (w, h) = (self.width(), self.height());
+                let bands: Vec<_> = self.view_mut(0, 0, w, h).chunks(rows).collect();
+                bands
+                    .into_par_iter()
+                    .enumerate()
+                    .map(move |(i, band)| (i as u32 * rows, band))
+            }
+        }
+    };
+}
+
+par_frame!(Buffer);
+par_frame!(Image);
+
+impl<T: Copy + Send + Sync> Image<T> {
+    /// Parallel `Image::from_fn`
+    pub fn par_from_fn<F>(width: u32, height: u32, f: F) -> Result<Self, IncorrectData>
+    where
+        F: Fn(u32, u32) -> T + Sync + Send,
+    {
+        let len = (width as usize)
+            .checked_mul(height as usize)
+            .filter(|x| x.checked_mul(std::mem::size_of::<T>()).is_some())
+            .ok_or(IncorrectData::Overflow)?;
+
+        let w = width.max(1) as usize;
+        let data = (0..len)
+            .into_par_iter()
+            .map(|i| f((i % w) as u32, (i / w) as u32))
+            .collect();
+        Self::from_vec(width, height, data)
+    }
+
+    /// Parallel `Image::map`
+    pub fn par_map<U, F>(&self, f: F) -> Image<U>
+    where
+        U: Copy + Send,
+        F: Fn(T) -> U + Sync + Send,
+    {
+        let data = self.par_iter().map(|x| f(*x)).collect();
+        Image::from_vec(self.width(), self.height(), data).unwrap()
+    }
+
+    pub fn par_map_in_place<F: Fn(T) -> T + Sync + Send>(&mut self, f: F) {
+        self[..].par_iter_mut().for_each(|x| *x = f(*x));
+    }
+
+    /// Parallel `Image::convert`
+    pub fn par_convert<U>(&self) -> Image<U>
+    where
+        T: Into<U> + 'static,
+        U: Copy + Send + 'static,
+    {
+        let data = self
+            .par_chunks(CONVERT_CHUNK)
+            .flat_map_iter(convert_vec::<T, U>)
+            .collect();
+        Image::from_vec(self.width(), self.height(), data).unwrap()
+    }
+
+    /// Parallel `Image::mirror_left_right`
+    pub fn par_mirror_left_right(&mut self) {
+        self.par_rows_mut().for_each(|row| row.reverse());
+    }
+
+    /// Parallel `Image::mirror_top_bottom`
+    pub fn par_mirror_top_bottom(&mut self) {
+        let (w, h) = (self.width() as usize, self.height() as usize);
+        let (top, bottom) = self[..].split_at_mut(w * (h / 2));
+        let bottom = &mut bottom[w * (h % 2)..];
+        top.par_chunks_mut(w.max(1))
+            .zip(bottom.par_chunks_mut(w.max(1)).rev())
+            .for_each(|(a, b)| a.swap_with_slice(b));
+    }
+
+    /// Parallel `Image::rotate90`
+    pub fn par_rotate90(&mut self) {
+        let (w, h) = (self.width() as usize, self.height() as usize);
+        let src = &self[..];
+        let data = (0..src.len())
+            .into_par_iter()
+            .map(|i| src[(h - 1 - i % h.max(1)) * w + i / h.max(1)])
+            .collect();
+        *self = Image::from_vec(h as u32, w as u32, data).unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::{RGB32, RGB8};
+
+    fn image(width: u32, height: u32) -> Image<RGB8> {
+        Image::from_fn(width, height, |x, y| {
+            RGB8::from([x as u8, y as u8, (x * 7 + y * 13) as u8])
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn from_fn() {
+        let f = |x: u32, y: u32| x * 1000 + y;
+        let seq = Image::from_fn(37, 23, f).unwrap();
+        let par = Image::par_from_fn(37, 23, f).unwrap();
+        assert_eq!(&seq[..], &par[..]);
+        assert_eq!(Image::par_from_fn(0, 5, f).unwrap().len(), 0);
+    }
+
+    #[test]
+    fn map() {
+        let img = image(40, 30);
+        let f = |p: RGB8| p.r as u32 + p.b as u32;
+        assert_eq!(&img.map(f)[..], &img.par_map(f)[..]);
+
+        let mut a = image(40, 30);
+        let mut b = image(40, 30);
+        let g = |p: RGB8| RGB8::from([p.b, p.g, p.r]);
+        a.map_in_place(g);
+        b.par_map_in_place(g);
+        assert_eq!(&a[..], &b[..]);
+    }
+
+    #[test]
+    fn convert() {
+        let img = image(300, 200);
+        let seq: Image<RGB32> = img.convert();
+        let par: Image<RGB32> = img.par_convert();
+        assert_eq!(&seq[..], &par[..]);
+
+        let seq: Image<u8> = img.convert();
+        let par: Image<u8> = img.par_convert();
+        assert_eq!(&seq[..], &par[..]);
+    }
+
+    #[test]
+    fn rows_and_pixels() {
+        let mut img = image(5, 4);
+        assert_eq!(img.par_rows().count(), 4);
+        let rows: Vec<&[RGB8]> = img.par_rows().collect();
+        assert_eq!(rows[2], img.row(2));
+
+        let sum: u32 = img.par_pixels().map(|(x, y, _)| x + y).sum();
+        let expected: u32 = img.pixels().map(|(x, y, _)| x + y).sum();
+        assert_eq!(sum, expected);
+
+        img.par_pixels_mut()
+            .for_each(|(x, y, p)| p.g = (x * y) as u8);
+        assert_eq!(img.pixel(4, 3).g, 12);
+
+        let mut empty = image(0, 3);
+        assert_eq!(empty.par_rows().count(), 3);
+        assert_eq!(empty.par_rows_mut().count(), 3);
+        assert_eq!(empty.par_pixels().count(), 0);
+    }
+
+    #[test]
+    fn tiles_and_bands() {
+        let img = image(10, 7);
+        let mut tiles: Vec<(u32, u32, u32, u32)> = img
+            .par_tiles(4, 3)
+            .map(|(x, y, t)| (x, y, t.width(), t.height()))
+            .collect();
+        tiles.sort();
+        assert_eq!(tiles.len(), 9);
+        assert_eq!(tiles[0], (0, 0, 4, 3));
+        assert_eq!(tiles[8], (8, 6, 2, 1));
+
+        let mut img = image(10, 7);
+        img.par_bands_mut(3)
+            .for_each(|(y, mut band)| band.fill(RGB8::from([y as u8, 0, 0])));
+        assert_eq!(img.pixel(0, 2).r, 0);
+        assert_eq!(img.pixel(9, 5).r, 3);
+        assert_eq!(img.pixel(3, 6).r, 6);
+    }
+
+    #[test]
+    fn transforms() {
+        for (w, h) in [(1, 1), (4, 4), (7, 3), (2, 9), (1, 5)] {
+            let mut seq = image(w, h);
+            let mut par = image(w, h);
+
+            seq.mirror_left_right();
+            par.par_mirror_left_right();
+            assert_eq!(&seq[..], &par[..]);
+
+            seq.mirror_top_bottom();
+            par.par_mirror_top_bottom();
+            assert_eq!(&seq[..], &par[..]);
+
+            seq.rotate90();
+            par.par_rotate90();
+            assert_eq!((seq.width(), seq.height()), (par.width(), par.height()));
+            assert_eq!(&seq[..], &par[..]);
+        }
+    }
+}
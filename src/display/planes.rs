@@ -0,0 +1,182 @@
+use super::{Frame, Image};
+use crate::{color::Pixel, error::IncorrectData};
+
+impl<P: Pixel> Image<P> {
+    /// Single channel image of the channel at `index` in storage order
+    pub fn channel(&self, index: usize) -> Image<P::Channel> {
+        assert!(index < P::CHANNELS);
+        let data = self.iter().map(|x| x.channels()[index]).collect();
+        Image::from_vec(self.width(), self.height(), data).unwrap()
+    }
+
+    /// One image per channel in storage order, e.g. b, g, r for `BGR`
+    pub fn split(&self) -> Vec<Image<P::Channel>> {
+        (0..P::CHANNELS).map(|i| self.channel(i)).collect()
+    }
+
+    /// Inverse of `split`, all planes must have the same size
+    pub fn merge(planes: &[Image<P::Channel>]) -> Result<Self, IncorrectData> {
+        if planes.len() != P::CHANNELS {
+            return Err(IncorrectData::Size {
+                expected: P::CHANNELS,
+                got: planes.len(),
+            });
+        }
+
+        let width = planes[0].width();
+        let height = planes[0].height();
+        for plane in planes {
+            if plane.width() != width || plane.height() != height {
+                return Err(IncorrectData::Size {
+                    expected: width as usize * height as usize,
+                    got: plane.len(),
+                });
+            }
+        }
+
+        let mut channels = vec![P::Channel::default(); P::CHANNELS];
+        let data = (0..planes[0].len())
+            .map(|i| {
+                for (c, plane) in channels.iter_mut().zip(planes) {
+                    *c = plane[i];
+                }
+                P::from_channels(&channels)
+            })
+            .collect();
+
+        Image::from_vec(width, height, data)
+    }
+
+    /// Channel `i` of every pixel becomes its old channel `order[i]`
+    ///
+    /// `[2, 1, 0]` reverses RGB, `[1, 2, 0]` gives GBR and `[0, 0, 0]`
+    /// replicates red.
+    pub fn swizzle(&mut self, order: &[usize]) {
+        assert_eq!(order.len(), P::CHANNELS);
+        assert!(order.iter().all(|x| *x < P::CHANNELS));
+
+        let mut old = vec![P::Channel::default(); P::CHANNELS];
+        for pixel in self[..].iter_mut() {
+            old.copy_from_slice(pixel.channels());
+            for (c, i) in pixel.channels_mut().iter_mut().zip(order) {
+                *c = old[*i];
+            }
+        }
+    }
+
+    /// Overwrite the channel at `index` with a single channel image
+    pub fn set_channel(
+        &mut self,
+        index: usize,
+        plane: &Image<P::Channel>,
+    ) -> Result<(), IncorrectData> {
+        assert!(index < P::CHANNELS);
+        if plane.width() != self.width() || plane.height() != self.height() {
+            return Err(IncorrectData::Size {
+                expected: self.len(),
+                got: plane.len(),
+            });
+        }
+
+        for (pixel, c) in self[..].iter_mut().zip(plane.iter()) {
+            pixel.channels_mut()[index] = *c;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::{BGR8, RGB32, RGB8};
+
+    fn image() -> Image<RGB8> {
+        let data = (0..12_u8)
+            .map(|i| RGB8::from([i, i + 100, 200 - i]))
+            .collect();
+        Image::from_vec(4, 3, data).unwrap()
+    }
+
+    #[test]
+    fn channel() {
+        let img = image();
+        let red = img.channel(0);
+        assert_eq!(red.width(), 4);
+        assert_eq!(red.height(), 3);
+        assert_eq!(red[5], 5);
+        assert_eq!(img.channel(2)[5], 195);
+    }
+
+    #[test]
+    fn split_merge() {
+        let img = image();
+        let planes = img.split();
+        assert_eq!(planes.len(), 3);
+        assert_eq!(*planes[1].pixel(1, 1), 105);
+
+        let merged = Image::<RGB8>::merge(&planes).unwrap();
+        assert_eq!(&merged[..], &img[..]);
+
+        let mut planes = planes;
+        planes.reverse();
+        let bgr = Image::<BGR8>::merge(&planes).unwrap();
+        assert_eq!(bgr[3], BGR8::from(img[3]));
+    }
+
+    #[test]
+    fn merge_size() {
+        let mut planes = image().split();
+        assert!(matches!(
+            Image::<RGB8>::merge(&planes[..2]),
+            Err(IncorrectData::Size {
+                expected: 3,
+                got: 2
+            })
+        ));
+
+        planes[1] = Image::new(2, 2, 0_u8).unwrap();
+        assert!(matches!(
+            Image::<RGB8>::merge(&planes),
+            Err(IncorrectData::Size {
+                expected: 12,
+                got: 4
+            })
+        ));
+    }
+
+    #[test]
+    fn swizzle() {
+        let mut img = image();
+        img.swizzle(&[2, 1, 0]);
+        assert_eq!(img[1], RGB8::from([199, 101, 1]));
+
+        let mut img = image();
+        img.swizzle(&[1, 2, 0]);
+        assert_eq!(img[1], RGB8::from([101, 199, 1]));
+
+        let mut img: Image<RGB32> = Image::new(2, 2, RGB32::from([0.5, 0.0, 1.0])).unwrap();
+        img.swizzle(&[0, 0, 0]);
+        assert_eq!(img[3], RGB32::from([0.5, 0.5, 0.5]));
+    }
+
+    #[test]
+    fn empty() {
+        let mut img: Image<RGB8> = Image::from_vec(0, 3, Vec::new()).unwrap();
+        img.swizzle(&[2, 1, 0]);
+        assert_eq!(img.len(), 0);
+
+        let merged = Image::<RGB8>::merge(&img.split()).unwrap();
+        assert_eq!((merged.width(), merged.height()), (0, 3));
+    }
+
+    #[test]
+    fn set_channel() {
+        let mut img = image();
+        let zero = Image::new(4, 3, 0_u8).unwrap();
+        img.set_channel(1, &zero).unwrap();
+        assert_eq!(img[2], RGB8::from([2, 0, 198]));
+
+        let small = Image::new(1, 1, 0_u8).unwrap();
+        assert!(img.set_channel(1, &small).is_err());
+    }
+}
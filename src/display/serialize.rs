@@ -0,0 +1,276 @@
+use super::{Buffer, Image};
+use crate::{
+    color::{cast_slice, cast_vec, BGR8, RGB8},
+    error::IncorrectData,
+};
+use serde::{
+    de::{self, SeqAccess, Visitor},
+    ser::SerializeStruct,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use std::{
+    any::{Any, TypeId},
+    fmt, mem,
+};
+
+// Byte images are written as a single byte string, which bincode stores
+// without per-pixel overhead and JSON as a flat array of numbers
+
+impl<T: Copy + Serialize + 'static> Serialize for Buffer<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_frame("Buffer", self, serializer)
+    }
+}
+
+impl<T: Copy + Serialize + 'static> Serialize for Image<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_frame("Image", &self.buffer, serializer)
+    }
+}
+
+impl<'de, T: Copy + Deserialize<'de> + 'static> Deserialize<'de> for Buffer<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = Raw::<T>::deserialize(deserializer)?;
+        Buffer::from_vec(raw.width, raw.height, raw.data.0).map_err(de::Error::custom)
+    }
+}
+
+impl<'de, T: Copy + Deserialize<'de> + 'static> Deserialize<'de> for Image<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = Raw::<T>::deserialize(deserializer)?;
+        Image::from_vec(raw.width, raw.height, raw.data.0).map_err(de::Error::custom)
+    }
+}
+
+fn serialize_frame<T, S>(
+    name: &'static str,
+    buffer: &Buffer<T>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    T: Copy + Serialize + 'static,
+    S: Serializer,
+{
+    let mut state = serializer.serialize_struct(name, 3)?;
+    state.serialize_field("width", &buffer.width)?;
+    state.serialize_field("height", &buffer.height)?;
+    state.serialize_field("data", &Data(&buffer.data))?;
+    state.end()
+}
+
+#[derive(Deserialize)]
+#[serde(bound = "T: Deserialize<'de> + 'static")]
+struct Raw<T> {
+    width: u32,
+    height: u32,
+    data: DataBuf<T>,
+}
+
+struct Data<'a, T>(&'a Vec<T>);
+
+impl<'a, T: Serialize + 'static> Serialize for Data<'a, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match as_bytes(self.0) {
+            Some(bytes) => serializer.serialize_bytes(bytes),
+            None => self.0.serialize(serializer),
+        }
+    }
+}
+
+struct DataBuf<T>(Vec<T>);
+
+impl<'de, T: Deserialize<'de> + 'static> Deserialize<'de> for DataBuf<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if !is_byte_pixel::<T>() {
+            return Vec::deserialize(deserializer).map(DataBuf);
+        }
+
+        let bytes = deserializer.deserialize_bytes(BytesVisitor)?;
+        if bytes.len() % mem::size_of::<T>() != 0 {
+            return Err(de::Error::invalid_length(bytes.len(), &"whole pixels"));
+        }
+        from_bytes(bytes).map(DataBuf).map_err(de::Error::custom)
+    }
+}
+
+struct BytesVisitor;
+
+impl<'de> Visitor<'de> for BytesVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("pixel bytes")
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        Ok(v.to_vec())
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+        Ok(v)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(x) = seq.next_element()? {
+            bytes.push(x);
+        }
+        Ok(bytes)
+    }
+}
+
+fn is_byte_pixel<T: 'static>() -> bool {
+    let id = TypeId::of::<T>();
+    id == TypeId::of::<u8>() || id == TypeId::of::<RGB8>() || id == TypeId::of::<BGR8>()
+}
+
+/// The pixels of `data` as bytes, `None` unless it holds byte pixels
+fn as_bytes(data: &dyn Any) -> Option<&[u8]> {
+    if let Some(data) = data.downcast_ref::<Vec<u8>>() {
+        Some(data)
+    } else if let Some(data) = data.downcast_ref::<Vec<RGB8>>() {
+        cast_slice(data).ok()
+    } else {
+        cast_slice(data.downcast_ref::<Vec<BGR8>>()?).ok()
+    }
+}
+
+/// `T` is one of the byte pixels
+fn from_bytes<T: 'static>(bytes: Vec<u8>) -> Result<Vec<T>, IncorrectData> {
+    let id = TypeId::of::<T>();
+    let pixels: Box<dyn Any> = if id == TypeId::of::<RGB8>() {
+        Box::new(cast_vec::<u8, RGB8>(bytes)?)
+    } else if id == TypeId::of::<BGR8>() {
+        Box::new(cast_vec::<u8, BGR8>(bytes)?)
+    } else {
+        Box::new(bytes)
+    };
+    Ok(*pixels.downcast().expect("not a byte pixel"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        color::{f16, HSL, RGB16F, RGB32},
+        display::Frame,
+        geometry::{Vec2, Vec3},
+    };
+
+    fn image() -> Image<RGB8> {
+        let data = (0..6_u8).map(|i| RGB8::from([i, 10 + i, 20 + i])).collect();
+        Image::from_vec(3, 2, data).unwrap()
+    }
+
+    #[test]
+    fn colors() {
+        let rgb = RGB8::from([1, 2, 3]);
+        assert_eq!(
+            serde_json::to_string(&rgb).unwrap(),
+            r#"{"r":1,"g":2,"b":3}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<RGB8>(r#"{"r":1,"g":2,"b":3}"#).unwrap(),
+            rgb
+        );
+
+        let bgr = BGR8::from([1, 2, 3]);
+        let json = serde_json::to_string(&bgr).unwrap();
+        assert_eq!(serde_json::from_str::<BGR8>(&json).unwrap(), bgr);
+
+        let hsl = HSL {
+            h: 120.0,
+            s: 0.5,
+            l: 0.25,
+        };
+        let json = serde_json::to_string(&hsl).unwrap();
+        assert_eq!(serde_json::from_str::<HSL>(&json).unwrap(), hsl);
+
+        let half = RGB16F::from([f16::ONE, f16::ZERO, f16::MAX]);
+        let bytes = bincode::serialize(&half).unwrap();
+        assert_eq!(bytes.len(), 6);
+        assert_eq!(bincode::deserialize::<RGB16F>(&bytes).unwrap(), half);
+
+        let json = serde_json::to_string(&half).unwrap();
+        assert_eq!(json, r#"{"r":1.0,"g":0.0,"b":65504.0}"#);
+        assert_eq!(serde_json::from_str::<RGB16F>(&json).unwrap(), half);
+        assert_eq!(
+            serde_json::from_str::<f16>("0.5").unwrap(),
+            f16::from_f32(0.5)
+        );
+    }
+
+    #[test]
+    fn geometry() {
+        let v = Vec2 { x: 1, y: -2 };
+        let json = serde_json::to_string(&v).unwrap();
+        assert_eq!(serde_json::from_str::<Vec2<i32>>(&json).unwrap(), v);
+
+        let v = Vec3 { x: 1, y: -2, z: 3 };
+        let json = serde_json::to_string(&v).unwrap();
+        assert_eq!(serde_json::from_str::<Vec3<i32>>(&json).unwrap(), v);
+    }
+
+    #[test]
+    fn byte_image_json() {
+        let json = serde_json::to_string(&image()).unwrap();
+        assert_eq!(
+            json,
+            r#"{"width":3,"height":2,"data":[0,10,20,1,11,21,2,12,22,3,13,23,4,14,24,5,15,25]}"#
+        );
+
+        let img: Image<RGB8> = serde_json::from_str(&json).unwrap();
+        assert_eq!(&img[..], &image()[..]);
+        assert_eq!((img.width(), img.height()), (3, 2));
+    }
+
+    #[test]
+    fn byte_image_bincode() {
+        let bytes = bincode::serialize(&image()).unwrap();
+        // width, height, length prefix and the raw components
+        assert_eq!(bytes.len(), 4 + 4 + 8 + 18);
+
+        let img: Image<RGB8> = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(&img[..], &image()[..]);
+
+        let gray = Buffer::new(4, 4, 7_u8).unwrap();
+        let bytes = bincode::serialize(&gray).unwrap();
+        assert_eq!(bytes.len(), 4 + 4 + 8 + 16);
+        let gray: Buffer<u8> = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(gray.data, vec![7; 16]);
+    }
+
+    #[test]
+    fn float_image() {
+        let img = Image::new(2, 1, RGB32::from([0.5, 0.25, 1.0])).unwrap();
+        let json = serde_json::to_string(&img).unwrap();
+        assert_eq!(
+            json,
+            r#"{"width":2,"height":1,"data":[{"r":0.5,"g":0.25,"b":1.0},{"r":0.5,"g":0.25,"b":1.0}]}"#
+        );
+
+        let back: Image<RGB32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(&back[..], &img[..]);
+
+        let bytes = bincode::serialize(&img).unwrap();
+        let back: Image<RGB32> = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(&back[..], &img[..]);
+    }
+
+    #[test]
+    fn size_mismatch() {
+        let json = r#"{"width":2,"height":2,"data":[0,1,2]}"#;
+        let err = serde_json::from_str::<Buffer<u8>>(json).err().unwrap();
+        let expected = IncorrectData::Size {
+            expected: 4,
+            got: 3,
+        };
+        assert!(err.to_string().starts_with(&expected.to_string()));
+
+        let json = r#"{"width":1,"height":1,"data":[0,1]}"#;
+        assert!(serde_json::from_str::<Image<RGB8>>(json).is_err());
+
+        let json = r#"{"width":1,"height":2,"data":[{"r":0.5,"g":0.25,"b":1.0}]}"#;
+        assert!(serde_json::from_str::<Image<RGB32>>(json).is_err());
+    }
+}
@@ -0,0 +1,270 @@
+use super::{Frame, FrameMut, Image};
+use crate::{color::Pod, error::IncorrectData};
+use std::{marker::PhantomData, mem, rc::Rc, slice, sync::Arc};
+
+/// Memory a `StridedFrame` can live in
+///
+/// # Safety
+///
+/// `as_slice` must return the same memory on every call, `StridedFrame`
+/// checks it once when it is created.
+pub unsafe trait Storage {
+    type Elem: Pod;
+
+    fn as_slice(&self) -> &[Self::Elem];
+}
+
+/// Storage with unique, writable access
+///
+/// # Safety
+///
+/// `as_mut_slice` must return the same memory as `as_slice`.
+pub unsafe trait StorageMut: Storage {
+    fn as_mut_slice(&mut self) -> &mut [Self::Elem];
+}
+
+macro_rules! storage {
+    ($($t:ty),*) => {
+        $(
+            unsafe impl<'a, E: Pod> Storage for $t {
+                type Elem = E;
+
+                #[inline]
+                fn as_slice(&self) -> &[E] {
+                    self
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! storage_mut {
+    ($($t:ty),*) => {
+        $(
+            unsafe impl<'a, E: Pod> StorageMut for $t {
+                #[inline]
+                fn as_mut_slice(&mut self) -> &mut [E] {
+                    self
+                }
+            }
+        )*
+    };
+}
+
+storage!(Vec<E>, Box<[E]>, Arc<[E]>, Rc<[E]>, &'a [E], &'a mut [E]);
+storage_mut!(Vec<E>, Box<[E]>, &'a mut [E]);
+
+/// Frame over any storage with rows `stride` bytes apart
+///
+/// Lets capture buffers with padded rows, e.g. a `&mut [u8]` of RGB8 pixels,
+/// be processed in place. Pixels are writable when the storage is
+/// `StorageMut`.
+pub struct StridedFrame<P, S> {
+    width: u32,
+    height: u32,
+    stride: usize,
+    data: S,
+    pixel: PhantomData<P>,
+}
+
+impl<P: Pod, S: Storage> StridedFrame<P, S> {
+    /// Fails when a row does not fit into `stride`, the rows do not fit into
+    /// `data` or the rows are not aligned for `P`
+    pub fn new(width: u32, height: u32, stride: usize, data: S) -> Result<Self, IncorrectData> {
+        let row = (width as usize)
+            .checked_mul(mem::size_of::<P>())
+            .ok_or(IncorrectData::Overflow)?;
+        if stride < row {
+            return Err(IncorrectData::Size {
+                expected: row,
+                got: stride,
+            });
+        }
+
+        let expected = match height {
+            0 => 0,
+            _ => (height as usize - 1)
+                .checked_mul(stride)
+                .and_then(|x| x.checked_add(row))
+                .ok_or(IncorrectData::Overflow)?,
+        };
+        let got = mem::size_of_val(data.as_slice());
+        if got < expected {
+            return Err(IncorrectData::Size { expected, got });
+        }
+
+        let align = mem::align_of::<P>();
+        let address = data.as_slice().as_ptr() as usize;
+        if !address.is_multiple_of(align) || !stride.is_multiple_of(align) {
+            return Err(IncorrectData::Alignment { expected: align });
+        }
+
+        Ok(Self {
+            width,
+            height,
+            stride,
+            data,
+            pixel: PhantomData,
+        })
+    }
+
+    /// Distance between rows in bytes
+    #[inline]
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    #[inline]
+    pub fn storage(&self) -> &S {
+        &self.data
+    }
+
+    #[inline]
+    pub fn into_storage(self) -> S {
+        self.data
+    }
+
+    /// Copy into a tightly packed image
+    pub fn to_image(&self) -> Image<P> {
+        let mut data = Vec::with_capacity(self.width as usize * self.height as usize);
+        for y in 0..self.height {
+            data.extend_from_slice(self.row(y));
+        }
+        Image::from_vec(self.width, self.height, data).unwrap()
+    }
+
+    /// Byte offset of the first pixel of `row`
+    #[inline]
+    fn offset(&self, row: u32) -> usize {
+        assert!(
+            row < self.height,
+            "row {} is outside of {}",
+            row,
+            self.height
+        );
+        row as usize * self.stride
+    }
+}
+
+impl<P: Pod, S: Storage> Frame for StridedFrame<P, S> {
+    type Pixel = P;
+
+    #[inline]
+    fn width(&self) -> u32 {
+        self.width
+    }
+
+    #[inline]
+    fn height(&self) -> u32 {
+        self.height
+    }
+
+    #[inline]
+    fn pixel(&self, x: u32, y: u32) -> &Self::Pixel {
+        debug_assert!(x < self.width);
+        &self.row(y)[x as usize]
+    }
+
+    #[inline]
+    fn row(&self, row: u32) -> &[Self::Pixel] {
+        let offset = self.offset(row);
+        let data = self.data.as_slice().as_ptr() as *const u8;
+        // `new` checked that every row is inside the storage and aligned
+        unsafe { slice::from_raw_parts(data.add(offset) as *const P, self.width as usize) }
+    }
+}
+
+impl<P: Pod, S: StorageMut> FrameMut for StridedFrame<P, S> {
+    #[inline]
+    fn pixel_mut(&mut self, x: u32, y: u32) -> &mut Self::Pixel {
+        debug_assert!(x < self.width);
+        &mut self.row_mut(y)[x as usize]
+    }
+
+    #[inline]
+    fn row_mut(&mut self, row: u32) -> &mut [Self::Pixel] {
+        let offset = self.offset(row);
+        let data = self.data.as_mut_slice().as_mut_ptr() as *mut u8;
+        // same as in `row`
+        unsafe { slice::from_raw_parts_mut(data.add(offset) as *mut P, self.width as usize) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::{RGB32, RGB8};
+
+    fn brighten<F: FrameMut<Pixel = RGB8>>(frame: &mut F) {
+        for y in 0..frame.height() {
+            for x in frame.row_mut(y) {
+                x.r = x.r.saturating_add(100);
+            }
+        }
+    }
+
+    #[test]
+    fn padded_bytes() {
+        // 5 RGB8 pixels use 15 bytes of each 16 byte row
+        let mut bytes: Vec<u8> = (0..48).collect();
+        {
+            let mut frame = StridedFrame::<RGB8, _>::new(5, 3, 16, &mut bytes[..]).unwrap();
+            assert_eq!(*frame.pixel(0, 1), RGB8::from([16, 17, 18]));
+            assert_eq!(frame.row(2)[4], RGB8::from([44, 45, 46]));
+            brighten(&mut frame);
+        }
+        assert_eq!(bytes[16], 116);
+        assert_eq!(bytes[15], 15);
+        assert_eq!(bytes[31], 31);
+    }
+
+    #[test]
+    fn storages() {
+        let pixels: Vec<RGB8> = (0..6_u8).map(|i| RGB8::from([i, i, i])).collect();
+
+        let frame = StridedFrame::<RGB8, _>::new(2, 2, 9, pixels.clone()).unwrap();
+        assert_eq!(frame.row(1), &pixels[3..5]);
+
+        let boxed: Box<[RGB8]> = pixels.clone().into_boxed_slice();
+        let mut frame = StridedFrame::<RGB8, _>::new(3, 2, 9, boxed).unwrap();
+        *frame.pixel_mut(2, 1) = RGB8::from([9, 9, 9]);
+        assert_eq!(frame.into_storage()[5], RGB8::from([9, 9, 9]));
+
+        let shared: Arc<[RGB8]> = pixels.clone().into();
+        let frame = StridedFrame::<RGB8, _>::new(1, 3, 6, shared.clone()).unwrap();
+        assert_eq!(frame.to_image()[..], [pixels[0], pixels[2], pixels[4]]);
+        assert_eq!(frame.storage().len(), shared.len());
+
+        let floats = [0.5_f32; 8];
+        let frame = StridedFrame::<RGB32, _>::new(1, 2, 16, &floats[..]).unwrap();
+        assert_eq!(*frame.pixel(0, 1), RGB32::from([0.5, 0.5, 0.5]));
+    }
+
+    #[test]
+    fn errors() {
+        let bytes = [0_u8; 40];
+        assert!(matches!(
+            StridedFrame::<RGB8, _>::new(5, 2, 14, &bytes[..]),
+            Err(IncorrectData::Size {
+                expected: 15,
+                got: 14
+            })
+        ));
+        assert!(matches!(
+            StridedFrame::<RGB8, _>::new(5, 3, 16, &bytes[..]),
+            Err(IncorrectData::Size {
+                expected: 47,
+                got: 40
+            })
+        ));
+
+        let floats = [0_f32; 10];
+        assert!(matches!(
+            StridedFrame::<f32, _>::new(2, 2, 10, &floats[..]),
+            Err(IncorrectData::Alignment { expected: 4 })
+        ));
+
+        let frame = StridedFrame::<RGB8, _>::new(0, 0, 0, &bytes[..0]).unwrap();
+        assert_eq!(frame.to_image().len(), 0);
+    }
+}
@@ -0,0 +1,533 @@
+use super::{Buffer, Frame, FrameMut, Image, ImageMut, ImageRef};
+use crate::error::IncorrectData;
+use std::{iter, mem, ops::Range};
+
+/// Rectangular window into a frame, rows are `stride` bytes apart
+pub struct FrameView<'a, T> {
+    width: u32,
+    height: u32,
+    stride: usize,
+    data: &'a [T],
+}
+
+/// Mutable rectangular window into a frame
+pub struct FrameViewMut<'a, T> {
+    width: u32,
+    height: u32,
+    stride: usize,
+    data: &'a mut [T],
+}
+
+impl<'a, T> Clone for FrameView<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T> Copy for FrameView<'a, T> {}
+
+/// Row distance in pixels, fails when `stride` is not a whole number of
+/// pixels, a row does not fit into it or the rows do not fit into `len`
+/// pixels
+fn check_stride<T>(
+    width: u32,
+    height: u32,
+    stride: usize,
+    len: usize,
+) -> Result<usize, IncorrectData> {
+    let size = mem::size_of::<T>().max(1);
+    if !stride.is_multiple_of(size) {
+        return Err(IncorrectData::Alignment { expected: size });
+    }
+    let row = (width as usize)
+        .checked_mul(size)
+        .ok_or(IncorrectData::Overflow)?;
+    if stride < row {
+        return Err(IncorrectData::Size {
+            expected: row,
+            got: stride,
+        });
+    }
+
+    let pixels = stride / size;
+    let expected = match height {
+        0 => 0,
+        _ => (height as usize - 1)
+            .checked_mul(pixels)
+            .and_then(|x| x.checked_add(width as usize))
+            .ok_or(IncorrectData::Overflow)?,
+    };
+    if len < expected {
+        return Err(IncorrectData::Size { expected, got: len });
+    }
+    Ok(pixels)
+}
+
+impl<'a, T> FrameView<'a, T> {
+    /// Whole frame stored in `data` with rows `stride` bytes apart
+    pub fn new(
+        width: u32,
+        height: u32,
+        stride: usize,
+        data: &'a [T],
+    ) -> Result<Self, IncorrectData> {
+        let stride = check_stride::<T>(width, height, stride, data.len())?;
+        Ok(Self {
+            width,
+            height,
+            stride,
+            data,
+        }
+        .view(0, 0, width, height))
+    }
+
+    /// Distance between rows in bytes
+    #[inline]
+    pub fn stride(&self) -> usize {
+        self.stride * mem::size_of::<T>()
+    }
+
+    /// Window of this view, coordinates are relative to the view
+    pub fn view(&self, x: u32, y: u32, width: u32, height: u32) -> FrameView<'a, T> {
+        let data: &'a [T] = self.data;
+        FrameView {
+            width,
+            height,
+            stride: self.stride,
+            data: &data[self.range(x, y, width, height)],
+        }
+    }
+
+    /// Part of `data` covered by a window, panics when it does not fit
+    fn range(&self, x: u32, y: u32, width: u32, height: u32) -> Range<usize> {
+        assert!(
+            x.checked_add(width).is_some_and(|x| x <= self.width)
+                && y.checked_add(height).is_some_and(|y| y <= self.height),
+            "window {}x{} at ({}, {}) is outside of {}x{}",
+            width,
+            height,
+            x,
+            y,
+            self.width,
+            self.height
+        );
+
+        let start = y as usize * self.stride + x as usize;
+        if width == 0 || height == 0 {
+            let start = start.min(self.data.len());
+            return start..start;
+        }
+        start..start + (height as usize - 1) * self.stride + width as usize
+    }
+
+    /// Rows `0..mid` and `mid..height`
+    pub fn split_at(self, mid: u32) -> (Self, Self) {
+        assert!(mid <= self.height);
+        let at = (mid as usize * self.stride).min(self.data.len());
+        let (top, bottom) = self.data.split_at(at);
+        (
+            Self {
+                width: self.width,
+                height: mid,
+                stride: self.stride,
+                data: top,
+            },
+            Self {
+                width: self.width,
+                height: self.height - mid,
+                stride: self.stride,
+                data: bottom,
+            },
+        )
+    }
+
+    /// Views of `rows` rows each, the last one may be shorter
+    pub fn chunks(self, rows: u32) -> impl Iterator<Item = FrameView<'a, T>> {
+        assert!(rows > 0);
+        let mut rest = Some(self);
+        iter::from_fn(move || {
+            let view = rest.take().filter(|x| x.height > 0)?;
+            let mid = rows.min(view.height);
+            let (chunk, tail) = view.split_at(mid);
+            rest = Some(tail);
+            Some(chunk)
+        })
+    }
+}
+
+impl<'a, T: Copy> FrameView<'a, T> {
+    /// Copy the window into a tightly packed image
+    pub fn to_image(&self) -> Image<T> {
+        let mut data = Vec::with_capacity(self.width as usize * self.height as usize);
+        for y in 0..self.height {
+            data.extend_from_slice(self.row(y));
+        }
+        Image::from_vec(self.width, self.height, data).unwrap()
+    }
+}
+
+impl<'a, T> FrameViewMut<'a, T> {
+    /// Whole frame stored in `data` with rows `stride` bytes apart
+    pub fn new(
+        width: u32,
+        height: u32,
+        stride: usize,
+        data: &'a mut [T],
+    ) -> Result<Self, IncorrectData> {
+        let stride = check_stride::<T>(width, height, stride, data.len())?;
+        Ok(Self {
+            width,
+            height,
+            stride,
+            data,
+        }
+        .into_view_mut(0, 0, width, height))
+    }
+
+    /// Distance between rows in bytes
+    #[inline]
+    pub fn stride(&self) -> usize {
+        self.stride * mem::size_of::<T>()
+    }
+
+    pub fn view(&self, x: u32, y: u32, width: u32, height: u32) -> FrameView<'_, T> {
+        self.as_view().view(x, y, width, height)
+    }
+
+    pub fn view_mut(&mut self, x: u32, y: u32, width: u32, height: u32) -> FrameViewMut<'_, T> {
+        FrameViewMut {
+            width: self.width,
+            height: self.height,
+            stride: self.stride,
+            data: &mut *self.data,
+        }
+        .into_view_mut(x, y, width, height)
+    }
+
+    /// Same as `view_mut` keeping the lifetime of the borrowed data
+    pub fn into_view_mut(self, x: u32, y: u32, width: u32, height: u32) -> FrameViewMut<'a, T> {
+        let range = self.as_view().range(x, y, width, height);
+        FrameViewMut {
+            width,
+            height,
+            stride: self.stride,
+            data: &mut self.data[range],
+        }
+    }
+
+    #[inline]
+    pub(crate) fn data_mut(&mut self) -> &mut [T] {
+        self.data
+    }
+
+    /// Distance between rows in pixels
+    #[inline]
+    pub(crate) fn pixel_stride(&self) -> usize {
+        self.stride
+    }
+
+    pub fn as_view(&self) -> FrameView<'_, T> {
+        FrameView {
+            width: self.width,
+            height: self.height,
+            stride: self.stride,
+            data: self.data,
+        }
+    }
+
+    /// Disjoint rows `0..mid` and `mid..height`
+    pub fn split_at(self, mid: u32) -> (Self, Self) {
+        assert!(mid <= self.height);
+        let at = (mid as usize * self.stride).min(self.data.len());
+        let (top, bottom) = self.data.split_at_mut(at);
+        (
+            Self {
+                width: self.width,
+                height: mid,
+                stride: self.stride,
+                data: top,
+            },
+            Self {
+                width: self.width,
+                height: self.height - mid,
+                stride: self.stride,
+                data: bottom,
+            },
+        )
+    }
+
+    /// Disjoint views of `rows` rows each, the last one may be shorter
+    pub fn chunks(self, rows: u32) -> impl Iterator<Item = FrameViewMut<'a, T>> {
+        assert!(rows > 0);
+        let mut rest = Some(self);
+        iter::from_fn(move || {
+            let view = rest.take().filter(|x| x.height > 0)?;
+            let mid = rows.min(view.height);
+            let (chunk, tail) = view.split_at(mid);
+            rest = Some(tail);
+            Some(chunk)
+        })
+    }
+}
+
+impl<'a, T: Copy> FrameViewMut<'a, T> {
+    pub fn to_image(&self) -> Image<T> {
+        self.as_view().to_image()
+    }
+
+    pub fn fill(&mut self, color: T) {
+        for y in 0..self.height {
+            for x in self.row_mut(y) {
+                *x = color;
+            }
+        }
+    }
+}
+
+macro_rules! view_frame {
+    ($name:ident) => {
+        impl<'a, T> Frame for $name<'a, T> {
+            type Pixel = T;
+
+            #[inline]
+            fn width(&self) -> u32 {
+                self.width
+            }
+
+            #[inline]
+            fn height(&self) -> u32 {
+                self.height
+            }
+
+            #[inline]
+            fn pixel(&self, x: u32, y: u32) -> &Self::Pixel {
+                debug_assert!(x < self.width);
+                debug_assert!(y < self.height);
+                &self.data[y as usize * self.stride + x as usize]
+            }
+
+            #[inline]
+            fn row(&self, row: u32) -> &[Self::Pixel] {
+                debug_assert!(row < self.height);
+                let start = row as usize * self.stride;
+                &self.data[start..start + self.width as usize]
+            }
+        }
+    };
+}
+
+view_frame!(FrameView);
+view_frame!(FrameViewMut);
+
+impl<'a, T> FrameMut for FrameViewMut<'a, T> {
+    #[inline]
+    fn pixel_mut(&mut self, x: u32, y: u32) -> &mut Self::Pixel {
+        debug_assert!(x < self.width);
+        &mut self.row_mut(y)[x as usize]
+    }
+
+    #[inline]
+    fn row_mut(&mut self, row: u32) -> &mut [Self::Pixel] {
+        debug_assert!(row < self.height);
+        let start = row as usize * self.stride;
+        &mut self.data[start..start + self.width as usize]
+    }
+}
+
+macro_rules! views {
+    ($name:ident, $($lt:lifetime)?) => {
+        impl<$($lt,)? T: Copy> $name<$($lt,)? T> {
+            /// Window of the frame, panics when it does not fit
+            pub fn view(&self, x: u32, y: u32, width: u32, height: u32) -> FrameView<'_, T> {
+                FrameView {
+                    width: self.width(),
+                    height: self.height(),
+                    stride: self.width() as usize,
+                    data: &self[..],
+                }
+                .view(x, y, width, height)
+            }
+
+            /// Copy of a window of the frame
+            pub fn crop(&self, x: u32, y: u32, width: u32, height: u32) -> Image<T> {
+                self.view(x, y, width, height).to_image()
+            }
+        }
+    };
+}
+
+macro_rules! views_mut {
+    ($name:ident, $($lt:lifetime)?) => {
+        impl<$($lt,)? T: Copy> $name<$($lt,)? T> {
+            /// Mutable window of the frame, panics when it does not fit
+            pub fn view_mut(
+                &mut self,
+                x: u32,
+                y: u32,
+                width: u32,
+                height: u32,
+            ) -> FrameViewMut<'_, T> {
+                FrameViewMut {
+                    width: self.width(),
+                    height: self.height(),
+                    stride: self.width() as usize,
+                    data: &mut self[..],
+                }
+                .into_view_mut(x, y, width, height)
+            }
+        }
+    };
+}
+
+views!(Buffer,);
+views!(Image,);
+views!(ImageRef, 'a);
+views!(ImageMut, 'a);
+views_mut!(Buffer,);
+views_mut!(Image,);
+views_mut!(ImageMut, 'a);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn image() -> Image<u32> {
+        Image::from_vec(6, 5, (0..30).collect()).unwrap()
+    }
+
+    #[test]
+    fn view() {
+        let img = image();
+        let view = img.view(1, 2, 3, 2);
+        assert_eq!(view.width(), 3);
+        assert_eq!(view.height(), 2);
+        assert_eq!(view.stride(), 24);
+        assert_eq!(view.row(0), &[13, 14, 15]);
+        assert_eq!(view.row(1), &[19, 20, 21]);
+        assert_eq!(*view.pixel(2, 1), 21);
+
+        let inner = view.view(1, 1, 2, 1);
+        assert_eq!(inner.row(0), &[20, 21]);
+    }
+
+    #[test]
+    fn view_edges() {
+        let img = image();
+        let view = img.view(5, 4, 1, 1);
+        assert_eq!(*view.pixel(0, 0), 29);
+
+        let empty = img.view(6, 5, 0, 0);
+        assert_eq!(empty.width(), 0);
+        assert_eq!(empty.to_image().len(), 0);
+
+        let whole = img.view(0, 0, 6, 5);
+        assert_eq!(&whole.to_image()[..], &img[..]);
+    }
+
+    #[test]
+    #[should_panic(expected = "is outside of")]
+    fn view_outside() {
+        image().view(4, 0, 3, 1);
+    }
+
+    #[test]
+    fn crop() {
+        let img = image();
+        let crop = img.crop(2, 1, 2, 3);
+        assert_eq!(crop.width(), 2);
+        assert_eq!(crop.height(), 3);
+        assert_eq!(&crop[..], &[8, 9, 14, 15, 20, 21]);
+    }
+
+    #[test]
+    fn view_mut() {
+        let mut img = image();
+        {
+            let mut view = img.view_mut(1, 1, 2, 2);
+            *view.pixel_mut(0, 0) = 100;
+            view.row_mut(1)[1] = 200;
+        }
+        assert_eq!(img[7], 100);
+        assert_eq!(img[14], 200);
+
+        img.view_mut(4, 0, 2, 5).fill(0);
+        for y in 0..5 {
+            assert_eq!(&img.row(y)[4..], &[0, 0]);
+        }
+    }
+
+    #[test]
+    fn split_at() {
+        let mut img = image();
+        let view = img.view_mut(1, 1, 4, 4);
+        let (mut top, mut bottom) = view.split_at(1);
+        assert_eq!(top.height(), 1);
+        assert_eq!(bottom.height(), 3);
+        top.fill(1);
+        bottom.fill(2);
+
+        assert_eq!(img.row(0), &[0, 1, 2, 3, 4, 5]);
+        assert_eq!(img.row(1), &[6, 1, 1, 1, 1, 11]);
+        assert_eq!(img.row(4), &[24, 2, 2, 2, 2, 29]);
+    }
+
+    #[test]
+    fn chunks() {
+        let img = image();
+        let heights: Vec<u32> = img.view(0, 0, 6, 5).chunks(2).map(|x| x.height()).collect();
+        assert_eq!(heights, vec![2, 2, 1]);
+
+        let mut img = image();
+        let (w, h) = (img.width(), img.height());
+        for (i, mut chunk) in img.view_mut(0, 0, w, h).chunks(2).enumerate() {
+            chunk.fill(i as u32);
+        }
+        assert_eq!(img.row(1), &[0; 6]);
+        assert_eq!(img.row(3), &[1; 6]);
+        assert_eq!(img.row(4), &[2; 6]);
+    }
+
+    #[test]
+    fn borrowed() {
+        let data: Vec<u32> = (0..30).collect();
+        let img = ImageRef::new(6, 5, &data).unwrap();
+        assert_eq!(img.view(0, 4, 2, 1).row(0), &[24, 25]);
+
+        let strided = FrameView::new(2, 3, 16, &data[..10]).unwrap();
+        assert_eq!(strided.row(2), &[8, 9]);
+        assert_eq!(&strided.to_image()[..], &[0, 1, 4, 5, 8, 9]);
+
+        let mut data = data;
+        let mut strided = FrameViewMut::new(3, 2, 20, &mut data[..8]).unwrap();
+        strided.fill(0);
+        assert_eq!(&data[..9], &[0, 0, 0, 3, 4, 0, 0, 0, 8]);
+    }
+
+    #[test]
+    fn new_errors() {
+        let data = [0_u32; 10];
+        assert!(matches!(
+            FrameView::new(4, 2, 12, &data[..]),
+            Err(IncorrectData::Size {
+                expected: 16,
+                got: 12
+            })
+        ));
+        assert!(matches!(
+            FrameView::new(2, 1, 9, &data[..]),
+            Err(IncorrectData::Alignment { expected: 4 })
+        ));
+        assert!(matches!(
+            FrameView::new(2, 3, 16, &data[..9]),
+            Err(IncorrectData::Size {
+                expected: 10,
+                got: 9
+            })
+        ));
+        assert!(matches!(
+            FrameView::new(1, u32::MAX, usize::MAX - 3, &data[..]),
+            Err(IncorrectData::Overflow)
+        ));
+        assert!(FrameView::new(0, 0, 0, &data[..0]).is_ok());
+    }
+}
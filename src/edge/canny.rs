@@ -0,0 +1,168 @@
+use super::{derivatives, Operator};
+use crate::{
+    color::Luma,
+    display::{Frame, Image},
+    filter::{correlate, Kernel, Plane},
+    transform::Border,
+};
+use std::f64::consts::{FRAC_PI_8, PI};
+
+const WEAK: u8 = 1;
+const STRONG: u8 = 2;
+
+/// Offset of the neighbor along the gradient `(x, y)`
+fn along(x: f64, y: f64) -> (i64, i64) {
+    let angle = y.atan2(x).rem_euclid(PI);
+    if !(FRAC_PI_8..7.0 * FRAC_PI_8).contains(&angle) {
+        (1, 0)
+    } else if angle < 3.0 * FRAC_PI_8 {
+        (1, 1)
+    } else if angle < 5.0 * FRAC_PI_8 {
+        (0, 1)
+    } else {
+        (-1, 1)
+    }
+}
+
+/// Canny edges of the luma of `frame`, edge pixels are 255 and the rest 0.
+///
+/// The luma is smoothed by a Gaussian of `sigma` (none for 0), thinned to
+/// local maxima of the Sobel gradient and pixels above `high` are kept along
+/// with the pixels above `low` connected to them.
+pub fn canny<F>(frame: &F, sigma: f64, low: f64, high: f64) -> Image<u8>
+where
+    F: Frame,
+    F::Pixel: Luma,
+{
+    let (width, height) = (frame.width(), frame.height());
+    let (w, h) = (width as i64, height as i64);
+    let luma = Plane::luma(frame);
+    let smooth = correlate(&luma, &Kernel::gaussian(sigma), &Border::Replicate);
+    let (gx, gy) = derivatives(&smooth, Operator::Sobel);
+    let magnitude: Vec<f64> = gx
+        .data
+        .iter()
+        .zip(&gy.data)
+        .map(|(x, y)| x.hypot(*y))
+        .collect();
+    let at = |x: i64, y: i64| {
+        if x >= 0 && y >= 0 && x < w && y < h {
+            magnitude[(y * w + x) as usize]
+        } else {
+            0.0
+        }
+    };
+
+    // non-maximum suppression, ties go to the first pixel so plateaus thin
+    let mut marks = vec![0_u8; magnitude.len()];
+    let mut stack = Vec::new();
+    for y in 0..h {
+        for x in 0..w {
+            let i = (y * w + x) as usize;
+            let m = magnitude[i];
+            if m < low || m == 0.0 {
+                continue;
+            }
+            let (dx, dy) = along(gx.data[i], gy.data[i]);
+            if m < at(x + dx, y + dy) || m <= at(x - dx, y - dy) {
+                continue;
+            }
+            if m >= high {
+                marks[i] = STRONG;
+                stack.push((x, y));
+            } else {
+                marks[i] = WEAK;
+            }
+        }
+    }
+
+    // hysteresis, grow strong edges into 8-connected weak ones
+    while let Some((x, y)) = stack.pop() {
+        for ny in y - 1..=y + 1 {
+            for nx in x - 1..=x + 1 {
+                if nx < 0 || ny < 0 || nx >= w || ny >= h {
+                    continue;
+                }
+                let i = (ny * w + nx) as usize;
+                if marks[i] == WEAK {
+                    marks[i] = STRONG;
+                    stack.push((nx, ny));
+                }
+            }
+        }
+    }
+
+    let data = marks
+        .into_iter()
+        .map(|m| if m == STRONG { 255 } else { 0 })
+        .collect();
+    Image::from_vec(width, height, data).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::RGB8;
+
+    fn square(value: f32, left: f32) -> Image<f32> {
+        Image::from_fn(20, 10, |x, y| {
+            if (2..8).contains(&y) && (2..8).contains(&x) {
+                if x < 5 {
+                    left
+                } else {
+                    value
+                }
+            } else {
+                0.0
+            }
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn thin_step() {
+        let step = Image::from_fn(12, 8, |x, _| if x < 5 { 0_u8 } else { 255 }).unwrap();
+        for sigma in [0.0, 1.0, 1.5] {
+            let edges = canny(&step, sigma, 0.05, 0.2);
+            for row in edges.rows() {
+                let on: Vec<usize> = (0..12).filter(|x| row[*x] == 255).collect();
+                assert_eq!(on.len(), 1, "sigma {}", sigma);
+                assert!(on[0] == 4 || on[0] == 5);
+            }
+        }
+    }
+
+    #[test]
+    fn color_square() {
+        let img = Image::from_fn(16, 16, |x, y| {
+            if (4..12).contains(&x) && (4..12).contains(&y) {
+                RGB8::from([250, 240, 10])
+            } else {
+                RGB8::from([10, 20, 90])
+            }
+        })
+        .unwrap();
+        let edges = canny(&img, 1.0, 0.05, 0.15);
+        assert_eq!(*edges.pixel(0, 0), 0);
+        assert_eq!(*edges.pixel(8, 8), 0);
+        assert!((3..=4).any(|x| *edges.pixel(x, 8) == 255));
+        assert!((11..=12).any(|y| *edges.pixel(8, y) == 255));
+    }
+
+    #[test]
+    fn hysteresis() {
+        // magnitude of a step is half its height
+        let weak = square(0.3, 0.3);
+        assert!(canny(&weak, 0.0, 0.1, 0.4).iter().all(|p| *p == 0));
+        assert!(canny(&weak, 0.0, 0.1, 0.12).contains(&255));
+
+        // the weak right half survives through the strong left half
+        let mixed = square(0.3, 1.0);
+        let edges = canny(&mixed, 0.0, 0.1, 0.4);
+        assert_eq!(*edges.pixel(1, 4), 255);
+        assert!((7..=8).any(|x| *edges.pixel(x, 4) == 255));
+        assert!(canny(&mixed, 0.0, 0.2, 0.4)
+            .rows()
+            .all(|r| r[7] == 0 && r[8] == 0));
+    }
+}
@@ -0,0 +1,129 @@
+//! Gradients and edges of the luma of a frame
+//!
+//! Gradients are in normalized luma per pixel, a ramp rising by 0.1 each
+//! pixel has a magnitude of 0.1 with every operator. Pixels outside of the
+//! frame replicate the edge.
+
+mod canny;
+
+pub use canny::canny;
+
+use crate::{
+    color::Luma,
+    display::{Frame, Image},
+    filter::{correlate, Kernel, Plane},
+    transform::Border,
+};
+
+/// Derivative kernel, smoothed across the derivative
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    /// 1 2 1 smoothing
+    Sobel,
+    /// 3 10 3 smoothing, closer to rotation invariant
+    Scharr,
+    /// 1 1 1 smoothing
+    Prewitt,
+}
+
+impl Operator {
+    /// Kernels of the x and y derivatives
+    fn kernels(self) -> (Kernel, Kernel) {
+        let smooth = match self {
+            Operator::Sobel => vec![0.25, 0.5, 0.25],
+            Operator::Scharr => vec![3.0 / 16.0, 10.0 / 16.0, 3.0 / 16.0],
+            Operator::Prewitt => vec![1.0 / 3.0; 3],
+        };
+        let derivative = vec![-0.5, 0.0, 0.5];
+        (
+            Kernel::from_separable(derivative.clone(), smooth.clone()),
+            Kernel::from_separable(smooth, derivative),
+        )
+    }
+}
+
+/// Per pixel gradient of a frame
+pub struct Gradient {
+    pub magnitude: Image<f32>,
+    /// Radians from the x axis towards the y axis, in `-PI..=PI`
+    pub direction: Image<f32>,
+}
+
+/// x and y derivatives of a single channel plane
+pub(crate) fn derivatives(plane: &Plane, operator: Operator) -> (Plane, Plane) {
+    let (kx, ky) = operator.kernels();
+    let border = Border::Replicate;
+    (
+        correlate(plane, &kx, &border),
+        correlate(plane, &ky, &border),
+    )
+}
+
+/// Gradient of the luma of `frame`
+pub fn gradient<F>(frame: &F, operator: Operator) -> Gradient
+where
+    F: Frame,
+    F::Pixel: Luma,
+{
+    let (gx, gy) = derivatives(&Plane::luma(frame), operator);
+    let (width, height) = (frame.width(), frame.height());
+    let magnitude = gx
+        .data
+        .iter()
+        .zip(&gy.data)
+        .map(|(x, y)| x.hypot(*y) as f32);
+    let direction = gx
+        .data
+        .iter()
+        .zip(&gy.data)
+        .map(|(x, y)| y.atan2(*x) as f32);
+
+    Gradient {
+        magnitude: Image::from_vec(width, height, magnitude.collect()).unwrap(),
+        direction: Image::from_vec(width, height, direction.collect()).unwrap(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::RGB8;
+    use std::f32::consts::FRAC_PI_2;
+
+    const OPERATORS: [Operator; 3] = [Operator::Sobel, Operator::Scharr, Operator::Prewitt];
+
+    #[test]
+    fn ramps() {
+        let across = Image::from_fn(8, 6, |x, _| x as f32 * 0.1).unwrap();
+        let down = Image::from_fn(8, 6, |_, y| 1.0 - y as f32 * 0.1).unwrap();
+        for operator in OPERATORS {
+            let g = gradient(&across, operator);
+            assert!((g.magnitude.pixel(3, 2) - 0.1).abs() < 1e-6);
+            assert!(g.direction.pixel(3, 2).abs() < 1e-6);
+
+            let g = gradient(&down, operator);
+            assert!((g.magnitude.pixel(4, 3) - 0.1).abs() < 1e-6);
+            assert!((g.direction.pixel(4, 3) + FRAC_PI_2).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn flat_and_color() {
+        let flat = Image::new(5, 5, RGB8::from([90, 20, 200])).unwrap();
+        let g = gradient(&flat, Operator::Sobel);
+        assert!(g.magnitude.iter().all(|m| *m == 0.0));
+
+        // a red to green edge still shows up in luma
+        let edge = Image::from_fn(6, 3, |x, _| {
+            if x < 3 {
+                RGB8::from([255, 0, 0])
+            } else {
+                RGB8::from([0, 255, 0])
+            }
+        })
+        .unwrap();
+        let g = gradient(&edge, Operator::Scharr);
+        assert!(*g.magnitude.pixel(2, 1) > 0.1);
+        assert_eq!(*g.magnitude.pixel(0, 1), 0.0);
+    }
+}
@@ -1,7 +1,17 @@
 #[derive(Debug)]
 pub enum IncorrectData {
-    Size { expected: usize, got: usize },
+    Size {
+        expected: usize,
+        got: usize,
+    },
     Overflow,
+    Alignment {
+        expected: usize,
+    },
+    /// Argument outside of the values it can take
+    Parameter {
+        name: &'static str,
+    },
 }
 
 impl std::error::Error for IncorrectData {}
@@ -17,6 +27,12 @@ impl std::fmt::Display for IncorrectData {
             IncorrectData::Overflow => {
                 write!(f, "operation was not performed, as it would overflow")
             }
+
+            IncorrectData::Alignment { ref expected } => {
+                write!(f, "data is not aligned to {} bytes", expected)
+            }
+
+            IncorrectData::Parameter { name } => write!(f, "invalid value of `{}`", name),
         }
     }
 }
@@ -0,0 +1,135 @@
+use super::{maxima, Keypoint};
+use crate::{
+    color::Luma,
+    display::{Frame, Image},
+    filter::Plane,
+    geometry::Vec2,
+};
+
+/// Bresenham circle of radius 3, clockwise from the top
+const CIRCLE: [(i64, i64); 16] = [
+    (0, -3),
+    (1, -3),
+    (2, -2),
+    (3, -1),
+    (3, 0),
+    (3, 1),
+    (2, 2),
+    (1, 3),
+    (0, 3),
+    (-1, 3),
+    (-2, 2),
+    (-3, 1),
+    (-3, 0),
+    (-3, -1),
+    (-2, -2),
+    (-1, -3),
+];
+
+/// Contiguous circle pixels that must all be brighter or all darker
+const ARC: usize = 9;
+
+/// Largest threshold the pixel stays a corner for, the best over arcs of
+/// the smallest difference along the arc
+fn score(center: f64, ring: &[f64; 16]) -> f64 {
+    let mut best = 0.0_f64;
+    for start in 0..16 {
+        let (mut brighter, mut darker) = (f64::INFINITY, f64::INFINITY);
+        for i in start..start + ARC {
+            let v = ring[i % 16];
+            brighter = brighter.min(v - center);
+            darker = darker.min(center - v);
+        }
+        best = best.max(brighter).max(darker);
+    }
+    best
+}
+
+/// FAST-9 corners, pixels with 9 contiguous pixels of the circle of radius
+/// 3 around them all brighter or all darker by more than `threshold`, in
+/// normalized luma. The response is the largest threshold the pixel passes,
+/// with `nonmax` only the pixels stronger than their 8 neighbors are kept.
+/// Pixels closer than 3 to the edge are not tested.
+pub fn fast<F>(frame: &F, threshold: f64, nonmax: bool) -> Vec<Keypoint>
+where
+    F: Frame,
+    F::Pixel: Luma,
+{
+    let luma = Plane::luma(frame);
+    let (width, height) = (frame.width(), frame.height());
+    let at = |x: i64, y: i64| luma.data[(y * width as i64 + x) as usize];
+
+    let mut scores = vec![0.0_f32; width as usize * height as usize];
+    let mut keypoints = Vec::new();
+    for y in 3..(height as i64 - 3) {
+        for x in 3..(width as i64 - 3) {
+            let center = at(x, y);
+            let mut ring = [0.0; 16];
+            for (r, (dx, dy)) in ring.iter_mut().zip(&CIRCLE) {
+                *r = at(x + dx, y + dy);
+            }
+
+            // any arc of 9 covers at least 2 of the 4 compass points
+            let compass = [ring[0], ring[4], ring[8], ring[12]];
+            let brighter = compass.iter().filter(|v| **v > center + threshold).count();
+            let darker = compass.iter().filter(|v| **v < center - threshold).count();
+            if brighter < 2 && darker < 2 {
+                continue;
+            }
+
+            let s = score(center, &ring);
+            if s > threshold {
+                scores[(y * width as i64 + x) as usize] = s as f32;
+                keypoints.push(Keypoint {
+                    position: Vec2 {
+                        x: x as u32,
+                        y: y as u32,
+                    },
+                    response: s as f32,
+                });
+            }
+        }
+    }
+
+    if nonmax {
+        let scores = Image::from_vec(width, height, scores).unwrap();
+        return maxima(&scores, 0.0);
+    }
+    keypoints
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::{near_corners, square};
+    use super::*;
+
+    #[test]
+    fn square_corners() {
+        let img = square();
+        let all = fast(&img, 0.2, false);
+        let kept = fast(&img, 0.2, true);
+        assert!(kept.len() < all.len());
+        assert_eq!(kept.len(), 4, "{:?}", kept);
+        assert!(near_corners(&kept, 2), "{:?}", kept);
+        // a binary corner passes any threshold below its contrast
+        let contrast = (190.0 / 255.0) as f32;
+        assert!(kept.iter().all(|k| (k.response - contrast).abs() < 1e-3));
+
+        assert!(fast(&img, 0.8, true).is_empty());
+        assert!(fast(&Image::new(16, 16, 50_u8).unwrap(), 0.0, true).is_empty());
+        assert!(fast(&Image::new(5, 5, 50_u8).unwrap(), 0.1, false).is_empty());
+    }
+
+    #[test]
+    fn arcs() {
+        let dark = [0.0; 16];
+        assert_eq!(score(0.5, &dark), 0.5);
+
+        // 8 bright pixels in a row are one short of an arc
+        let mut ring = [0.0; 16];
+        ring[3..11].iter_mut().for_each(|v| *v = 1.0);
+        assert_eq!(score(0.0, &ring), 0.0);
+        ring[11] = 0.6;
+        assert_eq!(score(0.0, &ring), 0.6);
+    }
+}
@@ -0,0 +1,211 @@
+//! Corner and keypoint detectors on the luma of a frame
+
+mod fast;
+
+pub use fast::fast;
+
+use crate::{
+    color::Luma,
+    display::{Frame, Image},
+    edge::{derivatives, Operator},
+    filter::{correlate, Kernel, Plane},
+    geometry::Vec2,
+    math::is_peak,
+    transform::Border,
+};
+
+/// Detected point and the strength of its response, higher is stronger
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Keypoint {
+    pub position: Vec2<u32>,
+    pub response: f32,
+}
+
+/// Corner measure of the structure tensor
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Response {
+    /// Determinant minus `k` times the squared trace, `k` is usually around
+    /// 0.04, negative along edges
+    Harris(f64),
+    /// Smaller eigenvalue, as in good features to track
+    ShiTomasi,
+}
+
+/// Corner response of every pixel, from Sobel gradients summed over a
+/// `block` x `block` window
+pub fn corner_response<F>(frame: &F, block: u32, response: Response) -> Image<f32>
+where
+    F: Frame,
+    F::Pixel: Luma,
+{
+    let (gx, gy) = derivatives(&Plane::luma(frame), Operator::Sobel);
+    let products = Plane {
+        width: gx.width,
+        height: gx.height,
+        channels: 3,
+        data: gx
+            .data
+            .iter()
+            .zip(&gy.data)
+            .flat_map(|(x, y)| [x * x, x * y, y * y])
+            .collect(),
+    };
+    let block = block.max(1);
+    let sums = correlate(
+        &products,
+        &Kernel::box_blur(block, block),
+        &Border::Replicate,
+    );
+
+    let data = sums
+        .data
+        .chunks(3)
+        .map(|t| {
+            let (a, b, c) = (t[0], t[1], t[2]);
+            let r = match response {
+                Response::Harris(k) => a * c - b * b - k * (a + c) * (a + c),
+                Response::ShiTomasi => (a + c) / 2.0 - ((a - c) * (a - c) / 4.0 + b * b).sqrt(),
+            };
+            r as f32
+        })
+        .collect();
+    Image::from_vec(frame.width(), frame.height(), data).unwrap()
+}
+
+/// Local maxima of `scores` over their 8 neighbors, ties go to the first
+/// pixel, with a score above `min`
+fn maxima(scores: &Image<f32>, min: f32) -> Vec<Keypoint> {
+    let (width, height) = (scores.width(), scores.height());
+    scores
+        .pixels()
+        .filter(|(x, y, s)| **s > min && is_peak(scores, width, height, *x, *y))
+        .map(|(x, y, s)| Keypoint {
+            position: Vec2 { x, y },
+            response: *s,
+        })
+        .collect()
+}
+
+/// Strongest corners, at most `max` of them. Corners weaker than `quality`
+/// times the strongest one or closer than `min_distance` to a stronger one
+/// are left out.
+pub fn corners<F>(
+    frame: &F,
+    block: u32,
+    response: Response,
+    quality: f64,
+    min_distance: f64,
+    max: usize,
+) -> Vec<Keypoint>
+where
+    F: Frame,
+    F::Pixel: Luma,
+{
+    let scores = corner_response(frame, block, response);
+    let strongest = scores.iter().copied().fold(0.0_f32, f32::max);
+    let mut candidates = maxima(&scores, (quality * strongest as f64) as f32);
+    candidates.sort_by(|a, b| b.response.total_cmp(&a.response));
+
+    // taken corners by cells of `min_distance`, only the cells around a
+    // candidate can hold one too close
+    let cell = min_distance.max(1.0);
+    let columns = (scores.width() as f64 / cell).ceil() as usize + 1;
+    let rows = (scores.height() as f64 / cell).ceil() as usize + 1;
+    let mut grid: Vec<Vec<Vec2<u32>>> = vec![Vec::new(); columns * rows];
+
+    let mut taken = Vec::new();
+    for candidate in candidates {
+        if taken.len() == max {
+            break;
+        }
+        let p = candidate.position;
+        let (cx, cy) = ((p.x as f64 / cell) as usize, (p.y as f64 / cell) as usize);
+        let near = (cy.saturating_sub(1)..=(cy + 1).min(rows - 1))
+            .flat_map(|y| (cx.saturating_sub(1)..=(cx + 1).min(columns - 1)).map(move |x| (x, y)))
+            .flat_map(|(x, y)| &grid[y * columns + x])
+            .any(|q| (p.x as f64 - q.x as f64).hypot(p.y as f64 - q.y as f64) < min_distance);
+        if !near {
+            grid[cy * columns + cx].push(p);
+            taken.push(candidate);
+        }
+    }
+    taken
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Bright square from 8 to 23 on a dark background
+    pub(super) fn square() -> Image<u8> {
+        Image::from_fn(32, 32, |x, y| {
+            if (8..24).contains(&x) && (8..24).contains(&y) {
+                200
+            } else {
+                10
+            }
+        })
+        .unwrap()
+    }
+
+    pub(super) fn near_corners(points: &[Keypoint], slack: u32) -> bool {
+        [(8, 8), (23, 8), (8, 23), (23, 23)].iter().all(|(x, y)| {
+            points
+                .iter()
+                .any(|k| k.position.x.abs_diff(*x) <= slack && k.position.y.abs_diff(*y) <= slack)
+        })
+    }
+
+    #[test]
+    fn responses() {
+        let img = square();
+        let harris = corner_response(&img, 3, Response::Harris(0.04));
+        assert!(*harris.pixel(8, 8) > 0.0);
+        // edges are negative and flat areas zero
+        assert!(*harris.pixel(16, 8) < 0.0);
+        assert_eq!(*harris.pixel(16, 16), 0.0);
+
+        let shi_tomasi = corner_response(&img, 3, Response::ShiTomasi);
+        assert!(*shi_tomasi.pixel(8, 8) > 0.0);
+        assert!(shi_tomasi.pixel(16, 8).abs() < 1e-9);
+    }
+
+    #[test]
+    fn square_corners() {
+        let img = square();
+        for response in [Response::Harris(0.04), Response::ShiTomasi] {
+            let points = corners(&img, 3, response, 0.1, 5.0, 100);
+            assert_eq!(points.len(), 4, "{:?}", points);
+            assert!(near_corners(&points, 1), "{:?}", points);
+            assert!(points.windows(2).all(|w| w[0].response >= w[1].response));
+        }
+
+        assert_eq!(corners(&img, 3, Response::ShiTomasi, 0.1, 5.0, 2).len(), 2);
+        let flat = Image::new(16, 16, 90_u8).unwrap();
+        assert!(corners(&flat, 3, Response::ShiTomasi, 0.1, 5.0, 100).is_empty());
+    }
+
+    #[test]
+    fn min_distance() {
+        // two corners of a thin bar are closer than the distance
+        let img = Image::from_fn(32, 32, |x, y| {
+            if (8..24).contains(&x) && (8..13).contains(&y) {
+                200_u8
+            } else {
+                10
+            }
+        })
+        .unwrap();
+        let all = corners(&img, 3, Response::ShiTomasi, 0.05, 1.0, 100);
+        let spread = corners(&img, 3, Response::ShiTomasi, 0.05, 8.0, 100);
+        assert_eq!((all.len(), spread.len()), (4, 2));
+        for (i, a) in spread.iter().enumerate() {
+            for b in &spread[i + 1..] {
+                let d = (a.position.x as f64 - b.position.x as f64)
+                    .hypot(a.position.y as f64 - b.position.y as f64);
+                assert!(d >= 8.0);
+            }
+        }
+    }
+}
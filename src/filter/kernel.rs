@@ -0,0 +1,189 @@
+use crate::error::IncorrectData;
+
+/// Weights of a linear filter, anchored at `(width / 2, height / 2)`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Kernel {
+    width: u32,
+    height: u32,
+    weights: Vec<f64>,
+    /// Row and column whose product is the kernel
+    factors: Option<(Vec<f64>, Vec<f64>)>,
+}
+
+impl Kernel {
+    /// `weights` row by row, separable kernels are detected
+    pub fn new(width: u32, height: u32, weights: Vec<f64>) -> Result<Self, IncorrectData> {
+        let expected = width as usize * height as usize;
+        if weights.len() != expected {
+            return Err(IncorrectData::Size {
+                expected,
+                got: weights.len(),
+            });
+        }
+
+        let factors = factorize(width as usize, &weights);
+        Ok(Self {
+            width,
+            height,
+            weights,
+            factors,
+        })
+    }
+
+    /// Outer product of a horizontal `row` and a vertical `column`
+    pub fn from_separable(row: Vec<f64>, column: Vec<f64>) -> Self {
+        let weights = column
+            .iter()
+            .flat_map(|c| row.iter().map(move |r| c * r))
+            .collect();
+        Self {
+            width: row.len() as u32,
+            height: column.len() as u32,
+            weights,
+            factors: Some((row, column)),
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn weights(&self) -> &[f64] {
+        &self.weights
+    }
+
+    /// Can be applied as a row pass followed by a column pass
+    pub fn is_separable(&self) -> bool {
+        self.factors.is_some()
+    }
+
+    pub(crate) fn factors(&self) -> Option<(&[f64], &[f64])> {
+        self.factors
+            .as_ref()
+            .map(|(row, column)| (&row[..], &column[..]))
+    }
+
+    /// Mean of `width` x `height` pixels
+    pub fn box_blur(width: u32, height: u32) -> Self {
+        let row = vec![1.0 / width as f64; width as usize];
+        let column = vec![1.0 / height as f64; height as usize];
+        Self::from_separable(row, column)
+    }
+
+    /// Normalized Gaussian reaching out `3 * sigma` pixels
+    pub fn gaussian(sigma: f64) -> Self {
+        let taps = gaussian_taps(sigma);
+        Self::from_separable(taps.clone(), taps)
+    }
+
+    pub fn sharpen() -> Self {
+        Self::new(3, 3, vec![0.0, -1.0, 0.0, -1.0, 5.0, -1.0, 0.0, -1.0, 0.0]).unwrap()
+    }
+
+    /// Relief lit from the top left
+    pub fn emboss() -> Self {
+        Self::new(3, 3, vec![-2.0, -1.0, 0.0, -1.0, 1.0, 1.0, 0.0, 1.0, 2.0]).unwrap()
+    }
+
+    /// 4-connected Laplacian
+    pub fn laplacian() -> Self {
+        Self::new(3, 3, vec![0.0, 1.0, 0.0, 1.0, -4.0, 1.0, 0.0, 1.0, 0.0]).unwrap()
+    }
+}
+
+/// 1D Gaussian summing to 1, a single tap for `sigma <= 0`
+fn gaussian_taps(sigma: f64) -> Vec<f64> {
+    if sigma.is_nan() || sigma <= 0.0 {
+        return vec![1.0];
+    }
+    let radius = (3.0 * sigma).ceil() as i64;
+    let taps: Vec<f64> = (-radius..=radius)
+        .map(|x| (-(x * x) as f64 / (2.0 * sigma * sigma)).exp())
+        .collect();
+    let sum: f64 = taps.iter().sum();
+    taps.into_iter().map(|x| x / sum).collect()
+}
+
+/// Splits a rank one kernel into a row and a column
+fn factorize(width: usize, weights: &[f64]) -> Option<(Vec<f64>, Vec<f64>)> {
+    let (pivot, max) = weights
+        .iter()
+        .enumerate()
+        .max_by(|a, b| a.1.abs().total_cmp(&b.1.abs()))?;
+    if *max == 0.0 {
+        return None;
+    }
+
+    let (px, py) = (pivot % width, pivot / width);
+    let row: Vec<f64> = weights[py * width..(py + 1) * width].to_vec();
+    let column: Vec<f64> = weights.chunks(width).map(|r| r[px] / max).collect();
+
+    let epsilon = max.abs() * 1e-9;
+    let rank_one = weights.chunks(width).zip(&column).all(|(r, c)| {
+        r.iter()
+            .zip(&row)
+            .all(|(w, x)| (w - c * x).abs() <= epsilon)
+    });
+    if rank_one {
+        Some((row, column))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new() {
+        assert!(Kernel::new(3, 2, vec![0.0; 6]).is_ok());
+        assert!(matches!(
+            Kernel::new(3, 3, vec![0.0; 6]),
+            Err(IncorrectData::Size {
+                expected: 9,
+                got: 6
+            })
+        ));
+    }
+
+    #[test]
+    fn separable() {
+        assert!(Kernel::box_blur(3, 5).is_separable());
+        assert!(Kernel::gaussian(1.5).is_separable());
+        assert!(!Kernel::sharpen().is_separable());
+        assert!(!Kernel::emboss().is_separable());
+        assert!(!Kernel::laplacian().is_separable());
+        assert!(!Kernel::new(2, 2, vec![0.0; 4]).unwrap().is_separable());
+
+        // Sobel is a smoothing column times a derivative row
+        let sobel =
+            Kernel::new(3, 3, vec![-1.0, 0.0, 1.0, -2.0, 0.0, 2.0, -1.0, 0.0, 1.0]).unwrap();
+        let (row, column) = sobel.factors().unwrap();
+        for (i, w) in sobel.weights().iter().enumerate() {
+            assert!((row[i % 3] * column[i / 3] - w).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn gaussian() {
+        let kernel = Kernel::gaussian(1.0);
+        assert_eq!((kernel.width(), kernel.height()), (7, 7));
+        let sum: f64 = kernel.weights().iter().sum();
+        assert!((sum - 1.0).abs() < 1e-12);
+        assert_eq!(
+            kernel.weights()[24],
+            *kernel
+                .weights()
+                .iter()
+                .max_by(|a, b| a.total_cmp(b))
+                .unwrap()
+        );
+
+        assert_eq!(Kernel::gaussian(0.0).weights(), &[1.0]);
+    }
+}
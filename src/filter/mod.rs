@@ -0,0 +1,381 @@
+//! Linear filtering
+//!
+//! Kernels are applied as a correlation, unflipped, and every channel is
+//! filtered on its own in `f64`.
+
+mod kernel;
+
+pub use kernel::Kernel;
+
+use crate::{
+    color::{Channel, Luma, Pixel},
+    display::{Frame, Image},
+    transform::Border,
+};
+
+/// Channels of a frame as `f64`, pixel after pixel
+pub(crate) struct Plane {
+    pub(crate) width: u32,
+    pub(crate) height: u32,
+    pub(crate) channels: usize,
+    pub(crate) data: Vec<f64>,
+}
+
+impl Plane {
+    pub(crate) fn from_frame<F>(frame: &F) -> Self
+    where
+        F: Frame,
+        F::Pixel: Pixel,
+    {
+        let data = frame
+            .rows()
+            .flatten()
+            .flat_map(|p| p.channels().iter().map(|c| c.to_f64()))
+            .collect();
+        Self {
+            width: frame.width(),
+            height: frame.height(),
+            channels: F::Pixel::CHANNELS,
+            data,
+        }
+    }
+
+    /// Single channel plane of the luma of `frame`
+    pub(crate) fn luma<F>(frame: &F) -> Self
+    where
+        F: Frame,
+        F::Pixel: Luma,
+    {
+        Self {
+            width: frame.width(),
+            height: frame.height(),
+            channels: 1,
+            data: frame.rows().flatten().map(|p| p.luma()).collect(),
+        }
+    }
+
+    pub(crate) fn to_image<P: Pixel>(&self) -> Image<P> {
+        let mut channels = vec![P::Channel::default(); self.channels];
+        Image::from_fn(self.width, self.height, |x, y| {
+            for (c, v) in channels.iter_mut().zip(self.at(x, y)) {
+                *c = P::Channel::from_f64(*v);
+            }
+            P::from_channels(&channels)
+        })
+        .unwrap()
+    }
+
+    #[inline]
+    pub(crate) fn at(&self, x: u32, y: u32) -> &[f64] {
+        let i = (y as usize * self.width as usize + x as usize) * self.channels;
+        &self.data[i..i + self.channels]
+    }
+
+    fn zeroed(&self) -> Self {
+        Self {
+            width: self.width,
+            height: self.height,
+            channels: self.channels,
+            data: vec![0.0; self.data.len()],
+        }
+    }
+
+    #[inline]
+    fn at_mut(&mut self, x: u32, y: u32) -> &mut [f64] {
+        let i = (y as usize * self.width as usize + x as usize) * self.channels;
+        &mut self.data[i..i + self.channels]
+    }
+}
+
+/// `border` with the constant pixel as `f64` channels
+pub(crate) fn plane_border<P: Pixel>(border: Border<P>) -> Border<Vec<f64>> {
+    match border {
+        Border::Constant(p) => Border::Constant(p.channels().iter().map(|c| c.to_f64()).collect()),
+        Border::Replicate => Border::Replicate,
+        Border::Reflect => Border::Reflect,
+        Border::Wrap => Border::Wrap,
+    }
+}
+
+/// Constant channels of `border` times `scale`
+fn outside(border: &Border<Vec<f64>>, channels: usize, scale: f64) -> Vec<f64> {
+    match border {
+        Border::Constant(values) => values.iter().map(|v| v * scale).collect(),
+        _ => vec![0.0; channels],
+    }
+}
+
+/// One dimensional pass of `taps` centered on each pixel
+fn pass(
+    plane: &Plane,
+    taps: &[f64],
+    horizontal: bool,
+    border: &Border<Vec<f64>>,
+    outside: &[f64],
+) -> Plane {
+    let anchor = (taps.len() / 2) as i64;
+    let len = if horizontal {
+        plane.width
+    } else {
+        plane.height
+    };
+    let mut out = plane.zeroed();
+
+    for y in 0..plane.height {
+        for x in 0..plane.width {
+            let pos = if horizontal { x } else { y } as i64;
+            let o = out.at_mut(x, y);
+            for (k, t) in taps.iter().enumerate() {
+                if *t == 0.0 {
+                    continue;
+                }
+                let values = match border.index(pos + k as i64 - anchor, len) {
+                    Some(i) if horizontal => plane.at(i, y),
+                    Some(i) => plane.at(x, i),
+                    None => outside,
+                };
+                for (o, v) in o.iter_mut().zip(values) {
+                    *o += t * v;
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Direct two dimensional correlation
+fn full(plane: &Plane, kernel: &Kernel, border: &Border<Vec<f64>>) -> Plane {
+    let (ax, ay) = ((kernel.width() / 2) as i64, (kernel.height() / 2) as i64);
+    let outside = outside(border, plane.channels, 1.0);
+    let mut out = plane.zeroed();
+
+    for y in 0..plane.height {
+        for x in 0..plane.width {
+            let o = out.at_mut(x, y);
+            for (ky, row) in kernel
+                .weights()
+                .chunks(kernel.width().max(1) as usize)
+                .enumerate()
+            {
+                let sy = border.index(y as i64 + ky as i64 - ay, plane.height);
+                for (kx, w) in row.iter().enumerate() {
+                    if *w == 0.0 {
+                        continue;
+                    }
+                    let sx = border.index(x as i64 + kx as i64 - ax, plane.width);
+                    let values = match (sx, sy) {
+                        (Some(sx), Some(sy)) => plane.at(sx, sy),
+                        _ => &outside,
+                    };
+                    for (o, v) in o.iter_mut().zip(values) {
+                        *o += w * v;
+                    }
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Correlates `plane` with `kernel`, separable kernels take two passes
+pub(crate) fn correlate(plane: &Plane, kernel: &Kernel, border: &Border<Vec<f64>>) -> Plane {
+    match kernel.factors() {
+        Some((row, column)) => {
+            let rows = pass(
+                plane,
+                row,
+                true,
+                border,
+                &outside(border, plane.channels, 1.0),
+            );
+            // a constant row outside went through the row pass too
+            let sum = row.iter().sum();
+            pass(
+                &rows,
+                column,
+                false,
+                border,
+                &outside(border, plane.channels, sum),
+            )
+        }
+        None => full(plane, kernel, border),
+    }
+}
+
+/// Filters `frame` with `kernel` centered on each pixel
+pub fn convolve<F>(frame: &F, kernel: &Kernel, border: Border<F::Pixel>) -> Image<F::Pixel>
+where
+    F: Frame,
+    F::Pixel: Pixel,
+{
+    let plane = Plane::from_frame(frame);
+    correlate(&plane, kernel, &plane_border(border)).to_image()
+}
+
+pub fn gaussian_blur<F>(frame: &F, sigma: f64, border: Border<F::Pixel>) -> Image<F::Pixel>
+where
+    F: Frame,
+    F::Pixel: Pixel,
+{
+    convolve(frame, &Kernel::gaussian(sigma), border)
+}
+
+/// Mean over a window of `radius` pixels around the pixel in both directions
+/// with running sums, the cost does not grow with `radius`
+fn running_mean(plane: &Plane, radius: u32, horizontal: bool, border: &Border<Vec<f64>>) -> Plane {
+    let channels = plane.channels;
+    let (len, lines) = if horizontal {
+        (plane.width, plane.height)
+    } else {
+        (plane.height, plane.width)
+    };
+    let outside = outside(border, channels, 1.0);
+    let window = 2 * radius as usize + 1;
+    let scale = 1.0 / window as f64;
+    let mut out = plane.zeroed();
+    let mut sums = vec![0.0; (len as usize + window) * channels];
+
+    for line in 0..lines {
+        let at = |i: u32| if horizontal { (i, line) } else { (line, i) };
+
+        // prefix sums of the line extended by `radius` on both sides
+        for (i, sum) in sums.chunks_mut(channels).enumerate().skip(1) {
+            let pos = i as i64 - 1 - radius as i64;
+            let values = match border.index(pos, len) {
+                Some(i) => {
+                    let (x, y) = at(i);
+                    plane.at(x, y)
+                }
+                None => &outside,
+            };
+            sum.copy_from_slice(values);
+        }
+        for i in channels..sums.len() {
+            sums[i] += sums[i - channels];
+        }
+
+        for i in 0..len {
+            let (x, y) = at(i);
+            let start = i as usize * channels;
+            let end = (i as usize + window) * channels;
+            for (c, o) in out.at_mut(x, y).iter_mut().enumerate() {
+                *o = (sums[end + c] - sums[start + c]) * scale;
+            }
+        }
+    }
+    out
+}
+
+/// Mean of the `(2 * radius + 1)` squared pixels around each pixel, the
+/// cost per pixel does not depend on `radius`
+pub fn box_blur<F>(frame: &F, radius: u32, border: Border<F::Pixel>) -> Image<F::Pixel>
+where
+    F: Frame,
+    F::Pixel: Pixel,
+{
+    let plane = Plane::from_frame(frame);
+    let border = plane_border(border);
+    let rows = running_mean(&plane, radius, true, &border);
+    running_mean(&rows, radius, false, &border).to_image()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::{RGB32, RGB8};
+
+    const BORDERS: [Border<u8>; 4] = [
+        Border::Constant(40),
+        Border::Replicate,
+        Border::Reflect,
+        Border::Wrap,
+    ];
+
+    fn gray(width: u32, height: u32) -> Image<u8> {
+        Image::from_fn(width, height, |x, y| ((x * 37 + y * 91) % 256) as u8).unwrap()
+    }
+
+    #[test]
+    fn identity() {
+        let img = gray(7, 5);
+        let kernel = Kernel::new(1, 1, vec![1.0]).unwrap();
+        for border in BORDERS {
+            assert_eq!(&convolve(&img, &kernel, border)[..], &img[..]);
+        }
+    }
+
+    #[test]
+    fn separable_matches_full() {
+        let plane = Plane::from_frame(&gray(9, 6));
+        for kernel in [Kernel::gaussian(1.2), Kernel::box_blur(5, 2)] {
+            for border in BORDERS {
+                let border = plane_border(border);
+                let fast = correlate(&plane, &kernel, &border);
+                let slow = full(&plane, &kernel, &border);
+                for (a, b) in fast.data.iter().zip(&slow.data) {
+                    assert!((a - b).abs() < 1e-9);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn running_sum_matches_kernel() {
+        let img = gray(11, 8);
+        for radius in [0, 1, 3, 12] {
+            let size = 2 * radius + 1;
+            for border in BORDERS {
+                let fast = box_blur(&img, radius, border);
+                let slow = convolve(&img, &Kernel::box_blur(size, size), border);
+                assert_eq!(&fast[..], &slow[..], "radius {} {:?}", radius, border);
+            }
+        }
+    }
+
+    #[test]
+    fn borders() {
+        let img = Image::from_vec(3, 1, vec![0_u8, 30, 90]).unwrap();
+        let kernel = Kernel::box_blur(3, 1);
+        let blur = |border| convolve(&img, &kernel, border);
+        assert_eq!(&blur(Border::Constant(60))[..], &[30, 40, 60]);
+        assert_eq!(&blur(Border::Replicate)[..], &[10, 40, 70]);
+        assert_eq!(&blur(Border::Reflect)[..], &[20, 40, 50]);
+        assert_eq!(&blur(Border::Wrap)[..], &[40, 40, 40]);
+    }
+
+    #[test]
+    fn kernels() {
+        let flat = Image::new(6, 6, RGB8::from([10, 100, 200])).unwrap();
+        for kernel in [
+            Kernel::gaussian(2.0),
+            Kernel::sharpen(),
+            Kernel::box_blur(4, 4),
+        ] {
+            let out = convolve(&flat, &kernel, Border::Replicate);
+            assert_eq!(&out[..], &flat[..]);
+        }
+
+        let flat = Image::new(4, 4, 0.5_f32).unwrap();
+        let edges = convolve(&flat, &Kernel::laplacian(), Border::Reflect);
+        assert!(edges.iter().all(|x| *x == 0.0));
+
+        // a bright dot spreads into a symmetric blob
+        let mut dot = Image::new(5, 5, RGB32::default()).unwrap();
+        dot[12] = RGB32::from([1.0, 1.0, 1.0]);
+        let blob = gaussian_blur(&dot, 1.0, Border::Constant(RGB32::default()));
+        assert_eq!(blob[11], blob[13]);
+        assert_eq!(blob[7], blob[17]);
+        assert!(blob[12].r > blob[11].r);
+
+        // emboss lights the top left side of a bright square
+        let mut square = Image::new(6, 6, 0.0_f32).unwrap();
+        for y in 2..4 {
+            for x in 2..4 {
+                square[y * 6 + x] = 1.0;
+            }
+        }
+        let relief = convolve(&square, &Kernel::emboss(), Border::Replicate);
+        assert!(relief[2 * 6 + 2] > 0.0);
+        assert!(relief[3 * 6 + 3] < 0.0);
+    }
+}
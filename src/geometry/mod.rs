@@ -1,5 +1,7 @@
+mod rect;
 mod vec2;
 mod vec3;
 
+pub use rect::Rect;
 pub use vec2::Vec2;
 pub use vec3::Vec3;
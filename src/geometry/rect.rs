@@ -0,0 +1,58 @@
+use super::Vec2;
+
+/// Axis aligned rectangle from its top left corner
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rect<T: Copy> {
+    pub x: T,
+    pub y: T,
+    pub width: T,
+    pub height: T,
+}
+
+macro_rules! rect_impl {
+    ($($t:ty),*) => {
+        $(
+            impl Rect<$t> {
+                pub fn position(&self) -> Vec2<$t> {
+                    Vec2 { x: self.x, y: self.y }
+                }
+
+                pub fn size(&self) -> Vec2<$t> {
+                    Vec2 { x: self.width, y: self.height }
+                }
+
+                /// Whether `point` lies inside, the right and bottom edges excluded
+                pub fn contains(&self, point: Vec2<$t>) -> bool {
+                    point.x >= self.x
+                        && point.y >= self.y
+                        && point.x - self.x < self.width
+                        && point.y - self.y < self.height
+                }
+            }
+        )*
+    };
+}
+
+rect_impl! {u8, i8, u16, i16, u32, i32, u64, i64, u128, i128, f32, f64}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contains() {
+        let rect = Rect {
+            x: 1_u32,
+            y: 2,
+            width: 3,
+            height: 1,
+        };
+        assert!(rect.contains(Vec2 { x: 1, y: 2 }));
+        assert!(rect.contains(Vec2 { x: 3, y: 2 }));
+        assert!(!rect.contains(Vec2 { x: 4, y: 2 }));
+        assert!(!rect.contains(Vec2 { x: 1, y: 3 }));
+        assert!(!rect.contains(Vec2 { x: 0, y: 2 }));
+        assert_eq!(rect.size(), Vec2 { x: 3, y: 1 });
+    }
+}
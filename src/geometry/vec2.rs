@@ -1,4 +1,5 @@
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Vec2<T: Copy> {
     pub x: T,
     pub y: T,
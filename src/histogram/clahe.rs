@@ -0,0 +1,168 @@
+use super::{bin, equalization, lookup, BINS};
+use crate::{
+    color::Normalized,
+    display::{Frame, Image},
+};
+
+/// Start of each of `count` near equal parts of `len`, and `len`
+fn splits(len: u32, count: u32) -> Vec<u32> {
+    (0..=count)
+        .map(|i| (len as u64 * i as u64 / count as u64) as u32)
+        .collect()
+}
+
+/// Clips every bin to `limit` and spreads what was cut evenly over all bins
+fn clip(counts: &mut [u64], limit: u64) {
+    let mut excess = 0;
+    for c in counts.iter_mut() {
+        if *c > limit {
+            excess += *c - limit;
+            *c = limit;
+        }
+    }
+    let (share, rest) = (excess / counts.len() as u64, excess % counts.len() as u64);
+    for (i, c) in counts.iter_mut().enumerate() {
+        *c += share + (if (i as u64) < rest { 1 } else { 0 });
+    }
+}
+
+/// Lower tile and the weight of the upper one for a pixel at `p`
+fn neighbors(p: u32, centers: &[f64]) -> (usize, f64) {
+    let p = p as f64;
+    match centers.iter().rposition(|c| *c <= p) {
+        None => (0, 0.0),
+        Some(i) if i + 1 == centers.len() => (i, 0.0),
+        Some(i) => (i, (p - centers[i]) / (centers[i + 1] - centers[i])),
+    }
+}
+
+/// Contrast Limited Adaptive Histogram Equalization, each channel is
+/// equalized over a grid of `tiles` with bins clipped at `clip_limit` times
+/// the mean bin count, then blended between tile centers.
+///
+/// A `clip_limit` of 1 leaves the frame close to unchanged, larger values
+/// allow more contrast.
+pub fn clahe<F>(frame: &F, tiles: (u32, u32), clip_limit: f64) -> Image<F::Pixel>
+where
+    F: Frame,
+    F::Pixel: Normalized,
+{
+    let (width, height) = (frame.width(), frame.height());
+    let (tx, ty) = (tiles.0.min(width).max(1), tiles.1.min(height).max(1));
+    let (xs, ys) = (splits(width, tx), splits(height, ty));
+    let channels = F::Pixel::CHANNELS;
+
+    let mut curves = Vec::with_capacity((tx * ty) as usize * channels);
+    for y in ys.windows(2) {
+        for x in xs.windows(2) {
+            let mut counts = vec![0; BINS * channels];
+            let mut values = vec![0.0; channels];
+            for row in y[0]..y[1] {
+                for pixel in &frame.row(row)[x[0] as usize..x[1] as usize] {
+                    pixel.normalize(&mut values);
+                    for (c, v) in values.iter().enumerate() {
+                        counts[c * BINS + bin(*v, BINS)] += 1;
+                    }
+                }
+            }
+
+            let area = (x[1] - x[0]) as f64 * (y[1] - y[0]) as f64;
+            let limit = (clip_limit.max(1.0) * area / BINS as f64).ceil() as u64;
+            for counts in counts.chunks_mut(BINS) {
+                clip(counts, limit.max(1));
+                curves.push(equalization(counts));
+            }
+        }
+    }
+
+    let center = |s: &[u32]| -> Vec<f64> {
+        s.windows(2)
+            .map(|w| (w[0] as f64 + w[1] as f64 - 1.0) / 2.0)
+            .collect()
+    };
+    let (cx, cy) = (center(&xs), center(&ys));
+    let curve = |i: usize, j: usize, c: usize| &curves[(j * tx as usize + i) * channels + c];
+
+    let mut values = vec![0.0; channels];
+    Image::from_fn(width, height, |x, y| {
+        let (i, fx) = neighbors(x, &cx);
+        let (j, fy) = neighbors(y, &cy);
+        let (i1, j1) = ((i + 1).min(cx.len() - 1), (j + 1).min(cy.len() - 1));
+
+        let mut pixel = *frame.pixel(x, y);
+        pixel.normalize(&mut values);
+        for (c, o) in values.iter_mut().enumerate() {
+            let v = *o;
+            let top = lookup(curve(i, j, c), v) * (1.0 - fx) + lookup(curve(i1, j, c), v) * fx;
+            let bottom = lookup(curve(i, j1, c), v) * (1.0 - fx) + lookup(curve(i1, j1, c), v) * fx;
+            *o = top * (1.0 - fy) + bottom * fy;
+        }
+        pixel.denormalize(&values);
+        pixel
+    })
+    .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::equalize;
+    use super::*;
+    use crate::color::RGB8;
+
+    fn spread(img: &Image<u8>, x0: u32, x1: u32) -> u8 {
+        let values = img
+            .pixels()
+            .filter(|(x, _, _)| (x0..x1).contains(x))
+            .map(|(_, _, p)| *p);
+        let (min, max) = values.fold((255, 0), |(a, b), p| (a.min(p), b.max(p)));
+        max - min
+    }
+
+    /// Faint texture on a dark left and a bright right half
+    fn halves() -> Image<u8> {
+        Image::from_fn(32, 16, |x, y| {
+            let texture = ((x * 3 + y * 5) % 8) as u8;
+            if x < 16 {
+                20 + texture
+            } else {
+                200 + texture
+            }
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn local_contrast() {
+        let img = halves();
+        let global = equalize(&img);
+        let local = clahe(&img, (2, 1), 40.0);
+        // tiles over each half stretch both textures further than one
+        // global curve can
+        assert!(spread(&local, 0, 10) > spread(&global, 0, 10));
+        assert!(spread(&local, 22, 32) > spread(&global, 22, 32));
+    }
+
+    #[test]
+    fn single_tile() {
+        let img = halves();
+        assert_eq!(&clahe(&img, (1, 1), 1e9)[..], &equalize(&img)[..]);
+
+        let gentle = clahe(&img, (4, 4), 1.0);
+        let strong = clahe(&img, (4, 4), 8.0);
+        assert!(spread(&strong, 0, 8) >= spread(&gentle, 0, 8));
+    }
+
+    #[test]
+    fn clipping() {
+        let mut counts = [10, 0, 0, 2];
+        clip(&mut counts, 4);
+        assert_eq!(counts, [6, 2, 1, 3]);
+        assert_eq!(splits(10, 3), [0, 3, 6, 10]);
+
+        // more tiles than pixels and empty frames do not panic
+        let img = Image::from_vec(2, 1, vec![RGB8::from([1, 2, 3]); 2]).unwrap();
+        assert_eq!(clahe(&img, (8, 8), 2.0).len(), 2);
+        let empty = Image::<u8>::new(0, 0, 0).unwrap();
+        assert_eq!(clahe(&empty, (2, 2), 2.0).len(), 0);
+    }
+}
@@ -0,0 +1,264 @@
+//! Histograms and tone curves derived from them
+//!
+//! Channels are binned over their normalized range, bin `i` of `n` is
+//! centered on `i / (n - 1)` and float values outside of [0, 1] land in the
+//! first or last bin. Every channel is handled on its own, packed and HSL
+//! pixels are read as their `Normalized` channels.
+
+mod clahe;
+
+pub use clahe::clahe;
+
+use crate::{
+    color::Normalized,
+    display::{Frame, Image},
+};
+
+/// Bins used by the tone curves, exact for `u8`
+const BINS: usize = 256;
+
+/// Counts of channel values of a frame
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Histogram {
+    bins: usize,
+    /// `bins` counts for each channel in turn
+    counts: Vec<u64>,
+}
+
+impl Histogram {
+    /// At least one bin, 256 matches `u8` and 65536 matches `u16`
+    pub fn new<F>(frame: &F, bins: usize) -> Self
+    where
+        F: Frame,
+        F::Pixel: Normalized,
+    {
+        let bins = bins.max(1);
+        let channels = F::Pixel::CHANNELS;
+        let mut counts = vec![0; bins * channels];
+        let mut values = vec![0.0; channels];
+        for pixel in frame.rows().flatten() {
+            pixel.normalize(&mut values);
+            for (c, value) in values.iter().enumerate() {
+                counts[c * bins + bin(*value, bins)] += 1;
+            }
+        }
+        Self { bins, counts }
+    }
+
+    pub fn bins(&self) -> usize {
+        self.bins
+    }
+
+    pub fn channels(&self) -> usize {
+        self.counts.len() / self.bins
+    }
+
+    pub fn channel(&self, channel: usize) -> &[u64] {
+        &self.counts[channel * self.bins..(channel + 1) * self.bins]
+    }
+
+    /// Count of values up to and including each bin
+    pub fn cumulative(&self, channel: usize) -> Vec<u64> {
+        self.channel(channel)
+            .iter()
+            .scan(0, |sum, n| {
+                *sum += n;
+                Some(*sum)
+            })
+            .collect()
+    }
+
+    /// Number of pixels counted
+    pub fn total(&self) -> u64 {
+        self.channel(0).iter().sum()
+    }
+}
+
+#[inline]
+fn bin(value: f64, bins: usize) -> usize {
+    (value.clamp(0.0, 1.0) * (bins - 1) as f64).round() as usize
+}
+
+/// `lut` at a normalized value, linear between bin centers
+#[inline]
+fn lookup(lut: &[f64], value: f64) -> f64 {
+    let t = value.clamp(0.0, 1.0) * (lut.len() - 1) as f64;
+    let i = (t as usize).min(lut.len() - 1);
+    match lut.get(i + 1) {
+        Some(next) => lut[i] + (next - lut[i]) * (t - i as f64),
+        None => lut[i],
+    }
+}
+
+/// Curve spreading `counts` evenly over [0, 1], identity for a single value
+fn equalization(counts: &[u64]) -> Vec<f64> {
+    let total: u64 = counts.iter().sum();
+    let first = counts.iter().copied().find(|n| *n > 0).unwrap_or(0);
+    let n = counts.len();
+    if total == first {
+        return (0..n).map(|i| i as f64 / (n - 1).max(1) as f64).collect();
+    }
+
+    let mut sum = 0;
+    counts
+        .iter()
+        .map(|c| {
+            sum += c;
+            (sum.saturating_sub(first)) as f64 / (total - first) as f64
+        })
+        .collect()
+}
+
+/// Maps every channel through its curve
+fn apply<F>(frame: &F, luts: &[Vec<f64>]) -> Image<F::Pixel>
+where
+    F: Frame,
+    F::Pixel: Normalized,
+{
+    let mut values = vec![0.0; F::Pixel::CHANNELS];
+    Image::from_fn(frame.width(), frame.height(), |x, y| {
+        let mut pixel = *frame.pixel(x, y);
+        pixel.normalize(&mut values);
+        for (v, lut) in values.iter_mut().zip(luts) {
+            *v = lookup(lut, *v);
+        }
+        pixel.denormalize(&values);
+        pixel
+    })
+    .unwrap()
+}
+
+/// Spreads the values of each channel evenly over its range
+pub fn equalize<F>(frame: &F) -> Image<F::Pixel>
+where
+    F: Frame,
+    F::Pixel: Normalized,
+{
+    let histogram = Histogram::new(frame, BINS);
+    let luts: Vec<_> = (0..histogram.channels())
+        .map(|c| equalization(histogram.channel(c)))
+        .collect();
+    apply(frame, &luts)
+}
+
+/// Maps each channel so its distribution follows the one of `reference`
+pub fn match_histogram<F, R>(frame: &F, reference: &R) -> Image<F::Pixel>
+where
+    F: Frame,
+    R: Frame<Pixel = F::Pixel>,
+    F::Pixel: Normalized,
+{
+    let source = Histogram::new(frame, BINS);
+    let target = Histogram::new(reference, BINS);
+
+    let luts: Vec<_> = (0..source.channels())
+        .map(|c| {
+            let (s, t) = (source.cumulative(c), target.cumulative(c));
+            let (s_total, t_total) = (source.total().max(1) as f64, target.total().max(1) as f64);
+            // first reference level holding at least the same share of pixels
+            s.iter()
+                .map(|n| {
+                    let share = *n as f64 / s_total;
+                    let level = t
+                        .iter()
+                        .position(|m| *m as f64 / t_total >= share - 1e-12)
+                        .unwrap_or(BINS - 1);
+                    level as f64 / (BINS - 1) as f64
+                })
+                .collect()
+        })
+        .collect();
+    apply(frame, &luts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::{ARGB1555, HSL, RGB565, RGB8};
+
+    #[test]
+    fn counts() {
+        let img = Image::from_vec(
+            3,
+            1,
+            vec![
+                RGB8::from([0, 10, 255]),
+                RGB8::from([0, 20, 255]),
+                RGB8::from([7, 10, 0]),
+            ],
+        )
+        .unwrap();
+        let histogram = Histogram::new(&img, 256);
+        assert_eq!((histogram.bins(), histogram.channels()), (256, 3));
+        assert_eq!(histogram.channel(0)[0], 2);
+        assert_eq!(histogram.channel(0)[7], 1);
+        assert_eq!(histogram.channel(1)[10], 2);
+        assert_eq!(histogram.channel(2)[255], 2);
+        assert_eq!(histogram.total(), 3);
+
+        let cumulative = histogram.cumulative(1);
+        assert_eq!((cumulative[9], cumulative[10], cumulative[255]), (0, 2, 3));
+    }
+
+    #[test]
+    fn float_bins() {
+        let img = Image::from_vec(6, 1, vec![0.0_f32, 0.3, 0.5, 1.0, 2.0, -1.0]).unwrap();
+        let histogram = Histogram::new(&img, 4);
+        assert_eq!(histogram.channel(0), &[2, 1, 1, 2]);
+
+        let single = Histogram::new(&img, 0);
+        assert_eq!(single.channel(0), &[6]);
+    }
+
+    #[test]
+    fn other_formats() {
+        let img =
+            Image::from_vec(2, 1, vec![RGB565::new(31, 0, 16), RGB565::new(0, 63, 16)]).unwrap();
+        let histogram = Histogram::new(&img, 32);
+        assert_eq!(histogram.channels(), 3);
+        assert_eq!((histogram.channel(0)[0], histogram.channel(0)[31]), (1, 1));
+        assert_eq!((histogram.channel(1)[0], histogram.channel(1)[31]), (1, 1));
+        assert_eq!(histogram.channel(2)[16], 2);
+
+        let img = Image::new(2, 2, ARGB1555::new(0, 0, 0).with_alpha(0)).unwrap();
+        let histogram = Histogram::new(&img, 2);
+        assert_eq!(histogram.channels(), 4);
+        assert_eq!(histogram.channel(3), &[4, 0]);
+
+        let img = Image::from_vec(2, 1, vec![HSL::from([180.0, 0.0, 1.0]); 2]).unwrap();
+        let histogram = Histogram::new(&img, 5);
+        assert_eq!(histogram.channel(0), &[0, 0, 2, 0, 0]);
+        assert_eq!(histogram.channel(2), &[0, 0, 0, 0, 2]);
+
+        let img = Image::from_fn(4, 1, |x, _| RGB565::new(8 + x as u8, 40, 4)).unwrap();
+        let out = equalize(&img);
+        assert_eq!((out.pixel(0, 0).r(), out.pixel(3, 0).r()), (0, 31));
+    }
+
+    #[test]
+    fn equalize_stretches() {
+        let img = Image::from_fn(8, 4, |x, _| 100 + x as u8).unwrap();
+        let out = equalize(&img);
+        assert_eq!(*out.pixel(0, 0), 0);
+        assert_eq!(*out.pixel(7, 0), 255);
+        for row in out.rows() {
+            assert!(row.windows(2).all(|w| w[0] < w[1]));
+        }
+
+        let flat = Image::new(3, 3, 77_u8).unwrap();
+        assert_eq!(&equalize(&flat)[..], &flat[..]);
+    }
+
+    #[test]
+    fn matching() {
+        let img = Image::from_fn(16, 4, |x, y| (x * 8 + y) as u8).unwrap();
+        assert_eq!(&match_histogram(&img, &img)[..], &img[..]);
+
+        // two levels in the reference make two levels in the output
+        let reference = Image::from_fn(10, 10, |x, _| if x < 5 { 40_u8 } else { 200 }).unwrap();
+        let out = match_histogram(&img, &reference);
+        for (x, _, p) in out.pixels() {
+            assert_eq!(*p, if x < 8 { 40 } else { 200 });
+        }
+    }
+}
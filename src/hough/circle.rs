@@ -0,0 +1,160 @@
+use crate::{
+    display::{Frame, Image},
+    edge::Gradient,
+    error::IncorrectData,
+    geometry::Vec2,
+    math::is_peak,
+};
+
+/// Circle found by `hough_circles`
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Circle {
+    pub center: Vec2<f64>,
+    pub radius: f64,
+    /// Accumulator votes of the center
+    pub votes: u32,
+}
+
+/// Gradient Hough transform, edge pixels vote for centers along their
+/// gradient direction both ways for radii in `radii`. Centers with at least
+/// `threshold` votes and no stronger center closer than `min_distance`
+/// take the radius most of the edge pixels around them agree on.
+///
+/// `gradient` comes from the frame the edges were found in, such as with
+/// `edge::gradient`, and must match the size of `edges`.
+pub fn hough_circles<F>(
+    edges: &F,
+    gradient: &Gradient,
+    radii: (u32, u32),
+    threshold: u32,
+    min_distance: f64,
+) -> Result<Vec<Circle>, IncorrectData>
+where
+    F: Frame,
+    F::Pixel: Copy + PartialEq + Default,
+{
+    let (width, height) = (edges.width(), edges.height());
+    let direction: &Image<f32> = &gradient.direction;
+    if (direction.width(), direction.height()) != (width, height)
+        || (gradient.magnitude.width(), gradient.magnitude.height()) != (width, height)
+    {
+        return Err(IncorrectData::Size {
+            expected: width as usize * height as usize,
+            got: direction.len(),
+        });
+    }
+
+    let (min, max) = (radii.0.max(1), radii.1.max(radii.0.max(1)));
+    let points = super::edges(edges);
+    let (w, h) = (width as i64, height as i64);
+    let mut acc = vec![0_u32; width as usize * height as usize];
+    for (x, y) in &points {
+        if *gradient.magnitude.pixel(*x as u32, *y as u32) == 0.0 {
+            continue;
+        }
+        let (sin, cos) = (*direction.pixel(*x as u32, *y as u32) as f64).sin_cos();
+        for r in min..=max {
+            for sign in [1.0, -1.0] {
+                let cx = (*x as f64 + sign * r as f64 * cos).round() as i64;
+                let cy = (*y as f64 + sign * r as f64 * sin).round() as i64;
+                if (0..w).contains(&cx) && (0..h).contains(&cy) {
+                    acc[(cy * w + cx) as usize] += 1;
+                }
+            }
+        }
+    }
+
+    let mut centers = Vec::new();
+    for y in 0..h {
+        for x in 0..w {
+            let v = acc[(y * w + x) as usize];
+            if v < threshold.max(1) {
+                continue;
+            }
+            if is_peak(&acc, width, height, x as u32, y as u32) {
+                centers.push((x, y, v));
+            }
+        }
+    }
+    centers.sort_by_key(|c| std::cmp::Reverse(c.2));
+
+    let mut circles: Vec<Circle> = Vec::new();
+    let mut counts = vec![(0_u32, 0.0_f64); (max - min + 1) as usize];
+    for (cx, cy, votes) in centers {
+        let center = Vec2 {
+            x: cx as f64,
+            y: cy as f64,
+        };
+        let near = circles
+            .iter()
+            .any(|c| (c.center.x - center.x).hypot(c.center.y - center.y) < min_distance);
+        if near {
+            continue;
+        }
+
+        // edge pixels at each radius and the sum of their distances
+        counts.iter_mut().for_each(|c| *c = (0, 0.0));
+        for (x, y) in &points {
+            let d = (*x as f64 - center.x).hypot(*y as f64 - center.y);
+            let r = d.round() as u32;
+            if (min..=max).contains(&r) {
+                let count = &mut counts[(r - min) as usize];
+                count.0 += 1;
+                count.1 += d;
+            }
+        }
+        let (count, sum) =
+            counts
+                .iter()
+                .copied()
+                .fold((0, 0.0), |best, c| if c.0 > best.0 { c } else { best });
+        if count > 0 {
+            circles.push(Circle {
+                center,
+                radius: sum / count as f64,
+                votes,
+            });
+        }
+    }
+    Ok(circles)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::edge::{canny, gradient, Operator};
+
+    fn disk(cx: f64, cy: f64, r: f64) -> impl Fn(u32, u32) -> bool {
+        move |x, y| (x as f64 - cx).hypot(y as f64 - cy) <= r
+    }
+
+    #[test]
+    fn circles() {
+        let (a, b) = (disk(15.0, 15.0, 8.0), disk(42.0, 20.0, 5.0));
+        let img =
+            Image::from_fn(60, 40, |x, y| if a(x, y) || b(x, y) { 220_u8 } else { 20 }).unwrap();
+        let edges = canny(&img, 1.0, 0.05, 0.15);
+        let grad = gradient(&img, Operator::Sobel);
+
+        let circles = hough_circles(&edges, &grad, (3, 12), 10, 5.0).unwrap();
+        assert_eq!(circles.len(), 2, "{:?}", circles);
+        let mut found: Vec<_> = circles.iter().map(|c| (c.center, c.radius)).collect();
+        found.sort_by(|a, b| a.0.x.total_cmp(&b.0.x));
+        for ((center, radius), (x, y, r)) in
+            found.iter().zip([(15.0, 15.0, 8.0), (42.0, 20.0, 5.0)])
+        {
+            assert!(
+                (center.x - x).abs() <= 1.0 && (center.y - y).abs() <= 1.0,
+                "{:?}",
+                center
+            );
+            assert!((radius - r).abs() <= 1.0, "{}", radius);
+        }
+        assert!(circles.iter().all(|c| c.votes >= 10));
+        assert!(circles.windows(2).all(|w| w[0].votes >= w[1].votes));
+
+        let small = gradient(&Image::new(10, 10, 0_u8).unwrap(), Operator::Sobel);
+        assert!(hough_circles(&edges, &small, (3, 12), 10, 5.0).is_err());
+    }
+}
@@ -0,0 +1,493 @@
+//! Hough transforms over binary edge frames
+//!
+//! Pixels not equal to `Default::default()` are edges, such as the output of
+//! `edge::canny`. Lines are in normal form, `x * cos(theta) + y * sin(theta)
+//! = rho` with `theta` in [0, pi).
+
+mod circle;
+
+pub use circle::{hough_circles, Circle};
+
+use crate::{display::Frame, error::IncorrectData, geometry::Vec2};
+use std::f64::consts::PI;
+
+/// Line found by `hough_lines`
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Line {
+    pub rho: f64,
+    pub theta: f64,
+    /// Edge pixels on the line
+    pub votes: u32,
+}
+
+impl Line {
+    /// Part of the line inside a `width` x `height` frame
+    pub fn segment(&self, width: u32, height: u32) -> Option<(Vec2<f64>, Vec2<f64>)> {
+        let (sin, cos) = self.theta.sin_cos();
+        let (right, bottom) = (width as f64 - 1.0, height as f64 - 1.0);
+        let mut points = Vec::with_capacity(4);
+        if sin.abs() > 1e-12 {
+            for x in [0.0, right] {
+                points.push(Vec2 {
+                    x,
+                    y: (self.rho - x * cos) / sin,
+                });
+            }
+        }
+        if cos.abs() > 1e-12 {
+            for y in [0.0, bottom] {
+                points.push(Vec2 {
+                    x: (self.rho - y * sin) / cos,
+                    y,
+                });
+            }
+        }
+        let inside = |p: &Vec2<f64>| {
+            (-1e-9..=right + 1e-9).contains(&p.x) && (-1e-9..=bottom + 1e-9).contains(&p.y)
+        };
+        points.retain(inside);
+
+        let mut best: Option<(Vec2<f64>, Vec2<f64>)> = None;
+        let length = |(a, b): (Vec2<f64>, Vec2<f64>)| (b.x - a.x).hypot(b.y - a.y);
+        for (i, a) in points.iter().enumerate() {
+            for b in &points[i..] {
+                if best.is_none_or(|s| length((*a, *b)) > length(s)) {
+                    best = Some((*a, *b));
+                }
+            }
+        }
+        best
+    }
+}
+
+/// Segment found by `hough_segments`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Segment {
+    pub start: Vec2<i32>,
+    pub end: Vec2<i32>,
+    /// Votes of its line when it was found
+    pub votes: u32,
+}
+
+/// Votes of edge pixels for every `(theta, rho)` bin
+struct Accumulator {
+    /// Cosine and sine of each theta
+    angles: Vec<(f64, f64)>,
+    rho_step: f64,
+    /// Bin of rho 0
+    offset: i64,
+    bins: usize,
+    votes: Vec<u32>,
+}
+
+impl Accumulator {
+    /// Fails when a step is not positive or there would be too many bins
+    fn new(width: u32, height: u32, rho_step: f64, theta_step: f64) -> Result<Self, IncorrectData> {
+        if !(rho_step.is_finite() && rho_step > 0.0) {
+            return Err(IncorrectData::Parameter { name: "rho_step" });
+        }
+        if !(theta_step.is_finite() && theta_step > 0.0) {
+            return Err(IncorrectData::Parameter { name: "theta_step" });
+        }
+
+        let count = (PI / theta_step).round().max(1.0);
+        let offset = ((width as f64).hypot(height as f64) / rho_step).ceil();
+        let bins = 2.0 * offset + 1.0;
+        if count * bins * std::mem::size_of::<u32>() as f64 > isize::MAX as f64 {
+            return Err(IncorrectData::Overflow);
+        }
+        let (count, offset, bins) = (count as usize, offset as i64, bins as usize);
+
+        let angles = (0..count)
+            .map(|i| {
+                let (sin, cos) = (i as f64 * PI / count as f64).sin_cos();
+                (cos, sin)
+            })
+            .collect();
+        Ok(Self {
+            angles,
+            rho_step,
+            offset,
+            bins,
+            votes: vec![0; count * bins],
+        })
+    }
+
+    fn theta(&self, t: usize) -> f64 {
+        t as f64 * PI / self.angles.len() as f64
+    }
+
+    fn rho(&self, r: usize) -> f64 {
+        (r as i64 - self.offset) as f64 * self.rho_step
+    }
+
+    fn bin(&self, t: usize, x: i64, y: i64) -> usize {
+        let (cos, sin) = self.angles[t];
+        let r = ((x as f64 * cos + y as f64 * sin) / self.rho_step).round() as i64;
+        t * self.bins + (r + self.offset) as usize
+    }
+
+    /// Adds the votes of `(x, y)`, returning the strongest bin it reached
+    fn vote(&mut self, x: i64, y: i64) -> (usize, u32) {
+        let mut best = (0, 0);
+        for t in 0..self.angles.len() {
+            let i = self.bin(t, x, y);
+            self.votes[i] += 1;
+            if self.votes[i] > best.1 {
+                best = (t, self.votes[i]);
+            }
+        }
+        best
+    }
+
+    fn unvote(&mut self, x: i64, y: i64) {
+        for t in 0..self.angles.len() {
+            let i = self.bin(t, x, y);
+            self.votes[i] -= 1;
+        }
+    }
+}
+
+/// Positions of the edge pixels of `frame`
+fn edges<F>(frame: &F) -> Vec<(i64, i64)>
+where
+    F: Frame,
+    F::Pixel: Copy + PartialEq + Default,
+{
+    frame
+        .pixels()
+        .filter(|(_, _, p)| **p != F::Pixel::default())
+        .map(|(x, y, _)| (x as i64, y as i64))
+        .collect()
+}
+
+/// Standard Hough transform, lines with at least `threshold` votes, the
+/// strongest first. A rasterized line also votes for the bins around it, so
+/// lines within `min_rho` pixels and `min_theta` radians of a stronger one are
+/// dropped.
+///
+/// Fails when a step is not positive or a minimum distance is negative.
+pub fn hough_lines<F>(
+    frame: &F,
+    rho_step: f64,
+    theta_step: f64,
+    threshold: u32,
+    min_rho: f64,
+    min_theta: f64,
+) -> Result<Vec<Line>, IncorrectData>
+where
+    F: Frame,
+    F::Pixel: Copy + PartialEq + Default,
+{
+    if min_rho.is_nan() || min_rho < 0.0 {
+        return Err(IncorrectData::Parameter { name: "min_rho" });
+    }
+    if min_theta.is_nan() || min_theta < 0.0 {
+        return Err(IncorrectData::Parameter { name: "min_theta" });
+    }
+    let mut acc = Accumulator::new(frame.width(), frame.height(), rho_step, theta_step)?;
+    for (x, y) in edges(frame) {
+        acc.vote(x, y);
+    }
+
+    let (thetas, bins) = (acc.angles.len() as i64, acc.bins as i64);
+    // theta wraps around at pi with rho negated
+    let wrap = |t: i64, r: i64| match t {
+        t if t < 0 => (t + thetas, bins - 1 - r),
+        t if t >= thetas => (t - thetas, bins - 1 - r),
+        t => (t, r),
+    };
+    let at = |t: i64, r: i64| {
+        let (t, r) = wrap(t, r);
+        if (0..bins).contains(&r) {
+            acc.votes[(t * bins + r) as usize]
+        } else {
+            0
+        }
+    };
+    let around =
+        |t: i64, r: i64| (-1..=1).flat_map(move |dt| (-1..=1).map(move |dr| (t + dt, r + dr)));
+
+    // local maxima, bins on a plateau ordered by how close to its middle
+    // they are, where the squared votes around them add up the most
+    let mut peaks = Vec::new();
+    for t in 0..thetas {
+        for r in 0..bins {
+            let v = at(t, r);
+            if v >= threshold.max(1) && around(t, r).all(|(t, r)| at(t, r) <= v) {
+                let sum: u64 = around(t, r).map(|(t, r)| (at(t, r) as u64).pow(2)).sum();
+                peaks.push((t, r, v, sum));
+            }
+        }
+    }
+    peaks.sort_by_key(|p| std::cmp::Reverse((p.2, p.3)));
+
+    let theta_bin = PI / thetas as f64;
+    let mut taken: Vec<(i64, i64)> = Vec::new();
+    let mut lines = Vec::new();
+    for (t, r, votes, _) in peaks {
+        let close = taken.iter().any(|(u, s)| {
+            let mirrored = bins - 1 - r;
+            [(t, r), (t - thetas, mirrored), (t + thetas, mirrored)]
+                .iter()
+                .any(|(t, r)| {
+                    (t - u).abs() as f64 * theta_bin <= min_theta
+                        && (r - s).abs() as f64 * acc.rho_step <= min_rho
+                })
+        });
+        if !close {
+            taken.push((t, r));
+            lines.push(Line {
+                rho: acc.rho(r as usize),
+                theta: acc.theta(t as usize),
+                votes,
+            });
+        }
+    }
+    Ok(lines)
+}
+
+/// Fixed seed shuffle, so results do not change between runs
+fn shuffle<T>(items: &mut [T]) {
+    let mut state = 0x9e37_79b9_7f4a_7c15_u64;
+    for i in (1..items.len()).rev() {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        items.swap(i, (state % (i as u64 + 1)) as usize);
+    }
+}
+
+/// Progressive probabilistic Hough transform, edge pixels vote in random
+/// order and once a line reaches `threshold` votes its pixels are followed
+/// both ways, across gaps of up to `max_gap` pixels. Runs of at least
+/// `min_length` become segments, their pixels stop voting either way.
+///
+/// Fails when a step is not positive.
+pub fn hough_segments<F>(
+    frame: &F,
+    rho_step: f64,
+    theta_step: f64,
+    threshold: u32,
+    min_length: f64,
+    max_gap: u32,
+) -> Result<Vec<Segment>, IncorrectData>
+where
+    F: Frame,
+    F::Pixel: Copy + PartialEq + Default,
+{
+    let (width, height) = (frame.width() as i64, frame.height() as i64);
+    let mut acc = Accumulator::new(frame.width(), frame.height(), rho_step, theta_step)?;
+    let mut points = edges(frame);
+    shuffle(&mut points);
+
+    let index = |x: i64, y: i64| (y * width + x) as usize;
+    let mut mask = vec![false; (width * height) as usize];
+    for (x, y) in &points {
+        mask[index(*x, *y)] = true;
+    }
+    let mut voted = vec![false; mask.len()];
+
+    let mut segments = Vec::new();
+    for (x, y) in points {
+        if !mask[index(x, y)] {
+            continue;
+        }
+        voted[index(x, y)] = true;
+        let (t, votes) = acc.vote(x, y);
+        if votes < threshold.max(1) {
+            continue;
+        }
+
+        // steps of one pixel along the major axis of the line
+        let (cos, sin) = acc.angles[t];
+        let scale = sin.abs().max(cos.abs());
+        let (dx, dy) = (-sin / scale, cos / scale);
+        let at = |k: i64| {
+            (
+                (x as f64 + k as f64 * dx).round() as i64,
+                (y as f64 + k as f64 * dy).round() as i64,
+            )
+        };
+        let inside = |(px, py): (i64, i64)| (0..width).contains(&px) && (0..height).contains(&py);
+
+        let mut reach = [0_i64; 2];
+        for (reach, sign) in reach.iter_mut().zip([1, -1]) {
+            let mut gap = 0;
+            for k in 1.. {
+                let p = at(sign * k);
+                if !inside(p) {
+                    break;
+                }
+                if mask[index(p.0, p.1)] {
+                    *reach = sign * k;
+                    gap = 0;
+                } else {
+                    gap += 1;
+                    if gap > max_gap {
+                        break;
+                    }
+                }
+            }
+        }
+
+        let (start, end) = (at(reach[1]), at(reach[0]));
+        let good = ((end.0 - start.0) as f64).hypot((end.1 - start.1) as f64) >= min_length;
+        for k in reach[1]..=reach[0] {
+            let (px, py) = at(k);
+            let i = index(px, py);
+            if mask[i] {
+                if good && voted[i] {
+                    acc.unvote(px, py);
+                }
+                mask[i] = false;
+            }
+        }
+
+        if good {
+            segments.push(Segment {
+                start: Vec2 {
+                    x: start.0 as i32,
+                    y: start.1 as i32,
+                },
+                end: Vec2 {
+                    x: end.0 as i32,
+                    y: end.1 as i32,
+                },
+                votes,
+            });
+        }
+    }
+    Ok(segments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::display::Image;
+    use std::f64::consts::FRAC_PI_2;
+
+    #[test]
+    fn lines() {
+        let img = Image::from_fn(30, 20, |x, y| {
+            if (y == 5 && x < 25) || (x == 3 && y >= 8) {
+                255_u8
+            } else {
+                0
+            }
+        })
+        .unwrap();
+        let degree = PI / 180.0;
+        let lines = hough_lines(&img, 1.0, degree, 12, 5.0, 5.0 * degree).unwrap();
+        assert_eq!(lines.len(), 2, "{:?}", lines);
+        assert_eq!(lines[0].votes, 25);
+        assert!((lines[0].theta - FRAC_PI_2).abs() < 1e-9 && (lines[0].rho - 5.0).abs() < 1e-9);
+        // the vertical line also crosses the horizontal one
+        assert_eq!(lines[1].votes, 13);
+        assert!(lines[1].theta.abs() < 1e-9 && (lines[1].rho - 3.0).abs() < 1e-9);
+
+        assert!(hough_lines(&img, 1.0, degree, 30, 5.0, 5.0 * degree)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn parallel_lines() {
+        // rules of a form, 5 pixels apart
+        let img = Image::from_fn(40, 30, |x, y| {
+            if (y == 10 || y == 15) && (2..38).contains(&x) {
+                255_u8
+            } else {
+                0
+            }
+        })
+        .unwrap();
+        let degree = PI / 180.0;
+        let lines = hough_lines(&img, 1.0, degree, 20, 2.0, 2.0 * degree).unwrap();
+        let mut rhos: Vec<f64> = lines.iter().map(|x| x.rho).collect();
+        rhos.sort_by(f64::total_cmp);
+        assert_eq!(rhos, vec![10.0, 15.0], "{:?}", lines);
+
+        let merged = hough_lines(&img, 1.0, degree, 20, 5.0, 2.0 * degree).unwrap();
+        assert_eq!(merged.len(), 1, "{:?}", merged);
+    }
+
+    #[test]
+    fn parameters() {
+        let img = Image::new(10, 10, 255_u8).unwrap();
+        for (rho_step, theta_step) in [(0.0, 0.1), (1.0, 0.0), (f64::NAN, 0.1), (1.0, -1.0)] {
+            assert!(matches!(
+                hough_lines(&img, rho_step, theta_step, 1, 1.0, 0.1),
+                Err(IncorrectData::Parameter { .. })
+            ));
+            assert!(matches!(
+                hough_segments(&img, rho_step, theta_step, 1, 1.0, 0),
+                Err(IncorrectData::Parameter { .. })
+            ));
+        }
+        assert!(matches!(
+            hough_lines(&img, 1.0, 0.1, 1, -1.0, 0.1),
+            Err(IncorrectData::Parameter { name: "min_rho" })
+        ));
+        assert!(matches!(
+            hough_lines(&img, 1e-300, 0.1, 1, 1.0, 0.1),
+            Err(IncorrectData::Overflow)
+        ));
+    }
+
+    #[test]
+    fn clipping() {
+        let line = Line {
+            rho: 5.0,
+            theta: FRAC_PI_2,
+            votes: 0,
+        };
+        let (a, b) = line.segment(30, 20).unwrap();
+        assert!(a.x.abs() < 1e-9 && (a.y - 5.0).abs() < 1e-9);
+        assert!((b.x - 29.0).abs() < 1e-9 && (b.y - 5.0).abs() < 1e-9);
+
+        // the diagonal through the corners
+        let diagonal = Line {
+            rho: 0.0,
+            theta: 3.0 * PI / 4.0,
+            votes: 0,
+        };
+        let (a, b) = diagonal.segment(10, 10).unwrap();
+        assert!((a.x - a.y).abs() < 1e-9 && (b.x - b.y).abs() < 1e-9);
+        assert!(((a.x - b.x).abs() - 9.0).abs() < 1e-9);
+
+        let outside = Line {
+            rho: 50.0,
+            theta: 0.0,
+            votes: 0,
+        };
+        assert!(outside.segment(10, 10).is_none());
+    }
+
+    #[test]
+    fn segments() {
+        // a long line with a one pixel gap, a short line and a stray pixel
+        let img = Image::from_fn(40, 20, |x, y| {
+            let long = y == 4 && (2..=30).contains(&x) && x != 15;
+            let short = x == 35 && (10..14).contains(&y);
+            if long || short || (x, y) == (10, 15) {
+                255_u8
+            } else {
+                0
+            }
+        })
+        .unwrap();
+        let segments = hough_segments(&img, 1.0, PI / 180.0, 5, 10.0, 2).unwrap();
+        assert_eq!(segments.len(), 1, "{:?}", segments);
+        let mut ends = [segments[0].start, segments[0].end];
+        ends.sort_by_key(|p| p.x);
+        assert_eq!(ends, [Vec2 { x: 2, y: 4 }, Vec2 { x: 30, y: 4 }]);
+
+        assert!(hough_segments(&img, 1.0, PI / 180.0, 5, 10.0, 0)
+            .unwrap()
+            .iter()
+            .all(|s| (s.end.x - s.start.x).abs() < 20));
+    }
+}
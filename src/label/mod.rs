@@ -0,0 +1,324 @@
+//! Connected component labeling
+//!
+//! Pixels equal to `Default::default()` are background, neighbors holding the
+//! same other value form a component. A binary mask gives its blobs and a
+//! label image keeps touching regions of different labels apart.
+
+use crate::{
+    color::{Channel, Pixel},
+    display::{Buffer, Frame},
+    error::IncorrectData,
+    geometry::{Rect, Vec2},
+};
+
+/// Neighbors joining pixels into a component
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connectivity {
+    /// Left, right, above and below
+    Four,
+    /// Diagonals as well
+    Eight,
+}
+
+/// Statistics of a component
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Component {
+    /// Number of pixels
+    pub area: u64,
+    pub bounds: Rect<u32>,
+    /// Mean pixel position
+    pub centroid: Vec2<f64>,
+}
+
+/// Components found by `label`
+pub struct Labels {
+    /// 0 for the background, components are numbered from 1 in the order
+    /// their first pixel appears row by row
+    pub labels: Buffer<u32>,
+    /// Statistics of label `i + 1` at `i`
+    pub components: Vec<Component>,
+}
+
+impl Labels {
+    /// Mean color of each component in `source`, which must match the size
+    /// of the labels
+    pub fn mean_colors<F>(&self, source: &F) -> Result<Vec<F::Pixel>, IncorrectData>
+    where
+        F: Frame,
+        F::Pixel: Pixel,
+    {
+        if (source.width(), source.height()) != (self.labels.width, self.labels.height) {
+            return Err(IncorrectData::Size {
+                expected: self.labels.len(),
+                got: source.width() as usize * source.height() as usize,
+            });
+        }
+
+        let channels = F::Pixel::CHANNELS;
+        let mut sums = vec![0.0; self.components.len() * channels];
+        for (labels, pixels) in self
+            .labels
+            .chunks(self.labels.width.max(1) as usize)
+            .zip(source.rows())
+        {
+            for (label, pixel) in labels.iter().zip(pixels) {
+                if *label == 0 {
+                    continue;
+                }
+                let sum = &mut sums[(*label - 1) as usize * channels..][..channels];
+                for (s, v) in sum.iter_mut().zip(pixel.channels()) {
+                    *s += v.to_f64();
+                }
+            }
+        }
+
+        let mut out = vec![<F::Pixel as Pixel>::Channel::default(); channels];
+        Ok(sums
+            .chunks(channels.max(1))
+            .zip(&self.components)
+            .map(|(sum, component)| {
+                for (o, s) in out.iter_mut().zip(sum) {
+                    *o = Channel::from_f64(s / component.area as f64);
+                }
+                F::Pixel::from_channels(&out)
+            })
+            .collect())
+    }
+}
+
+/// Root of `label`, halving the path on the way
+fn find(parents: &mut [u32], mut label: u32) -> u32 {
+    while parents[label as usize] != label {
+        let parent = parents[label as usize];
+        parents[label as usize] = parents[parent as usize];
+        label = parent;
+    }
+    label
+}
+
+/// Joins the sets of `a` and `b` under the smaller root
+fn union(parents: &mut [u32], a: u32, b: u32) {
+    let (a, b) = (find(parents, a), find(parents, b));
+    parents[a.max(b) as usize] = a.min(b);
+}
+
+/// Running statistics of a component
+struct Sums {
+    area: u64,
+    min: Vec2<u32>,
+    max: Vec2<u32>,
+    sum: Vec2<f64>,
+}
+
+impl Default for Sums {
+    fn default() -> Self {
+        Self {
+            area: 0,
+            min: Vec2 {
+                x: u32::MAX,
+                y: u32::MAX,
+            },
+            max: Vec2 { x: 0, y: 0 },
+            sum: Vec2 { x: 0.0, y: 0.0 },
+        }
+    }
+}
+
+impl Sums {
+    fn add(&mut self, x: u32, y: u32) {
+        let point = Vec2 { x, y };
+        self.area += 1;
+        self.min = self.min.min(point);
+        self.max = self.max.max(point);
+        self.sum.x += x as f64;
+        self.sum.y += y as f64;
+    }
+
+    fn component(&self) -> Component {
+        Component {
+            area: self.area,
+            bounds: Rect {
+                x: self.min.x,
+                y: self.min.y,
+                width: self.max.x - self.min.x + 1,
+                height: self.max.y - self.min.y + 1,
+            },
+            centroid: Vec2 {
+                x: self.sum.x / self.area as f64,
+                y: self.sum.y / self.area as f64,
+            },
+        }
+    }
+}
+
+/// Labels the connected components of `frame` in two passes, provisional
+/// labels joined with union-find and then resolved to their final number
+pub fn label<F>(frame: &F, connectivity: Connectivity) -> Labels
+where
+    F: Frame,
+    F::Pixel: Copy + PartialEq + Default,
+{
+    let (width, height) = (frame.width(), frame.height());
+    let background = F::Pixel::default();
+    let mut labels = vec![0_u32; width as usize * height as usize];
+    // provisional label 0 is the background
+    let mut parents = vec![0_u32];
+
+    let w = width as usize;
+    for y in 0..height as usize {
+        let row = frame.row(y as u32);
+        let above = (y > 0).then(|| frame.row(y as u32 - 1));
+        for x in 0..w {
+            let value = row[x];
+            if value == background {
+                continue;
+            }
+
+            let mut neighbors = [None; 4];
+            if x > 0 && row[x - 1] == value {
+                neighbors[0] = Some(labels[y * w + x - 1]);
+            }
+            if let Some(above) = above {
+                let index = (y - 1) * w + x;
+                if above[x] == value {
+                    neighbors[1] = Some(labels[index]);
+                }
+                if connectivity == Connectivity::Eight {
+                    if x > 0 && above[x - 1] == value {
+                        neighbors[2] = Some(labels[index - 1]);
+                    }
+                    if x + 1 < w && above[x + 1] == value {
+                        neighbors[3] = Some(labels[index + 1]);
+                    }
+                }
+            }
+
+            let mut neighbors = neighbors.iter().flatten();
+            labels[y * w + x] = match neighbors.next() {
+                Some(first) => {
+                    for other in neighbors {
+                        union(&mut parents, *first, *other);
+                    }
+                    *first
+                }
+                None => {
+                    let new = parents.len() as u32;
+                    parents.push(new);
+                    new
+                }
+            };
+        }
+    }
+
+    let mut numbers = vec![0_u32; parents.len()];
+    let mut sums: Vec<Sums> = Vec::new();
+    for (i, label) in labels.iter_mut().enumerate() {
+        if *label == 0 {
+            continue;
+        }
+        let root = find(&mut parents, *label) as usize;
+        if numbers[root] == 0 {
+            sums.push(Sums::default());
+            numbers[root] = sums.len() as u32;
+        }
+        *label = numbers[root];
+        sums[*label as usize - 1].add((i % w) as u32, (i / w) as u32);
+    }
+
+    let components = sums.iter().map(Sums::component).collect();
+
+    Labels {
+        labels: Buffer::from_vec(width, height, labels).unwrap(),
+        components,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::mask;
+    use crate::{color::RGB8, display::Image};
+
+    #[test]
+    fn connectivity() {
+        let img = mask(&["#..", ".#.", "..#"]);
+        let four = label(&img, Connectivity::Four);
+        assert_eq!(four.components.len(), 3);
+        assert_eq!(&four.labels[..], &[1, 0, 0, 0, 2, 0, 0, 0, 3]);
+
+        let eight = label(&img, Connectivity::Eight);
+        assert_eq!(eight.components.len(), 1);
+        assert_eq!(eight.components[0].area, 3);
+        assert_eq!(
+            eight.components[0].bounds,
+            Rect {
+                x: 0,
+                y: 0,
+                width: 3,
+                height: 3
+            }
+        );
+    }
+
+    #[test]
+    fn merging() {
+        // the arms start as separate labels and meet at the bottom, the
+        // anti-diagonal one only joins through the top right neighbor
+        let img = mask(&["#.#...#", "#.#..#.", "###.#..", "......."]);
+        let labels = label(&img, Connectivity::Eight);
+        assert_eq!(labels.components.len(), 2);
+        assert_eq!(*labels.labels.pixel(0, 0), *labels.labels.pixel(2, 0));
+        assert_eq!(*labels.labels.pixel(0, 0), 1);
+        assert_eq!(*labels.labels.pixel(6, 0), 2);
+        assert_eq!(*labels.labels.pixel(4, 2), 2);
+
+        let u = labels.components[0];
+        assert_eq!(u.area, 7);
+        assert_eq!(
+            u.bounds,
+            Rect {
+                x: 0,
+                y: 0,
+                width: 3,
+                height: 3
+            }
+        );
+        assert!((u.centroid.x - 1.0).abs() < 1e-12);
+        assert!((u.centroid.y - 8.0 / 7.0).abs() < 1e-12);
+
+        assert_eq!(label(&img, Connectivity::Four).components.len(), 4);
+    }
+
+    #[test]
+    fn label_images() {
+        let img = Image::from_vec(4, 1, vec![3_u8, 3, 5, 0]).unwrap();
+        let labels = label(&img, Connectivity::Eight);
+        assert_eq!(&labels.labels[..], &[1, 1, 2, 0]);
+
+        let empty = Image::<u8>::new(0, 0, 0).unwrap();
+        assert!(label(&empty, Connectivity::Four).components.is_empty());
+    }
+
+    #[test]
+    fn mean_colors() {
+        let img = mask(&["##.#"]);
+        let source = Image::from_vec(
+            4,
+            1,
+            vec![
+                RGB8::from([10, 20, 30]),
+                RGB8::from([20, 40, 60]),
+                RGB8::from([255, 255, 255]),
+                RGB8::from([1, 2, 3]),
+            ],
+        )
+        .unwrap();
+        let labels = label(&img, Connectivity::Four);
+        let colors = labels.mean_colors(&source).unwrap();
+        assert_eq!(colors, [RGB8::from([15, 30, 45]), RGB8::from([1, 2, 3])]);
+
+        let small = Image::new(3, 1, RGB8::from([0, 0, 0])).unwrap();
+        assert!(labels.mean_colors(&small).is_err());
+    }
+}
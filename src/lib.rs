@@ -1,5 +1,23 @@
+#![allow(clippy::upper_case_acronyms)]
+
 pub mod color;
+pub mod contour;
 pub mod display;
+pub mod edge;
 pub mod error;
+pub mod feature;
+pub mod filter;
 pub mod geometry;
+pub mod histogram;
+pub mod hough;
+pub mod label;
+pub mod matching;
 pub mod math;
+pub mod morphology;
+pub mod resize;
+pub mod threshold;
+pub mod tonemap;
+pub mod transform;
+
+#[cfg(test)]
+mod test_util;
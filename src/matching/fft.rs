@@ -0,0 +1,175 @@
+use crate::filter::Plane;
+use std::f64::consts::PI;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct Complex {
+    re: f64,
+    im: f64,
+}
+
+impl Complex {
+    #[inline]
+    fn add(self, o: Self) -> Self {
+        Self {
+            re: self.re + o.re,
+            im: self.im + o.im,
+        }
+    }
+
+    #[inline]
+    fn sub(self, o: Self) -> Self {
+        Self {
+            re: self.re - o.re,
+            im: self.im - o.im,
+        }
+    }
+
+    #[inline]
+    fn mul(self, o: Self) -> Self {
+        Self {
+            re: self.re * o.re - self.im * o.im,
+            im: self.re * o.im + self.im * o.re,
+        }
+    }
+
+    #[inline]
+    fn conj(self) -> Self {
+        Self {
+            re: self.re,
+            im: -self.im,
+        }
+    }
+}
+
+/// In place radix-2 transform of `data`, whose length is a power of two,
+/// every `stride`th value from the start. The inverse is not scaled.
+fn fft(data: &mut [Complex], start: usize, stride: usize, len: usize, inverse: bool) {
+    if len < 2 {
+        return;
+    }
+    let at = |i: usize| start + i * stride;
+    let bits = len.trailing_zeros();
+    for i in 0..len {
+        let j = i.reverse_bits() >> (usize::BITS - bits);
+        if i < j {
+            data.swap(at(i), at(j));
+        }
+    }
+
+    let sign = if inverse { 1.0 } else { -1.0 };
+    let mut size = 2;
+    while size <= len {
+        let (sin, cos) = (sign * 2.0 * PI / size as f64).sin_cos();
+        let step = Complex { re: cos, im: sin };
+        for block in (0..len).step_by(size) {
+            let mut w = Complex { re: 1.0, im: 0.0 };
+            for k in 0..size / 2 {
+                let (a, b) = (at(block + k), at(block + k + size / 2));
+                let t = w.mul(data[b]);
+                data[b] = data[a].sub(t);
+                data[a] = data[a].add(t);
+                w = w.mul(step);
+            }
+        }
+        size *= 2;
+    }
+}
+
+/// Transform of a `width` x `height` grid, rows then columns
+fn fft2(data: &mut [Complex], width: usize, height: usize, inverse: bool) {
+    for y in 0..height {
+        fft(data, y * width, 1, width, inverse);
+    }
+    for x in 0..width {
+        fft(data, x, width, height, inverse);
+    }
+}
+
+/// Sums of `image` times `template` with the template at each position
+/// fully inside, per channel, through the frequency domain
+pub(crate) fn correlate(image: &Plane, template: &Plane) -> Vec<f64> {
+    let channels = image.channels;
+    let (width, height) = (image.width as usize, image.height as usize);
+    let (ow, oh) = (
+        width - template.width as usize + 1,
+        height - template.height as usize + 1,
+    );
+    // no wrapping reaches the positions inside
+    let (n, m) = (width.next_power_of_two(), height.next_power_of_two());
+    let scale = 1.0 / (n * m) as f64;
+
+    let mut out = vec![0.0; ow * oh * channels];
+    let (mut a, mut b) = (
+        vec![Complex::default(); n * m],
+        vec![Complex::default(); n * m],
+    );
+    for c in 0..channels {
+        let load = |grid: &mut [Complex], plane: &Plane| {
+            grid.iter_mut().for_each(|v| *v = Complex::default());
+            for y in 0..plane.height {
+                for x in 0..plane.width {
+                    grid[y as usize * n + x as usize].re = plane.at(x, y)[c];
+                }
+            }
+        };
+        load(&mut a, image);
+        load(&mut b, template);
+        fft2(&mut a, n, m, false);
+        fft2(&mut b, n, m, false);
+        for (a, b) in a.iter_mut().zip(&b) {
+            *a = a.mul(b.conj());
+        }
+        fft2(&mut a, n, m, true);
+
+        for y in 0..oh {
+            for x in 0..ow {
+                out[(y * ow + x) * channels + c] = a[y * n + x].re * scale;
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let values = [1.0, -2.0, 3.5, 0.0, 4.0, 1.0, -1.0, 2.0];
+        let mut data: Vec<_> = values.iter().map(|v| Complex { re: *v, im: 0.0 }).collect();
+        fft(&mut data, 0, 1, 8, false);
+        // the first bin is the sum
+        assert!((data[0].re - 8.5).abs() < 1e-12 && data[0].im.abs() < 1e-12);
+        fft(&mut data, 0, 1, 8, true);
+        for (d, v) in data.iter().zip(values) {
+            assert!((d.re / 8.0 - v).abs() < 1e-12 && d.im.abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn correlation() {
+        let image = Plane {
+            width: 5,
+            height: 3,
+            channels: 1,
+            data: (0..15).map(|i| (i * 7 % 5) as f64).collect(),
+        };
+        let template = Plane {
+            width: 2,
+            height: 2,
+            channels: 1,
+            data: vec![1.0, 2.0, -1.0, 0.5],
+        };
+        let out = correlate(&image, &template);
+        assert_eq!(out.len(), 8);
+        for y in 0..2 {
+            for x in 0..4 {
+                let expected = image.at(x, y)[0] + 2.0 * image.at(x + 1, y)[0]
+                    - image.at(x, y + 1)[0]
+                    + 0.5 * image.at(x + 1, y + 1)[0];
+                assert!((out[(y * 4 + x) as usize] - expected).abs() < 1e-9);
+            }
+        }
+    }
+}
@@ -0,0 +1,358 @@
+//! Template matching
+//!
+//! Frames are compared over all of their channels in normalized units. A
+//! score map holds the score of each position of the top left corner of the
+//! template that keeps it inside the frame.
+
+mod fft;
+
+use crate::{
+    color::Pixel,
+    display::{Frame, Image},
+    filter::Plane,
+    geometry::Vec2,
+};
+
+/// Score of the template against a window of the frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    /// Sum of squared differences, 0 for a perfect match
+    SquaredDifference,
+    /// Squared differences over the root of the product of the sums of
+    /// squares, 0 for a perfect match
+    NormedSquaredDifference,
+    /// Sum of products
+    CrossCorrelation,
+    /// Zero mean cross-correlation over the root of the product of the
+    /// variances, from -1 to 1 for a perfect match, 0 for flat windows
+    NormedCrossCorrelation,
+}
+
+impl Method {
+    /// Whether lower scores are better matches
+    pub fn lower_is_better(self) -> bool {
+        matches!(
+            self,
+            Method::SquaredDifference | Method::NormedSquaredDifference
+        )
+    }
+}
+
+/// Position of the template and its score
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Match {
+    pub position: Vec2<u32>,
+    pub score: f32,
+}
+
+/// Work of the direct sums over the work of the frequency domain past
+/// which the latter is used
+const FFT_RATIO: f64 = 4.0;
+
+/// Per channel prefix sums of the values and of their squares
+struct Integral {
+    width: usize,
+    channels: usize,
+    sums: Vec<(f64, f64)>,
+}
+
+impl Integral {
+    fn new(plane: &Plane) -> Self {
+        let (width, channels) = (plane.width as usize + 1, plane.channels);
+        let mut sums = vec![(0.0, 0.0); width * (plane.height as usize + 1) * channels];
+        for y in 0..plane.height as usize {
+            for x in 0..plane.width as usize {
+                for c in 0..channels {
+                    let v = plane.data[(y * plane.width as usize + x) * channels + c];
+                    let i = |x: usize, y: usize| (y * width + x) * channels + c;
+                    let (left, up, corner) = (sums[i(x, y + 1)], sums[i(x + 1, y)], sums[i(x, y)]);
+                    sums[i(x + 1, y + 1)] = (
+                        v + left.0 + up.0 - corner.0,
+                        v * v + left.1 + up.1 - corner.1,
+                    );
+                }
+            }
+        }
+        Self {
+            width,
+            channels,
+            sums,
+        }
+    }
+
+    /// Sum and sum of squares of channel `c` over a window
+    fn window(&self, x: usize, y: usize, width: usize, height: usize, c: usize) -> (f64, f64) {
+        let at = |x: usize, y: usize| self.sums[(y * self.width + x) * self.channels + c];
+        let (a, b) = (at(x, y), at(x + width, y));
+        let (d, e) = (at(x, y + height), at(x + width, y + height));
+        (e.0 - b.0 - d.0 + a.0, e.1 - b.1 - d.1 + a.1)
+    }
+}
+
+/// Sums of `image` times `template` at each position, per channel
+fn correlate(image: &Plane, template: &Plane) -> Vec<f64> {
+    let channels = image.channels;
+    let (ow, oh) = (
+        (image.width - template.width + 1) as usize,
+        (image.height - template.height + 1) as usize,
+    );
+    let (n, m) = (
+        image.width.next_power_of_two() as f64,
+        image.height.next_power_of_two() as f64,
+    );
+    let direct = (ow * oh) as f64 * (template.width * template.height) as f64;
+    if direct > FFT_RATIO * 3.0 * n * m * (n * m).log2() {
+        return fft::correlate(image, template);
+    }
+
+    let mut out = vec![0.0; ow * oh * channels];
+    for y in 0..oh {
+        for x in 0..ow {
+            let sums = &mut out[(y * ow + x) * channels..][..channels];
+            for ty in 0..template.height as usize {
+                let start = ((y + ty) * image.width as usize + x) * channels;
+                let row = &image.data[start..][..template.width as usize * channels];
+                let t = &template.data[ty * template.width as usize * channels..][..row.len()];
+                for (k, (i, t)) in row.iter().zip(t).enumerate() {
+                    sums[k % channels] += i * t;
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Scores of `template` at every position inside `frame`, empty when the
+/// template is empty or does not fit
+pub fn match_template<F, T>(frame: &F, template: &T, method: Method) -> Image<f32>
+where
+    F: Frame,
+    T: Frame<Pixel = F::Pixel>,
+    F::Pixel: Pixel,
+{
+    let (width, height) = (template.width(), template.height());
+    if width == 0 || height == 0 || width > frame.width() || height > frame.height() {
+        return Image::new(0, 0, 0.0).unwrap();
+    }
+
+    let (image, template) = (Plane::from_frame(frame), Plane::from_frame(template));
+    let channels = image.channels;
+    let n = (width * height) as f64;
+    let integral = Integral::new(&image);
+    let whole = Integral::new(&template);
+    let totals: Vec<(f64, f64)> = (0..channels)
+        .map(|c| whole.window(0, 0, width as usize, height as usize, c))
+        .collect();
+    let cross = correlate(&image, &template);
+
+    let (ow, oh) = (image.width - width + 1, image.height - height + 1);
+    Image::from_fn(ow, oh, |x, y| {
+        let i = (y * ow + x) as usize * channels;
+        let (mut products, mut window, mut squares) = (0.0, 0.0, 0.0);
+        let (mut covariance, mut variance, mut template_variance) = (0.0, 0.0, 0.0);
+        for (c, (t, tt)) in totals.iter().enumerate() {
+            let (f, ff) =
+                integral.window(x as usize, y as usize, width as usize, height as usize, c);
+            let ft = cross[i + c];
+            products += ft;
+            window += ff;
+            squares += tt;
+            covariance += ft - f * t / n;
+            variance += ff - f * f / n;
+            template_variance += tt - t * t / n;
+        }
+
+        let difference = (squares - 2.0 * products + window).max(0.0);
+        let score = match method {
+            Method::SquaredDifference => difference,
+            Method::NormedSquaredDifference => {
+                let norm = (window * squares).sqrt();
+                match (norm > 0.0, difference > 1e-12) {
+                    (true, _) => difference / norm,
+                    (false, true) => 1.0,
+                    (false, false) => 0.0,
+                }
+            }
+            Method::CrossCorrelation => products,
+            Method::NormedCrossCorrelation => {
+                // flat within rounding
+                let flat = |v: f64| v <= 1e-12 * n;
+                if flat(variance) || flat(template_variance) {
+                    0.0
+                } else {
+                    (covariance / (variance * template_variance).sqrt()).clamp(-1.0, 1.0)
+                }
+            }
+        };
+        score as f32
+    })
+    .unwrap()
+}
+
+/// Up to `count` best positions of `scores`, best first. Positions closer
+/// than `min_distance` on both axes to a better one are left out, so the
+/// size of the template keeps matches from overlapping.
+pub fn best_matches(
+    scores: &Image<f32>,
+    method: Method,
+    count: usize,
+    min_distance: u32,
+) -> Vec<Match> {
+    let mut order: Vec<Match> = scores
+        .pixels()
+        .filter(|(_, _, s)| !s.is_nan())
+        .map(|(x, y, s)| Match {
+            position: Vec2 { x, y },
+            score: *s,
+        })
+        .collect();
+    if method.lower_is_better() {
+        order.sort_by(|a, b| a.score.total_cmp(&b.score));
+    } else {
+        order.sort_by(|a, b| b.score.total_cmp(&a.score));
+    }
+
+    let mut matches: Vec<Match> = Vec::new();
+    for m in order {
+        if matches.len() == count {
+            break;
+        }
+        let near = matches.iter().any(|b| {
+            m.position.x.abs_diff(b.position.x) < min_distance
+                && m.position.y.abs_diff(b.position.y) < min_distance
+        });
+        if !near {
+            matches.push(m);
+        }
+    }
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::RGB8;
+
+    fn texture(width: u32, height: u32) -> Image<u8> {
+        Image::from_fn(width, height, |x, y| {
+            ((x * 7919 + y * 104_729) ^ (x * y * 31)).wrapping_mul(2_654_435_761) as u8
+        })
+        .unwrap()
+    }
+
+    fn crop<P: Copy>(img: &Image<P>, x: u32, y: u32, width: u32, height: u32) -> Image<P> {
+        Image::from_fn(width, height, |i, j| *img.pixel(x + i, y + j)).unwrap()
+    }
+
+    const METHODS: [Method; 4] = [
+        Method::SquaredDifference,
+        Method::NormedSquaredDifference,
+        Method::CrossCorrelation,
+        Method::NormedCrossCorrelation,
+    ];
+
+    #[test]
+    fn exact() {
+        let img = texture(24, 16);
+        let template = crop(&img, 7, 4, 6, 5);
+        for method in METHODS {
+            let scores = match_template(&img, &template, method);
+            assert_eq!((scores.width(), scores.height()), (19, 12));
+            let best = best_matches(&scores, method, 1, 0)[0];
+            assert_eq!(best.position, Vec2 { x: 7, y: 4 }, "{:?}", method);
+            match method {
+                Method::SquaredDifference | Method::NormedSquaredDifference => {
+                    assert!(best.score.abs() < 1e-6)
+                }
+                Method::NormedCrossCorrelation => assert!((best.score - 1.0).abs() < 1e-6),
+                Method::CrossCorrelation => {}
+            }
+        }
+
+        let color = texture(20, 12).map(|v| RGB8::from([v, 255 - v, v / 2]));
+        let template = crop(&color, 11, 6, 4, 4);
+        let scores = match_template(&color, &template, Method::NormedCrossCorrelation);
+        assert_eq!(
+            best_matches(&scores, Method::NormedCrossCorrelation, 1, 0)[0].position,
+            Vec2 { x: 11, y: 6 }
+        );
+    }
+
+    #[test]
+    fn large_templates() {
+        // large enough for the frequency domain, which agrees with the
+        // direct sums
+        let img = texture(64, 64);
+        let template = crop(&img, 5, 9, 40, 40);
+        let image = Plane::from_frame(&img);
+        let plane = Plane::from_frame(&template);
+        let fast = correlate(&image, &plane);
+        let slow: Vec<f64> = (0..25)
+            .flat_map(|y| (0..25).map(move |x| (x, y)))
+            .map(|(x, y)| {
+                (0..40)
+                    .flat_map(|j| (0..40).map(move |i| (i, j)))
+                    .map(|(i, j)| image.at(x + i, y + j)[0] * plane.at(i, j)[0])
+                    .sum()
+            })
+            .collect();
+        assert!(fast.iter().zip(&slow).all(|(a, b)| (a - b).abs() < 1e-9));
+
+        let scores = match_template(&img, &template, Method::SquaredDifference);
+        let best = best_matches(&scores, Method::SquaredDifference, 1, 0)[0];
+        assert_eq!(best.position, Vec2 { x: 5, y: 9 });
+        assert!(best.score.abs() < 1e-6);
+    }
+
+    #[test]
+    fn flat_and_empty() {
+        let flat = Image::new(8, 8, 100_u8).unwrap();
+        let template = Image::new(3, 3, 100_u8).unwrap();
+        let scores = match_template(&flat, &template, Method::NormedCrossCorrelation);
+        assert!(scores.iter().all(|s| *s == 0.0));
+        let scores = match_template(&flat, &template, Method::NormedSquaredDifference);
+        assert!(scores.iter().all(|s| s.abs() < 1e-6));
+
+        let black = Image::new(3, 3, 0_u8).unwrap();
+        let scores = match_template(&flat, &black, Method::NormedSquaredDifference);
+        assert!(scores.iter().all(|s| *s == 1.0));
+
+        assert_eq!(
+            match_template(&template, &flat, Method::SquaredDifference).len(),
+            0
+        );
+        let empty = Image::new(0, 3, 0_u8).unwrap();
+        assert_eq!(
+            match_template(&flat, &empty, Method::SquaredDifference).len(),
+            0
+        );
+    }
+
+    #[test]
+    fn suppression() {
+        // two copies of a pattern on a dark background
+        let pattern = texture(5, 5);
+        let img = Image::from_fn(30, 12, |x, y| match (x, y) {
+            (2..=6, 3..=7) => *pattern.pixel(x - 2, y - 3),
+            (20..=24, 5..=9) => *pattern.pixel(x - 20, y - 5),
+            _ => 0,
+        })
+        .unwrap();
+        let scores = match_template(&img, &pattern, Method::SquaredDifference);
+
+        let matches = best_matches(&scores, Method::SquaredDifference, 2, 5);
+        let positions: Vec<_> = matches.iter().map(|m| m.position).collect();
+        assert!(positions.contains(&Vec2 { x: 2, y: 3 }));
+        assert!(positions.contains(&Vec2 { x: 20, y: 5 }));
+
+        // only two positions match exactly
+        let close = best_matches(&scores, Method::SquaredDifference, 3, 0);
+        assert_eq!(close.len(), 3);
+        assert!(close[2].score > 0.0);
+        assert_eq!(
+            best_matches(&scores, Method::SquaredDifference, 0, 5).len(),
+            0
+        );
+    }
+}
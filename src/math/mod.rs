@@ -9,6 +9,28 @@ pub(crate) const fn index2d_to_index(side: u32, x: u32, y: u32) -> usize {
     side as usize * y as usize + x as usize
 }
 
+/// Whether `(x, y)` of a `width` x `height` grid is at least as high as its 8
+/// neighbors, ties go to the first of the equal values in row order
+pub(crate) fn is_peak<T: PartialOrd>(data: &[T], width: u32, height: u32, x: u32, y: u32) -> bool {
+    let (w, h) = (width as i64, height as i64);
+    let v = &data[index2d_to_index(width, x, y)];
+    (-1..=1_i64)
+        .flat_map(|dy| (-1..=1_i64).map(move |dx| (dx, dy)))
+        .filter(|d| *d != (0, 0))
+        .all(|(dx, dy)| {
+            let (nx, ny) = (x as i64 + dx, y as i64 + dy);
+            if !(0..w).contains(&nx) || !(0..h).contains(&ny) {
+                return true;
+            }
+            let n = &data[(ny * w + nx) as usize];
+            if (dy, dx) < (0, 0) {
+                v > n
+            } else {
+                v >= n
+            }
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -46,4 +68,19 @@ mod tests {
         let index = index_to_index2d(width, index);
         assert_eq!(index, (width - 1, width - 1));
     }
+
+    #[test]
+    fn peaks() {
+        #[rustfmt::skip]
+        let data = [
+            1, 5, 5,
+            0, 2, 1,
+            3, 0, 3,
+        ];
+        let peaks: Vec<(u32, u32)> = (0..3)
+            .flat_map(|y| (0..3).map(move |x| (x, y)))
+            .filter(|(x, y)| is_peak(&data, 3, 3, *x, *y))
+            .collect();
+        assert_eq!(peaks, vec![(1, 0), (0, 2), (2, 2)]);
+    }
 }
@@ -0,0 +1,120 @@
+use crate::error::IncorrectData;
+
+/// Structuring element, anchored at `(width / 2, height / 2)`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Element {
+    width: u32,
+    height: u32,
+    mask: Vec<bool>,
+}
+
+impl Element {
+    /// `mask` row by row, `true` where the element covers
+    pub fn new(width: u32, height: u32, mask: Vec<bool>) -> Result<Self, IncorrectData> {
+        let expected = width as usize * height as usize;
+        if mask.len() != expected {
+            return Err(IncorrectData::Size {
+                expected,
+                got: mask.len(),
+            });
+        }
+        Ok(Self {
+            width,
+            height,
+            mask,
+        })
+    }
+
+    pub fn rect(width: u32, height: u32) -> Self {
+        Self::from_fn(width, height, |_, _| true)
+    }
+
+    /// Middle row and middle column
+    pub fn cross(width: u32, height: u32) -> Self {
+        Self::from_fn(width, height, |x, y| x == width / 2 || y == height / 2)
+    }
+
+    /// Ellipse inscribed in the `width` x `height` box
+    pub fn ellipse(width: u32, height: u32) -> Self {
+        let (rx, ry) = (width as f64 / 2.0, height as f64 / 2.0);
+        Self::from_fn(width, height, |x, y| {
+            let dx = (x as f64 + 0.5 - rx) / rx;
+            let dy = (y as f64 + 0.5 - ry) / ry;
+            dx * dx + dy * dy <= 1.0
+        })
+    }
+
+    fn from_fn<F: Fn(u32, u32) -> bool>(width: u32, height: u32, f: F) -> Self {
+        let mask = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| f(x, y))
+            .collect();
+        Self {
+            width,
+            height,
+            mask,
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn mask(&self) -> &[bool] {
+        &self.mask
+    }
+
+    /// Covers its whole box, which allows the separable fast path
+    pub fn is_rect(&self) -> bool {
+        self.mask.iter().all(|x| *x)
+    }
+
+    /// Covered offsets from the anchor
+    pub(crate) fn offsets(&self) -> Vec<(i64, i64)> {
+        let (ax, ay) = ((self.width / 2) as i64, (self.height / 2) as i64);
+        let width = self.width.max(1) as usize;
+        self.mask
+            .iter()
+            .enumerate()
+            .filter(|(_, m)| **m)
+            .map(|(i, _)| ((i % width) as i64 - ax, (i / width) as i64 - ay))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(element: &Element) -> String {
+        element
+            .mask()
+            .chunks(element.width() as usize)
+            .map(|row| row.iter().map(|m| if *m { '#' } else { '.' }).collect())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn shapes() {
+        assert_eq!(text(&Element::cross(3, 3)), ".#.\n###\n.#.");
+        assert_eq!(text(&Element::rect(2, 2)), "##\n##");
+        assert_eq!(
+            text(&Element::ellipse(5, 5)),
+            ".###.\n#####\n#####\n#####\n.###."
+        );
+        assert!(Element::rect(4, 1).is_rect());
+        assert!(!Element::cross(3, 3).is_rect());
+    }
+
+    #[test]
+    fn new() {
+        assert!(Element::new(2, 2, vec![true; 3]).is_err());
+        let element = Element::new(3, 1, vec![false, false, true]).unwrap();
+        assert_eq!(element.offsets(), [(1, 0)]);
+    }
+}
@@ -0,0 +1,399 @@
+//! Morphology of single channel frames
+//!
+//! Pixels outside of the frame are ignored, so shapes touching the edge are
+//! neither eroded nor grown by it. Binary images are the special case of
+//! only 0 and `MAX` values.
+
+mod element;
+
+pub use element::Element;
+
+use crate::{
+    color::Channel,
+    display::{Frame, Image},
+};
+
+#[inline]
+fn min<T: PartialOrd>(a: T, b: T) -> T {
+    if b < a {
+        b
+    } else {
+        a
+    }
+}
+
+#[inline]
+fn max<T: PartialOrd>(a: T, b: T) -> T {
+    if b > a {
+        b
+    } else {
+        a
+    }
+}
+
+/// `op` over windows of `size` starting `anchor` before each value, van Herk
+/// and Gil-Werman style with two comparisons per value whatever the size
+fn line<T: Copy>(input: &[T], size: usize, anchor: usize, op: fn(T, T) -> T, out: &mut [T]) {
+    let n = input.len();
+    // replicating the edge never changes a min or max over a window that
+    // already contains it
+    let padded: Vec<T> = (0..n + size - 1)
+        .map(|i| input[i.saturating_sub(anchor).min(n - 1)])
+        .collect();
+
+    let mut forward = padded.clone();
+    for i in 1..padded.len() {
+        if !i.is_multiple_of(size) {
+            forward[i] = op(forward[i - 1], padded[i]);
+        }
+    }
+    let mut backward = padded;
+    for i in (0..backward.len() - 1).rev() {
+        if !(i + 1).is_multiple_of(size) {
+            backward[i] = op(backward[i + 1], backward[i]);
+        }
+    }
+
+    for (x, o) in out.iter_mut().enumerate() {
+        *o = op(backward[x], forward[x + size - 1]);
+    }
+}
+
+/// Rectangle of `width` x `height` with the window starting `anchor`
+/// before each pixel
+fn rect<F>(
+    frame: &F,
+    width: u32,
+    height: u32,
+    anchor: (u32, u32),
+    op: fn(F::Pixel, F::Pixel) -> F::Pixel,
+) -> Image<F::Pixel>
+where
+    F: Frame,
+    F::Pixel: Channel,
+{
+    let (w, h) = (frame.width() as usize, frame.height() as usize);
+    let mut rows = vec![F::Pixel::default(); w * h];
+    for (row, out) in frame.rows().zip(rows.chunks_mut(w.max(1))) {
+        line(row, width as usize, anchor.0 as usize, op, out);
+    }
+
+    let mut data = vec![F::Pixel::default(); w * h];
+    let mut column = vec![F::Pixel::default(); h];
+    let mut out = vec![F::Pixel::default(); h];
+    for x in 0..w {
+        for (y, c) in column.iter_mut().enumerate() {
+            *c = rows[y * w + x];
+        }
+        line(&column, height as usize, anchor.1 as usize, op, &mut out);
+        for (y, o) in out.iter().enumerate() {
+            data[y * w + x] = *o;
+        }
+    }
+    Image::from_vec(frame.width(), frame.height(), data).unwrap()
+}
+
+/// `op` over the pixels at `offsets` from each pixel
+fn by_offsets<F>(
+    frame: &F,
+    offsets: &[(i64, i64)],
+    op: fn(F::Pixel, F::Pixel) -> F::Pixel,
+) -> Image<F::Pixel>
+where
+    F: Frame,
+    F::Pixel: Channel,
+{
+    let (w, h) = (frame.width() as i64, frame.height() as i64);
+    Image::from_fn(frame.width(), frame.height(), |x, y| {
+        let (x, y) = (x as i64, y as i64);
+        offsets
+            .iter()
+            .map(|(dx, dy)| (x + dx, y + dy))
+            .filter(|(x, y)| *x >= 0 && *y >= 0 && *x < w && *y < h)
+            .map(|(x, y)| *frame.pixel(x as u32, y as u32))
+            .reduce(op)
+            .unwrap_or(*frame.pixel(x as u32, y as u32))
+    })
+    .unwrap()
+}
+
+fn apply<F>(
+    frame: &F,
+    element: &Element,
+    op: fn(F::Pixel, F::Pixel) -> F::Pixel,
+    reflect: bool,
+) -> Image<F::Pixel>
+where
+    F: Frame,
+    F::Pixel: Channel,
+{
+    let empty = frame.width() == 0 || frame.height() == 0;
+    if element.is_rect() && element.width() > 0 && element.height() > 0 && !empty {
+        let (w, h) = (element.width(), element.height());
+        let anchor = if reflect {
+            (w - 1 - w / 2, h - 1 - h / 2)
+        } else {
+            (w / 2, h / 2)
+        };
+        return rect(frame, w, h, anchor, op);
+    }
+
+    let mut offsets = element.offsets();
+    if reflect {
+        offsets.iter_mut().for_each(|(x, y)| {
+            *x = -*x;
+            *y = -*y;
+        });
+    }
+    by_offsets(frame, &offsets, op)
+}
+
+/// Minimum under `element` placed on each pixel
+pub fn erode<F>(frame: &F, element: &Element) -> Image<F::Pixel>
+where
+    F: Frame,
+    F::Pixel: Channel,
+{
+    apply(frame, element, min, false)
+}
+
+/// Maximum under the reflected `element` placed on each pixel
+pub fn dilate<F>(frame: &F, element: &Element) -> Image<F::Pixel>
+where
+    F: Frame,
+    F::Pixel: Channel,
+{
+    apply(frame, element, max, true)
+}
+
+/// Erosion then dilation, removes bright specks smaller than `element`
+pub fn open<F>(frame: &F, element: &Element) -> Image<F::Pixel>
+where
+    F: Frame,
+    F::Pixel: Channel,
+{
+    dilate(&erode(frame, element), element)
+}
+
+/// Dilation then erosion, fills dark holes smaller than `element`
+pub fn close<F>(frame: &F, element: &Element) -> Image<F::Pixel>
+where
+    F: Frame,
+    F::Pixel: Channel,
+{
+    erode(&dilate(frame, element), element)
+}
+
+fn difference<T: Channel>(a: &Image<T>, b: &Image<T>) -> Image<T> {
+    let data = a
+        .iter()
+        .zip(b.iter())
+        .map(|(a, b)| a.saturating_sub(*b))
+        .collect();
+    Image::from_vec(a.width(), a.height(), data).unwrap()
+}
+
+/// Dilation minus erosion, the outline of shapes
+pub fn gradient<F>(frame: &F, element: &Element) -> Image<F::Pixel>
+where
+    F: Frame,
+    F::Pixel: Channel,
+{
+    difference(&dilate(frame, element), &erode(frame, element))
+}
+
+/// Frame minus its opening, bright details smaller than `element`
+pub fn top_hat<F>(frame: &F, element: &Element) -> Image<F::Pixel>
+where
+    F: Frame,
+    F::Pixel: Channel,
+{
+    let opened = open(frame, element);
+    let data = frame
+        .rows()
+        .flatten()
+        .zip(opened.iter())
+        .map(|(a, b)| a.saturating_sub(*b))
+        .collect();
+    Image::from_vec(frame.width(), frame.height(), data).unwrap()
+}
+
+/// Closing minus the frame, dark details smaller than `element`
+pub fn black_hat<F>(frame: &F, element: &Element) -> Image<F::Pixel>
+where
+    F: Frame,
+    F::Pixel: Channel,
+{
+    let closed = close(frame, element);
+    let data = closed
+        .iter()
+        .zip(frame.rows().flatten())
+        .map(|(a, b)| a.saturating_sub(*b))
+        .collect();
+    Image::from_vec(frame.width(), frame.height(), data).unwrap()
+}
+
+/// `MAX` where every pixel under `hit` is foreground and every pixel under
+/// `miss` is background, foreground being anything above 0. Pixels outside
+/// of the frame are background.
+pub fn hit_or_miss<F>(frame: &F, hit: &Element, miss: &Element) -> Image<F::Pixel>
+where
+    F: Frame,
+    F::Pixel: Channel,
+{
+    let (w, h) = (frame.width() as i64, frame.height() as i64);
+    let (hit, miss) = (hit.offsets(), miss.offsets());
+    let foreground = |x: i64, y: i64| {
+        x >= 0 && y >= 0 && x < w && y < h && *frame.pixel(x as u32, y as u32) > F::Pixel::default()
+    };
+
+    Image::from_fn(frame.width(), frame.height(), |x, y| {
+        let (x, y) = (x as i64, y as i64);
+        let hits = hit.iter().all(|(dx, dy)| foreground(x + dx, y + dy));
+        if hits && !miss.iter().any(|(dx, dy)| foreground(x + dx, y + dy)) {
+            F::Pixel::MAX
+        } else {
+            F::Pixel::default()
+        }
+    })
+    .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::mask;
+
+    /// `#` is 255, `.` is 0
+    fn text(image: &Image<u8>) -> Vec<String> {
+        image
+            .rows()
+            .map(|r| {
+                r.iter()
+                    .map(|p| if *p == 255 { '#' } else { '.' })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn noisy() -> Image<u8> {
+        mask(&[
+            "#.......", "..####..", "..####.#", "..####..", "..#.##..", "........",
+        ])
+    }
+
+    #[test]
+    fn erode_and_dilate() {
+        let img = noisy();
+        let square = Element::rect(3, 3);
+        assert_eq!(
+            text(&erode(&img, &square)),
+            ["........", "........", "...##...", "........", "........", "........"]
+        );
+        assert_eq!(
+            text(&dilate(&img, &Element::cross(3, 3))),
+            ["######..", "########", ".#######", ".#######", ".######.", "..#.##..",]
+        );
+    }
+
+    #[test]
+    fn fast_path_matches() {
+        let img = Image::from_fn(13, 9, |x, y| ((x * 53 + y * 29) % 97) as u8).unwrap();
+        for (w, h) in [(3, 3), (4, 2), (1, 5), (7, 1), (20, 20)] {
+            let element = Element::rect(w, h);
+            let offsets = element.offsets();
+            let reflected: Vec<_> = offsets.iter().map(|(x, y)| (-x, -y)).collect();
+            assert_eq!(
+                &erode(&img, &element)[..],
+                &by_offsets(&img, &offsets, min)[..]
+            );
+            assert_eq!(
+                &dilate(&img, &element)[..],
+                &by_offsets(&img, &reflected, max)[..]
+            );
+        }
+    }
+
+    #[test]
+    fn open_and_close() {
+        let img = noisy();
+        let square = Element::rect(3, 3);
+        assert_eq!(
+            text(&open(&img, &square)),
+            ["........", "..####..", "..####..", "..####..", "........", "........"]
+        );
+
+        let holes = mask(&["#####", "#.###", "##.##", "#####"]);
+        assert!(close(&holes, &square).iter().all(|p| *p == 255));
+    }
+
+    #[test]
+    fn hats_and_gradient() {
+        let mut img = Image::new(12, 12, 100_u8).unwrap();
+        img[3 * 12 + 3] = 180;
+        img[8 * 12 + 8] = 20;
+        let square = Element::rect(3, 3);
+
+        let bright = top_hat(&img, &square);
+        assert_eq!(bright[3 * 12 + 3], 80);
+        assert_eq!(bright.iter().filter(|p| **p > 0).count(), 1);
+
+        let dark = black_hat(&img, &square);
+        assert_eq!(dark[8 * 12 + 8], 80);
+        assert_eq!(dark.iter().filter(|p| **p > 0).count(), 1);
+
+        let outline = gradient(
+            &mask(&[".....", ".###.", ".###.", ".###.", "....."]),
+            &square,
+        );
+        assert_eq!(
+            text(&outline),
+            ["#####", "#####", "##.##", "#####", "#####"]
+        );
+    }
+
+    #[test]
+    fn asymmetric() {
+        // erosion looks right, dilation pushes right
+        let right = Element::new(3, 1, vec![false, false, true]).unwrap();
+        let img = mask(&["..#.."]);
+        assert_eq!(text(&erode(&img, &right)), [".#..."]);
+        assert_eq!(text(&dilate(&img, &right)), ["...#."]);
+
+        // even sizes keep dilation the reflection of erosion
+        let wide = Element::rect(2, 1);
+        assert_eq!(text(&erode(&mask(&["..##.."]), &wide)), ["...#.."]);
+        assert_eq!(text(&dilate(&img, &wide)), [".##.."]);
+    }
+
+    #[test]
+    fn hit_or_miss_isolated() {
+        let img = mask(&["#....", "...#.", "...##", ".#..."]);
+        let center = Element::new(
+            3,
+            3,
+            [false, false, false, false, true, false, false, false, false].to_vec(),
+        )
+        .unwrap();
+        let ring = Element::new(
+            3,
+            3,
+            [true, true, true, true, false, true, true, true, true].to_vec(),
+        )
+        .unwrap();
+        assert_eq!(
+            text(&hit_or_miss(&img, &center, &ring)),
+            ["#....", ".....", ".....", ".#..."]
+        );
+    }
+
+    #[test]
+    fn float_and_empty() {
+        let img = Image::from_fn(5, 1, |x, _| x as f32 * 1.5).unwrap();
+        let out = erode(&img, &Element::rect(3, 1));
+        assert_eq!(&out[..], &[0.0, 0.0, 1.5, 3.0, 4.5]);
+
+        let empty = Image::<u8>::new(0, 3, 0).unwrap();
+        assert_eq!(dilate(&empty, &Element::rect(3, 3)).len(), 0);
+    }
+}
@@ -0,0 +1,496 @@
+//! Scaling with separable filters
+//!
+//! Works on any `Normalized` pixel. Channels are filtered as stored, integers
+//! in their display encoding and floats as the linear light `tonemap` works
+//! on. Pixels with an alpha channel are premultiplied while filtering so
+//! transparent pixels do not bleed their color, a hue channel is blended as a
+//! direction so hues across 0° meet there.
+
+use crate::{
+    color::Normalized,
+    display::{Frame, Image},
+    transform::catmull_rom,
+};
+use std::f64::consts::{PI, TAU};
+
+/// Reconstruction filter used by `resize`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Filter {
+    /// Closest source pixel, no blending
+    Nearest,
+    /// Area average, the fastest good choice for downscaling
+    Box,
+    /// Triangle filter
+    Bilinear,
+    /// Cubic with B = 0, C = 0.5, sharp
+    CatmullRom,
+    /// Cubic with B = C = 1/3, less ringing than Catmull-Rom
+    Mitchell,
+    Lanczos2,
+    Lanczos3,
+}
+
+impl Filter {
+    /// Radius of the filter at scale 1
+    fn support(self) -> f64 {
+        match self {
+            Filter::Nearest | Filter::Box => 0.5,
+            Filter::Bilinear => 1.0,
+            Filter::CatmullRom | Filter::Mitchell | Filter::Lanczos2 => 2.0,
+            Filter::Lanczos3 => 3.0,
+        }
+    }
+
+    fn weight(self, x: f64) -> f64 {
+        let x = x.abs();
+        match self {
+            Filter::Nearest | Filter::Box => {
+                if x <= 0.5 {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            Filter::Bilinear => (1.0 - x).max(0.0),
+            Filter::CatmullRom => catmull_rom(x),
+            Filter::Mitchell => mitchell(x),
+            Filter::Lanczos2 => lanczos(x, 2.0),
+            Filter::Lanczos3 => lanczos(x, 3.0),
+        }
+    }
+}
+
+fn mitchell(x: f64) -> f64 {
+    const B: f64 = 1.0 / 3.0;
+    const C: f64 = 1.0 / 3.0;
+    let x2 = x * x;
+    let x3 = x2 * x;
+    if x < 1.0 {
+        ((12.0 - 9.0 * B - 6.0 * C) * x3 + (-18.0 + 12.0 * B + 6.0 * C) * x2 + (6.0 - 2.0 * B))
+            / 6.0
+    } else if x < 2.0 {
+        ((-B - 6.0 * C) * x3
+            + (6.0 * B + 30.0 * C) * x2
+            + (-12.0 * B - 48.0 * C) * x
+            + (8.0 * B + 24.0 * C))
+            / 6.0
+    } else {
+        0.0
+    }
+}
+
+fn sinc(x: f64) -> f64 {
+    if x == 0.0 {
+        1.0
+    } else {
+        let x = x * PI;
+        x.sin() / x
+    }
+}
+
+fn lanczos(x: f64, a: f64) -> f64 {
+    if x < a {
+        sinc(x) * sinc(x / a)
+    } else {
+        0.0
+    }
+}
+
+/// Source pixels and their weights for every output pixel along one axis
+struct Weights {
+    /// First source index of each output pixel
+    start: Vec<usize>,
+    /// `taps` weights per output pixel, zero padded
+    values: Vec<f64>,
+    taps: usize,
+}
+
+impl Weights {
+    fn new(src: u32, dst: u32, filter: Filter) -> Self {
+        let scale = src as f64 / dst as f64;
+        let mut start = Vec::with_capacity(dst as usize);
+
+        if filter == Filter::Nearest {
+            for i in 0..dst {
+                let center = (i as f64 + 0.5) * scale;
+                start.push((center as usize).min(src as usize - 1));
+            }
+            let values = vec![1.0; dst as usize];
+            return Self {
+                start,
+                values,
+                taps: 1,
+            };
+        }
+
+        // widen the filter when shrinking so every source pixel contributes
+        let stretch = scale.max(1.0);
+        let support = filter.support() * stretch;
+        let taps = (support * 2.0).ceil() as usize + 1;
+        let mut values = vec![0.0; taps * dst as usize];
+
+        for (i, weights) in values.chunks_mut(taps).enumerate() {
+            let center = (i as f64 + 0.5) * scale;
+            let first = (center - support).floor().max(0.0) as usize;
+            let last = ((center + support).ceil() as usize).min(src as usize);
+
+            let mut sum = 0.0;
+            for (w, j) in weights.iter_mut().zip(first..last) {
+                *w = filter.weight((j as f64 + 0.5 - center) / stretch);
+                sum += *w;
+            }
+            // taps cut off at the edges are made up for by the ones left
+            if sum != 0.0 {
+                weights.iter_mut().for_each(|w| *w /= sum);
+            }
+            start.push(first);
+        }
+
+        Self {
+            start,
+            values,
+            taps,
+        }
+    }
+
+    /// Source indices and weights of output pixel `i`
+    #[inline]
+    fn get(&self, i: usize) -> (usize, &[f64]) {
+        (
+            self.start[i],
+            &self.values[i * self.taps..(i + 1) * self.taps],
+        )
+    }
+}
+
+/// Pixel channels in a flat `f64` buffer, premultiplied by alpha
+struct Plane {
+    width: usize,
+    data: Vec<f64>,
+}
+
+/// Values per pixel in a `Plane`, a hue takes two for its cosine and sine
+#[inline]
+fn plane_channels<P: Normalized>() -> usize {
+    P::CHANNELS + P::HUE.is_some() as usize
+}
+
+fn load<P: Normalized>(pixel: &P, out: &mut [f64]) {
+    pixel.normalize(out);
+    if let Some(hue) = P::HUE {
+        let (sin, cos) = (out[hue] * TAU).sin_cos();
+        out[hue] = cos;
+        out[P::CHANNELS] = sin;
+    }
+    if let Some(alpha) = P::ALPHA {
+        let a = out[alpha];
+        for (i, o) in out.iter_mut().enumerate() {
+            if i != alpha {
+                *o *= a;
+            }
+        }
+    }
+}
+
+fn store<P: Normalized>(values: &mut [f64], out: &mut P) {
+    if let Some(alpha) = P::ALPHA {
+        let a = values[alpha];
+        for (i, v) in values.iter_mut().enumerate() {
+            if i != alpha {
+                *v = if a > 0.0 { *v / a } else { 0.0 };
+            }
+        }
+    }
+    if let Some(hue) = P::HUE {
+        let turns = (values[P::CHANNELS].atan2(values[hue]) / TAU).rem_euclid(1.0);
+        // a tiny negative angle rounds up to a full turn
+        values[hue] = if turns < 1.0 { turns } else { 0.0 };
+    }
+    out.denormalize(values);
+}
+
+/// Filters one source row into `out`, `width` output pixels
+fn horizontal<P: Normalized>(row: &[P], weights: &Weights, out: &mut [f64]) {
+    let channels = plane_channels::<P>();
+    let mut src = vec![0.0; row.len() * channels];
+    for (p, s) in row.iter().zip(src.chunks_mut(channels)) {
+        load(p, s);
+    }
+
+    for (x, o) in out.chunks_mut(channels).enumerate() {
+        let (start, w) = weights.get(x);
+        o.iter_mut().for_each(|o| *o = 0.0);
+        for (w, s) in w.iter().zip(src[start * channels..].chunks(channels)) {
+            for (o, s) in o.iter_mut().zip(s) {
+                *o += w * s;
+            }
+        }
+    }
+}
+
+/// Filters the columns of `plane` into output row `y`
+fn vertical<P: Normalized>(plane: &Plane, weights: &Weights, y: usize, out: &mut [P]) {
+    let channels = plane_channels::<P>();
+    let row_len = plane.width * channels;
+    let (start, w) = weights.get(y);
+    let mut acc = vec![0.0; row_len];
+
+    for (w, row) in w.iter().zip(plane.data[start * row_len..].chunks(row_len)) {
+        if *w == 0.0 {
+            continue;
+        }
+        for (a, s) in acc.iter_mut().zip(row) {
+            *a += w * s;
+        }
+    }
+    for (p, a) in out.iter_mut().zip(acc.chunks_mut(channels)) {
+        store(a, p);
+    }
+}
+
+fn check<F: Frame>(frame: &F, width: u32, height: u32) -> bool {
+    frame.width() > 0 && frame.height() > 0 && width > 0 && height > 0
+}
+
+/// Scales `frame` to `width` x `height`, an empty source or target gives an
+/// empty image of the requested size
+pub fn resize<F>(frame: &F, width: u32, height: u32, filter: Filter) -> Image<F::Pixel>
+where
+    F: Frame,
+    F::Pixel: Normalized + Default,
+{
+    if !check(frame, width, height) {
+        return empty(width, height);
+    }
+    let channels = plane_channels::<F::Pixel>();
+    let horizontal_weights = Weights::new(frame.width(), width, filter);
+    let vertical_weights = Weights::new(frame.height(), height, filter);
+
+    let row_len = width as usize * channels;
+    let mut plane = Plane {
+        width: width as usize,
+        data: vec![0.0; row_len * frame.height() as usize],
+    };
+    for (row, out) in frame.rows().zip(plane.data.chunks_mut(row_len)) {
+        horizontal(row, &horizontal_weights, out);
+    }
+
+    let mut image = Image::new(width, height, F::Pixel::default()).unwrap();
+    for (y, row) in image.rows_mut().enumerate() {
+        vertical(&plane, &vertical_weights, y, row);
+    }
+    image
+}
+
+/// Parallel `resize`
+#[cfg(feature = "rayon")]
+pub fn par_resize<F>(frame: &F, width: u32, height: u32, filter: Filter) -> Image<F::Pixel>
+where
+    F: Frame + Sync,
+    F::Pixel: Normalized + Default + Send + Sync,
+{
+    use rayon::prelude::*;
+
+    if !check(frame, width, height) {
+        return empty(width, height);
+    }
+    let channels = plane_channels::<F::Pixel>();
+    let horizontal_weights = Weights::new(frame.width(), width, filter);
+    let vertical_weights = Weights::new(frame.height(), height, filter);
+
+    let row_len = width as usize * channels;
+    let mut plane = Plane {
+        width: width as usize,
+        data: vec![0.0; row_len * frame.height() as usize],
+    };
+    plane
+        .data
+        .par_chunks_mut(row_len)
+        .enumerate()
+        .for_each(|(y, out)| horizontal(frame.row(y as u32), &horizontal_weights, out));
+
+    let mut image = Image::new(width, height, F::Pixel::default()).unwrap();
+    image
+        .par_rows_mut()
+        .enumerate()
+        .for_each(|(y, row)| vertical(&plane, &vertical_weights, y, row));
+    image
+}
+
+fn empty<P: Copy + Default>(width: u32, height: u32) -> Image<P> {
+    Image::new(width, height, P::default()).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::{ARGB4444, HSL, RGB32, RGB565, RGB8};
+
+    const FILTERS: [Filter; 7] = [
+        Filter::Nearest,
+        Filter::Box,
+        Filter::Bilinear,
+        Filter::CatmullRom,
+        Filter::Mitchell,
+        Filter::Lanczos2,
+        Filter::Lanczos3,
+    ];
+
+    fn gradient(width: u32, height: u32) -> Image<RGB8> {
+        Image::from_fn(width, height, |x, y| {
+            RGB8::from([(x * 10) as u8, (y * 10) as u8, 128])
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn weights_sum_to_one() {
+        for filter in FILTERS {
+            for (src, dst) in [(10, 3), (3, 10), (7, 7), (100, 1), (1, 5)] {
+                let weights = Weights::new(src, dst, filter);
+                for i in 0..dst as usize {
+                    let (start, w) = weights.get(i);
+                    assert!(start < src as usize);
+                    let sum: f64 = w.iter().sum();
+                    assert!((sum - 1.0).abs() < 1e-9, "{:?} {} {}", filter, src, dst);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn same_size() {
+        let img = gradient(9, 6);
+        // Mitchell is not interpolating, it smooths even at the same size
+        for filter in FILTERS.iter().filter(|f| **f != Filter::Mitchell) {
+            let out = resize(&img, 9, 6, *filter);
+            assert_eq!(&out[..], &img[..], "{:?}", filter);
+        }
+    }
+
+    #[test]
+    fn flat_color() {
+        let color = RGB8::from([200, 50, 7]);
+        let img = Image::new(13, 11, color).unwrap();
+        for filter in FILTERS {
+            for (w, h) in [(4, 3), (30, 25), (1, 1), (13, 2)] {
+                let out = resize(&img, w, h, filter);
+                assert_eq!((out.width(), out.height()), (w, h));
+                assert!(out.iter().all(|p| *p == color), "{:?}", filter);
+            }
+        }
+    }
+
+    #[test]
+    fn box_averages() {
+        let img = Image::from_vec(4, 2, vec![0_u8, 100, 50, 50, 200, 100, 50, 50]).unwrap();
+        let out = resize(&img, 2, 1, Filter::Box);
+        assert_eq!(&out[..], &[100, 50]);
+
+        let out = resize(&img, 1, 1, Filter::Box);
+        assert_eq!(&out[..], &[75]);
+    }
+
+    #[test]
+    fn nearest_upscale() {
+        let img = Image::from_vec(2, 1, vec![1_u8, 2]).unwrap();
+        let out = resize(&img, 4, 2, Filter::Nearest);
+        assert_eq!(&out[..], &[1, 1, 2, 2, 1, 1, 2, 2]);
+    }
+
+    #[test]
+    fn float_overshoot() {
+        // a step makes Lanczos ring, floats keep the values outside [0, 1]
+        let img = Image::from_fn(8, 1, |x, _| if x < 4 { 0.0_f32 } else { 1.0 }).unwrap();
+        let out = resize(&img, 16, 1, Filter::Lanczos3);
+        assert!(out.iter().any(|x| *x < 0.0));
+        assert!(out.iter().any(|x| *x > 1.0));
+
+        let img = Image::from_fn(8, 1, |x, _| if x < 4 { 0_u8 } else { 255 }).unwrap();
+        let out = resize(&img, 16, 1, Filter::Lanczos3);
+        assert_eq!(out[0], 0);
+        assert_eq!(out[15], 255);
+    }
+
+    #[test]
+    fn alpha() {
+        // a transparent white pixel must not tint its opaque neighbor
+        let gray = ARGB4444::new(8, 8, 8);
+        let img = Image::from_vec(2, 1, vec![ARGB4444(0x0FFF), gray]).unwrap();
+        let out = resize(&img, 1, 1, Filter::Box);
+        assert_eq!(out[0], gray.with_alpha(8));
+
+        let clear = Image::new(3, 3, ARGB4444(0x0ABC)).unwrap();
+        let out = resize(&clear, 2, 2, Filter::Bilinear);
+        assert!(out.iter().all(|p| *p == ARGB4444(0)));
+    }
+
+    #[test]
+    fn as_stored() {
+        let img = Image::from_vec(2, 1, vec![0.0_f32, 1.0]).unwrap();
+        assert_eq!(resize(&img, 1, 1, Filter::Box)[0], 0.5);
+
+        let img = Image::from_vec(2, 1, vec![0_u8, 255]).unwrap();
+        assert_eq!(resize(&img, 1, 1, Filter::Box)[0], 128);
+    }
+
+    #[test]
+    fn other_formats() {
+        let color = RGB565::new(20, 40, 10);
+        let img = Image::new(5, 4, color).unwrap();
+        for filter in FILTERS {
+            let out = resize(&img, 3, 7, filter);
+            assert!(out.iter().all(|p| *p == color), "{:?}", filter);
+        }
+        let img =
+            Image::from_vec(2, 1, vec![RGB565::new(0, 0, 0), RGB565::new(30, 60, 2)]).unwrap();
+        assert_eq!(resize(&img, 1, 1, Filter::Box)[0], RGB565::new(15, 30, 1));
+
+        let img = Image::from_vec(
+            2,
+            1,
+            vec![HSL::from([100.0, 0.2, 0.4]), HSL::from([200.0, 0.4, 0.6])],
+        )
+        .unwrap();
+        let out = resize(&img, 1, 1, Filter::Box);
+        assert!((out[0].h - 150.0).abs() < 1e-9);
+        assert!((out[0].s - 0.3).abs() < 1e-9);
+        assert!((out[0].l - 0.5).abs() < 1e-9);
+
+        // 350° and 10° meet at red, not at cyan
+        let img = Image::from_vec(
+            2,
+            1,
+            vec![HSL::from([350.0, 0.5, 0.5]), HSL::from([10.0, 0.5, 0.5])],
+        )
+        .unwrap();
+        let h = resize(&img, 1, 1, Filter::Box)[0].h;
+        assert!((0.0..360.0).contains(&h));
+        assert!(h.min(360.0 - h) < 1e-9);
+    }
+
+    #[test]
+    fn views_and_empty() {
+        let img = gradient(20, 10);
+        let out = resize(&img.view(5, 5, 10, 5), 5, 5, Filter::Mitchell);
+        assert_eq!((out.width(), out.height()), (5, 5));
+
+        let out = resize(&img, 0, 4, Filter::Lanczos2);
+        assert_eq!((out.width(), out.height(), out.len()), (0, 4, 0));
+
+        let floats: Image<RGB32> = img.convert();
+        let out = resize(&floats, 10, 5, Filter::Box);
+        assert!((out.pixel(0, 0).r - (0.0 + 10.0) / 2.0 / 255.0).abs() < 1e-6);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn parallel() {
+        let img = gradient(23, 17);
+        for filter in FILTERS {
+            let seq = resize(&img, 11, 31, filter);
+            let par = par_resize(&img, 11, 31, filter);
+            assert_eq!(&seq[..], &par[..]);
+        }
+    }
+}
@@ -0,0 +1,12 @@
+//! Fixtures shared by the tests of several modules
+
+use crate::display::Image;
+
+/// Binary image drawn as text, `#` is 255 and anything else 0
+pub(crate) fn mask(rows: &[&str]) -> Image<u8> {
+    let data = rows
+        .iter()
+        .flat_map(|row| row.chars().map(|c| if c == '#' { 255 } else { 0 }))
+        .collect();
+    Image::from_vec(rows[0].len() as u32, rows.len() as u32, data).unwrap()
+}
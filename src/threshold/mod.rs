@@ -0,0 +1,303 @@
+//! Thresholding of single channel frames
+//!
+//! Otsu and triangle levels come from a 256 bin histogram of the normalized
+//! values, exact for `u8` and quantized for wider channels.
+
+use crate::{
+    color::Channel,
+    display::{Frame, Image},
+    filter::{correlate, Kernel, Plane},
+    transform::Border,
+};
+
+const BINS: usize = 256;
+
+/// What happens to pixels above and below the level
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Threshold {
+    /// `MAX` above the level, 0 otherwise
+    Binary,
+    /// 0 above the level, `MAX` otherwise
+    BinaryInverted,
+    /// The level above it, unchanged otherwise
+    Truncate,
+    /// Unchanged above the level, 0 otherwise
+    ToZero,
+}
+
+impl Threshold {
+    #[inline]
+    fn apply<T: Channel>(self, value: T, level: T) -> T {
+        let above = value > level;
+        match self {
+            Threshold::Binary if above => T::MAX,
+            Threshold::BinaryInverted if !above => T::MAX,
+            Threshold::Binary | Threshold::BinaryInverted => T::MIN,
+            Threshold::Truncate if above => level,
+            Threshold::ToZero if !above => T::MIN,
+            Threshold::Truncate | Threshold::ToZero => value,
+        }
+    }
+}
+
+/// Local level of `adaptive`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Adaptive {
+    /// Mean of the block
+    Mean,
+    /// Gaussian weighted mean of the block
+    Gaussian,
+}
+
+/// Applies `mode` with a fixed `level`
+pub fn threshold<F>(frame: &F, level: F::Pixel, mode: Threshold) -> Image<F::Pixel>
+where
+    F: Frame,
+    F::Pixel: Channel,
+{
+    let data = frame
+        .rows()
+        .flatten()
+        .map(|v| mode.apply(*v, level))
+        .collect();
+    Image::from_vec(frame.width(), frame.height(), data).unwrap()
+}
+
+fn histogram<F>(frame: &F) -> [u64; BINS]
+where
+    F: Frame,
+    F::Pixel: Channel,
+{
+    let mut histogram = [0; BINS];
+    for v in frame.rows().flatten() {
+        let bin = (v.to_f64().clamp(0.0, 1.0) * (BINS - 1) as f64).round() as usize;
+        histogram[bin] += 1;
+    }
+    histogram
+}
+
+fn level<T: Channel>(bin: usize) -> T {
+    T::from_f64(bin as f64 / (BINS - 1) as f64)
+}
+
+/// Level maximizing the variance between the two classes
+fn otsu_bin(histogram: &[u64; BINS]) -> usize {
+    let total: u64 = histogram.iter().sum();
+    let sum: f64 = histogram
+        .iter()
+        .enumerate()
+        .map(|(i, n)| i as f64 * *n as f64)
+        .sum();
+
+    let (mut below, mut below_sum) = (0, 0.0);
+    // a single value has no split, nothing goes above it
+    let mut best = histogram.iter().rposition(|n| *n > 0).unwrap_or(0);
+    let mut best_variance = -1.0;
+    for (i, n) in histogram.iter().enumerate() {
+        below += n;
+        below_sum += i as f64 * *n as f64;
+        let above = total - below;
+        if below == 0 || above == 0 {
+            continue;
+        }
+        let mean_below = below_sum / below as f64;
+        let mean_above = (sum - below_sum) / above as f64;
+        let variance = below as f64 * above as f64 * (mean_below - mean_above).powi(2);
+        if variance > best_variance {
+            best = i;
+            best_variance = variance;
+        }
+    }
+    best
+}
+
+/// Bin farthest below the line from the peak to the end of the longer tail
+fn triangle_bin(histogram: &[u64; BINS]) -> usize {
+    let first = histogram.iter().position(|n| *n > 0).unwrap_or(0);
+    let last = histogram.iter().rposition(|n| *n > 0).unwrap_or(0);
+    let peak = (0..BINS)
+        .max_by_key(|i| (histogram[*i], std::cmp::Reverse(*i)))
+        .unwrap();
+
+    // the line ends one bin past the last filled one, where the count is 0
+    let (end, range): (f64, Vec<usize>) = if last - peak >= peak - first {
+        ((last + 1) as f64, (peak..=last).collect())
+    } else {
+        (first as f64 - 1.0, (first..=peak).collect())
+    };
+
+    let (p, h) = (peak as f64, histogram[peak] as f64);
+    let distance = |i: usize| {
+        // below the line from (p, h) to (end, 0), scaled
+        h * (end - i as f64) / (end - p) - histogram[i] as f64
+    };
+    range
+        .into_iter()
+        .max_by(|a, b| distance(*a).total_cmp(&distance(*b)))
+        .unwrap_or(peak)
+}
+
+/// Otsu's level, returned along with `mode` applied with it
+pub fn otsu<F>(frame: &F, mode: Threshold) -> (F::Pixel, Image<F::Pixel>)
+where
+    F: Frame,
+    F::Pixel: Channel,
+{
+    let level = level(otsu_bin(&histogram(frame)));
+    (level, threshold(frame, level, mode))
+}
+
+/// Triangle method level, suited to one dominant peak with a long tail such
+/// as text on paper, returned along with `mode` applied with it
+pub fn triangle<F>(frame: &F, mode: Threshold) -> (F::Pixel, Image<F::Pixel>)
+where
+    F: Frame,
+    F::Pixel: Channel,
+{
+    let level = level(triangle_bin(&histogram(frame)));
+    (level, threshold(frame, level, mode))
+}
+
+/// Gaussian taps across `size` pixels
+fn gaussian_taps(size: u32) -> Vec<f64> {
+    // the usual sigma for a block size
+    let sigma = 0.3 * ((size as f64 - 1.0) * 0.5 - 1.0) + 0.8;
+    let center = (size / 2) as f64;
+    let taps: Vec<f64> = (0..size)
+        .map(|i| (-(i as f64 - center).powi(2) / (2.0 * sigma * sigma)).exp())
+        .collect();
+    let sum: f64 = taps.iter().sum();
+    taps.into_iter().map(|t| t / sum).collect()
+}
+
+/// Applies `mode` with a level of the local mean of the `block` x `block`
+/// pixels around each pixel minus `offset`, in normalized units. Odd block
+/// sizes center on the pixel.
+pub fn adaptive<F>(
+    frame: &F,
+    method: Adaptive,
+    block: u32,
+    offset: f64,
+    mode: Threshold,
+) -> Image<F::Pixel>
+where
+    F: Frame,
+    F::Pixel: Channel,
+{
+    let plane = Plane {
+        width: frame.width(),
+        height: frame.height(),
+        channels: 1,
+        data: frame.rows().flatten().map(|v| v.to_f64()).collect(),
+    };
+    let kernel = match method {
+        Adaptive::Mean => Kernel::box_blur(block, block),
+        Adaptive::Gaussian => {
+            let taps = gaussian_taps(block);
+            Kernel::from_separable(taps.clone(), taps)
+        }
+    };
+    let mean = correlate(&plane, &kernel, &Border::Replicate);
+
+    let data = frame
+        .rows()
+        .flatten()
+        .zip(&mean.data)
+        .map(|(v, m)| mode.apply(*v, F::Pixel::from_f64(m - offset)))
+        .collect();
+    Image::from_vec(frame.width(), frame.height(), data).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn modes() {
+        let img = Image::from_vec(4, 1, vec![10_u8, 100, 101, 250]).unwrap();
+        let apply = |mode| threshold(&img, 100, mode).to_vec();
+        assert_eq!(apply(Threshold::Binary), [0, 0, 255, 255]);
+        assert_eq!(apply(Threshold::BinaryInverted), [255, 255, 0, 0]);
+        assert_eq!(apply(Threshold::Truncate), [10, 100, 100, 100]);
+        assert_eq!(apply(Threshold::ToZero), [0, 0, 101, 250]);
+
+        let img = Image::from_vec(2, 1, vec![0.2_f32, 0.7]).unwrap();
+        assert_eq!(threshold(&img, 0.5, Threshold::Binary).to_vec(), [0.0, 1.0]);
+    }
+
+    /// Dark and bright noisy clusters
+    fn bimodal() -> Image<u8> {
+        Image::from_fn(20, 10, |x, y| {
+            let noise = ((x * 7 + y * 13) % 21) as u8;
+            if x < 8 {
+                30 + noise
+            } else {
+                180 + noise
+            }
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn otsu_split() {
+        let img = bimodal();
+        let (level, mask) = otsu(&img, Threshold::Binary);
+        assert!((50..180).contains(&level), "{}", level);
+        for (x, _, p) in mask.pixels() {
+            assert_eq!(*p, if x < 8 { 0 } else { 255 });
+        }
+
+        let float: Image<f32> = img.map(|v| v as f32 / 255.0);
+        let (level, _) = otsu(&float, Threshold::Binary);
+        assert!((50.0 / 255.0..180.0 / 255.0).contains(&level));
+
+        let flat = Image::new(3, 3, 77_u8).unwrap();
+        let (_, mask) = otsu(&flat, Threshold::Binary);
+        assert!(mask.iter().all(|p| *p == 0));
+    }
+
+    #[test]
+    fn triangle_tail() {
+        // paper at 220 with a few dark strokes spread towards black
+        let img = Image::from_fn(30, 10, |x, y| {
+            if x == 3 || (y == 5 && x > 20) {
+                (x * 6) as u8
+            } else {
+                220
+            }
+        })
+        .unwrap();
+        let (level, mask) = triangle(&img, Threshold::BinaryInverted);
+        assert!((100..220).contains(&level), "{}", level);
+        assert_eq!(*mask.pixel(3, 0), 255);
+        assert_eq!(*mask.pixel(10, 0), 0);
+
+        let (level, _) = triangle(&bimodal().map(|v| 255 - v), Threshold::Binary);
+        assert!(level > 0);
+    }
+
+    #[test]
+    fn uneven_lighting() {
+        // dark dots on a background brightening from left to right
+        let dot = |x: u32, y: u32| x % 6 == 3 && y % 6 == 3;
+        let img = Image::from_fn(36, 12, |x, y| {
+            let background = 40 + x * 5;
+            if dot(x, y) {
+                (background - 30) as u8
+            } else {
+                background as u8
+            }
+        })
+        .unwrap();
+
+        let (_, global) = otsu(&img, Threshold::BinaryInverted);
+        assert!(global.pixels().any(|(x, y, p)| *p == 255 && !dot(x, y)));
+
+        for method in [Adaptive::Mean, Adaptive::Gaussian] {
+            let mask = adaptive(&img, method, 5, 5.0 / 255.0, Threshold::BinaryInverted);
+            for (x, y, p) in mask.pixels() {
+                assert_eq!(*p == 255, dot(x, y), "{:?} at {} {}", method, x, y);
+            }
+        }
+    }
+}
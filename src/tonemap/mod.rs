@@ -0,0 +1,300 @@
+use crate::{
+    color::{RGB32, RGB8},
+    display::{Frame, Image},
+    error::IncorrectData,
+};
+
+/// Curves mapping scene-referred linear light into display range
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Operator {
+    /// L / (1 + L) applied to luminance
+    Reinhard,
+    /// Reinhard with the luminance `white` mapped to pure white, `white`
+    /// must be positive and finite
+    ReinhardExtended { white: f32 },
+    /// John Hable's Uncharted 2 filmic curve
+    Hable,
+    /// Krzysztof Narkowicz's fit of the ACES filmic curve
+    Aces,
+    /// Fitted AgX base curve with the default inset/outset matrices
+    AgX,
+}
+
+impl Operator {
+    /// Fails with `IncorrectData::Parameter` for a `white` that is not
+    /// positive and finite
+    pub fn validate(self) -> Result<Self, IncorrectData> {
+        match self {
+            Operator::ReinhardExtended { white } if !(white.is_finite() && white > 0.0) => {
+                Err(IncorrectData::Parameter { name: "white" })
+            }
+            _ => Ok(self),
+        }
+    }
+
+    /// Maps a linear color to linear display values in [0, 1], `self` must
+    /// pass `validate`
+    fn apply(self, rgb: RGB32) -> RGB32 {
+        let rgb = RGB32 {
+            r: non_negative(rgb.r),
+            g: non_negative(rgb.g),
+            b: non_negative(rgb.b),
+        };
+
+        let rgb = match self {
+            Operator::Reinhard => reinhard(rgb, f32::INFINITY),
+            Operator::ReinhardExtended { white } => reinhard(rgb, white),
+            Operator::Hable => hable(rgb),
+            Operator::Aces => map(rgb, aces),
+            Operator::AgX => agx(rgb),
+        };
+        map(rgb, |x| x.clamp(0.0, 1.0))
+    }
+}
+
+/// `exposure` is in stops, the image is scaled by 2^exposure before mapping.
+/// The result is sRGB encoded.
+///
+/// Fails when `operator` does not pass `Operator::validate`.
+pub fn tone_map(
+    image: &Image<RGB32>,
+    operator: Operator,
+    exposure: f32,
+) -> Result<Image<RGB8>, IncorrectData> {
+    let operator = operator.validate()?;
+    let scale = exposure.exp2();
+    let data = image
+        .iter()
+        .map(|rgb| {
+            let rgb = operator.apply(*rgb * scale);
+            RGB8 {
+                r: RGB8::percent32_to_byte(linear_to_srgb(rgb.r)),
+                g: RGB8::percent32_to_byte(linear_to_srgb(rgb.g)),
+                b: RGB8::percent32_to_byte(linear_to_srgb(rgb.b)),
+            }
+        })
+        .collect();
+
+    Image::from_vec(image.width(), image.height(), data)
+}
+
+#[inline]
+fn non_negative(x: f32) -> f32 {
+    // also flushes NaN
+    if x > 0.0 {
+        x
+    } else {
+        0.0
+    }
+}
+
+#[inline]
+fn map<F: Fn(f32) -> f32>(rgb: RGB32, f: F) -> RGB32 {
+    RGB32 {
+        r: f(rgb.r),
+        g: f(rgb.g),
+        b: f(rgb.b),
+    }
+}
+
+#[inline]
+fn luminance(rgb: RGB32) -> f32 {
+    0.2126 * rgb.r + 0.7152 * rgb.g + 0.0722 * rgb.b
+}
+
+fn linear_to_srgb(x: f32) -> f32 {
+    if x <= 0.003_130_8 {
+        x * 12.92
+    } else {
+        1.055 * x.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+fn reinhard(rgb: RGB32, white: f32) -> RGB32 {
+    let l = luminance(rgb);
+    if l <= 0.0 {
+        return rgb;
+    }
+    let mapped = l * (1.0 + l / (white * white)) / (1.0 + l);
+    rgb * (mapped / l)
+}
+
+fn hable_partial(x: f32) -> f32 {
+    const A: f32 = 0.15;
+    const B: f32 = 0.50;
+    const C: f32 = 0.10;
+    const D: f32 = 0.20;
+    const E: f32 = 0.02;
+    const F: f32 = 0.30;
+    ((x * (A * x + C * B) + D * E) / (x * (A * x + B) + D * F)) - E / F
+}
+
+fn hable(rgb: RGB32) -> RGB32 {
+    const EXPOSURE_BIAS: f32 = 2.0;
+    const WHITE: f32 = 11.2;
+    let white_scale = 1.0 / hable_partial(WHITE);
+    map(rgb, |x| hable_partial(x * EXPOSURE_BIAS) * white_scale)
+}
+
+fn aces(x: f32) -> f32 {
+    const A: f32 = 2.51;
+    const B: f32 = 0.03;
+    const C: f32 = 2.43;
+    const D: f32 = 0.59;
+    const E: f32 = 0.14;
+    (x * (A * x + B)) / (x * (C * x + D) + E)
+}
+
+fn mul_matrix(m: &[[f32; 3]; 3], rgb: RGB32) -> RGB32 {
+    RGB32 {
+        r: m[0][0] * rgb.r + m[0][1] * rgb.g + m[0][2] * rgb.b,
+        g: m[1][0] * rgb.r + m[1][1] * rgb.g + m[1][2] * rgb.b,
+        b: m[2][0] * rgb.r + m[2][1] * rgb.g + m[2][2] * rgb.b,
+    }
+}
+
+fn agx(rgb: RGB32) -> RGB32 {
+    const INSET: [[f32; 3]; 3] = [
+        [0.842_479_06, 0.078_433_6, 0.079_223_745],
+        [0.042_328_242, 0.878_468_6, 0.079_166_13],
+        [0.042_375_655, 0.078_433_6, 0.879_143],
+    ];
+    const OUTSET: [[f32; 3]; 3] = [
+        [1.196_879, -0.098_020_88, -0.099_029_74],
+        [-0.052_896_85, 1.151_903_1, -0.098_961_18],
+        [-0.052_971_635, -0.098_043_45, 1.151_073_7],
+    ];
+    const MIN_EV: f32 = -12.473_931;
+    const MAX_EV: f32 = 4.026_069;
+
+    let rgb = mul_matrix(&INSET, rgb);
+    let rgb = map(rgb, |x| {
+        let x =
+            (x.max(f32::MIN_POSITIVE).log2().clamp(MIN_EV, MAX_EV) - MIN_EV) / (MAX_EV - MIN_EV);
+        let x2 = x * x;
+        let x4 = x2 * x2;
+        15.5 * x4 * x2 - 40.14 * x4 * x + 31.96 * x4 - 6.868 * x2 * x + 0.4298 * x2 + 0.1191 * x
+            - 0.00232
+    });
+    let rgb = mul_matrix(&OUTSET, rgb);
+    // the curve outputs display encoded values, linearize them again
+    map(rgb, |x| non_negative(x).powf(2.2))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gray(x: f32) -> RGB32 {
+        RGB32::from([x, x, x])
+    }
+
+    #[test]
+    fn reinhard() {
+        let rgb = Operator::Reinhard.apply(gray(1.0));
+        assert!((rgb.r - 0.5).abs() < 1e-6);
+
+        let rgb = Operator::Reinhard.apply(gray(0.0));
+        assert_eq!(rgb, gray(0.0));
+
+        let rgb = Operator::Reinhard.apply(gray(1.0e6));
+        assert!(rgb.r < 1.0);
+    }
+
+    #[test]
+    fn reinhard_extended() {
+        let op = Operator::ReinhardExtended { white: 4.0 };
+        let rgb = op.apply(gray(4.0));
+        assert!((rgb.g - 1.0).abs() < 1e-6);
+
+        let rgb = op.apply(gray(100.0));
+        assert_eq!(rgb, gray(1.0));
+
+        assert!(op.validate().is_ok());
+        for white in [0.0, -1.0, f32::NAN, f32::INFINITY] {
+            assert!(matches!(
+                Operator::ReinhardExtended { white }.validate(),
+                Err(IncorrectData::Parameter { name: "white" })
+            ));
+        }
+    }
+
+    #[test]
+    fn reinhard_keeps_hue() {
+        let rgb = Operator::Reinhard.apply(RGB32::from([2.0, 1.0, 0.0]));
+        assert!((rgb.r / rgb.g - 2.0).abs() < 1e-5);
+        assert_eq!(rgb.b, 0.0);
+    }
+
+    #[test]
+    fn hable() {
+        let rgb = Operator::Hable.apply(gray(0.0));
+        assert!(rgb.r.abs() < 1e-6);
+
+        let rgb = Operator::Hable.apply(gray(5.6));
+        assert!((rgb.r - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn aces() {
+        assert_eq!(Operator::Aces.apply(gray(0.0)), gray(0.0));
+        assert_eq!(Operator::Aces.apply(gray(100.0)), gray(1.0));
+    }
+
+    #[test]
+    fn monotonic() {
+        let operators = [
+            Operator::Reinhard,
+            Operator::ReinhardExtended { white: 8.0 },
+            Operator::Hable,
+            Operator::Aces,
+            Operator::AgX,
+        ];
+        for op in operators.iter() {
+            let mut last = -1.0;
+            for i in 0..200 {
+                let rgb = op.apply(gray(i as f32 * 0.1));
+                assert!(rgb.r >= 0.0 && rgb.r <= 1.0, "{:?}", op);
+                assert!(rgb.r >= last, "{:?}", op);
+                last = rgb.r;
+            }
+        }
+    }
+
+    #[test]
+    fn invalid_input() {
+        let rgb = Operator::Aces.apply(RGB32::from([f32::NAN, -1.0, f32::INFINITY]));
+        assert_eq!(rgb.r, 0.0);
+        assert_eq!(rgb.g, 0.0);
+    }
+
+    #[test]
+    fn tone_map() {
+        let mut img: Image<RGB32> = Image::new(4, 2, gray(0.0)).unwrap();
+        img[1] = gray(1000.0);
+        img[2] = RGB32::from([1.0, 0.25, 0.0]);
+
+        let out = super::tone_map(&img, Operator::ReinhardExtended { white: 16.0 }, 0.0).unwrap();
+        assert_eq!(out.width(), 4);
+        assert_eq!(out.height(), 2);
+        assert_eq!(out[0], RGB8::from([0, 0, 0]));
+        assert_eq!(out[1], RGB8::from([255, 255, 255]));
+        assert!(out[2].r > out[2].g && out[2].g > out[2].b);
+
+        let op = Operator::ReinhardExtended { white: 0.0 };
+        assert!(super::tone_map(&img, op, 0.0).is_err());
+    }
+
+    #[test]
+    fn exposure() {
+        let img: Image<RGB32> = Image::new(1, 1, gray(0.25)).unwrap();
+        let a = super::tone_map(&img, Operator::Reinhard, 2.0).unwrap();
+        let b = super::tone_map(
+            &Image::new(1, 1, gray(1.0)).unwrap(),
+            Operator::Reinhard,
+            0.0,
+        )
+        .unwrap();
+        assert_eq!(a[0], b[0]);
+    }
+}
@@ -0,0 +1,346 @@
+mod orientation;
+mod rotate;
+mod warp;
+
+pub use orientation::Orientation;
+#[cfg(feature = "rayon")]
+pub use rotate::par_rotate;
+pub use rotate::{rotate, Canvas};
+pub use warp::{affine_from_points, perspective_from_points, warp_affine, warp_perspective};
+#[cfg(feature = "rayon")]
+pub use warp::{par_warp_affine, par_warp_perspective};
+
+use crate::{
+    color::{Channel, Pixel},
+    display::Frame,
+};
+
+/// Most channels an interpolated pixel can have, nearest sampling has no
+/// limit
+const MAX_CHANNELS: usize = 4;
+
+/// How pixel values between pixel centers are reconstructed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interpolation {
+    /// Closest pixel
+    Nearest,
+    /// Linear blend of the 2x2 closest pixels
+    Bilinear,
+    /// Catmull-Rom spline through the 4x4 closest pixels
+    Bicubic,
+}
+
+impl Interpolation {
+    /// First tap and weights of up to 4 taps around `t`
+    #[inline]
+    fn taps(self, t: f64) -> (i64, usize, [f64; 4]) {
+        match self {
+            Interpolation::Nearest => (t.round() as i64, 1, [1.0, 0.0, 0.0, 0.0]),
+            Interpolation::Bilinear => {
+                let start = t.floor();
+                let f = t - start;
+                (start as i64, 2, [1.0 - f, f, 0.0, 0.0])
+            }
+            Interpolation::Bicubic => {
+                let start = t.floor();
+                let f = t - start;
+                let w = [
+                    catmull_rom(1.0 + f),
+                    catmull_rom(f),
+                    catmull_rom(1.0 - f),
+                    catmull_rom(2.0 - f),
+                ];
+                (start as i64 - 1, 4, w)
+            }
+        }
+    }
+}
+
+/// Catmull-Rom cubic (B = 0, C = 0.5) at distance `x`
+#[inline]
+pub(crate) fn catmull_rom(x: f64) -> f64 {
+    let x = x.abs();
+    if x < 1.0 {
+        1.5 * x * x * x - 2.5 * x * x + 1.0
+    } else if x < 2.0 {
+        -0.5 * x * x * x + 2.5 * x * x - 4.0 * x + 2.0
+    } else {
+        0.0
+    }
+}
+
+/// Pixels read outside of the frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Border<P> {
+    /// Always this pixel
+    Constant(P),
+    /// Closest edge pixel, `aaa|abcd|ddd`
+    Replicate,
+    /// Mirrored around the edge pixels, `dcb|abcd|cba`
+    Reflect,
+    /// Tiled, `bcd|abcd|abc`
+    Wrap,
+}
+
+impl<P> Border<P> {
+    /// Index `i` moved inside `0..len`, `None` when the constant is read
+    #[inline]
+    pub(crate) fn index(&self, i: i64, len: u32) -> Option<u32> {
+        let len = len as i64;
+        if i >= 0 && i < len {
+            return Some(i as u32);
+        }
+        if len == 0 {
+            return None;
+        }
+        let i = match self {
+            Border::Constant(_) => return None,
+            Border::Replicate => i.clamp(0, len - 1),
+            Border::Reflect if len == 1 => 0,
+            Border::Reflect => {
+                let period = 2 * (len - 1);
+                let i = i.rem_euclid(period);
+                if i < len {
+                    i
+                } else {
+                    period - i
+                }
+            }
+            Border::Wrap => i.rem_euclid(len),
+        };
+        Some(i as u32)
+    }
+}
+
+/// Keeps far away coordinates from overflowing tap indices
+const MAX_COORDINATE: f64 = 1e12;
+
+/// Value at `(x, y)` with pixel centers at integer coordinates. Non-finite
+/// coordinates and empty frames give the constant border, or the default
+/// pixel for the other borders.
+pub(crate) fn sample<F>(
+    frame: &F,
+    x: f64,
+    y: f64,
+    interpolation: Interpolation,
+    border: Border<F::Pixel>,
+) -> F::Pixel
+where
+    F: Frame,
+    F::Pixel: Pixel + Default,
+{
+    let outside = match border {
+        Border::Constant(p) => p,
+        _ => F::Pixel::default(),
+    };
+    let tap = |x: i64, y: i64| match (
+        border.index(x, frame.width()),
+        border.index(y, frame.height()),
+    ) {
+        (Some(x), Some(y)) => *frame.pixel(x, y),
+        _ => outside,
+    };
+
+    if !x.is_finite() || !y.is_finite() {
+        return outside;
+    }
+    let x = x.clamp(-MAX_COORDINATE, MAX_COORDINATE);
+    let y = y.clamp(-MAX_COORDINATE, MAX_COORDINATE);
+    if interpolation == Interpolation::Nearest {
+        return tap(x.round() as i64, y.round() as i64);
+    }
+
+    assert!(
+        F::Pixel::CHANNELS <= MAX_CHANNELS,
+        "cannot interpolate pixels with more than {} channels",
+        MAX_CHANNELS
+    );
+    let (x0, nx, wx) = interpolation.taps(x);
+    let (y0, ny, wy) = interpolation.taps(y);
+
+    let mut acc = [0.0; MAX_CHANNELS];
+    for (j, wy) in wy.iter().enumerate().take(ny) {
+        for (i, wx) in wx.iter().enumerate().take(nx) {
+            let pixel = tap(x0 + i as i64, y0 + j as i64);
+            let w = wx * wy;
+            for (a, c) in acc.iter_mut().zip(pixel.channels()) {
+                *a += w * c.to_f64();
+            }
+        }
+    }
+
+    let mut out = outside;
+    for (c, a) in out.channels_mut().iter_mut().zip(&acc) {
+        *c = <F::Pixel as Pixel>::Channel::from_f64(*a);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        color::{RGB32, RGB8},
+        display::Image,
+    };
+
+    #[test]
+    fn catmull_rom_weights() {
+        assert_eq!(catmull_rom(0.0), 1.0);
+        assert_eq!(catmull_rom(1.0), 0.0);
+        assert_eq!(catmull_rom(2.0), 0.0);
+        for i in 0..=10 {
+            let f = i as f64 / 10.0;
+            let (_, _, w) = Interpolation::Bicubic.taps(f);
+            assert!((w.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn sample_exact() {
+        let img = Image::from_fn(4, 4, |x, y| (x * 10 + y * 50) as u8).unwrap();
+        for interpolation in [
+            Interpolation::Nearest,
+            Interpolation::Bilinear,
+            Interpolation::Bicubic,
+        ] {
+            assert_eq!(
+                sample(&img, 1.0, 2.0, interpolation, Border::Constant(0)),
+                110
+            );
+        }
+    }
+
+    #[test]
+    fn sample_between() {
+        let img = Image::from_fn(2, 1, |x, _| RGB32::from([x as f32, 0.0, 1.0])).unwrap();
+        let black = RGB32::default();
+        let p = sample(
+            &img,
+            0.25,
+            0.0,
+            Interpolation::Bilinear,
+            Border::Constant(black),
+        );
+        assert_eq!(p, RGB32::from([0.25, 0.0, 1.0]));
+
+        let p = sample(
+            &img,
+            1.5,
+            0.0,
+            Interpolation::Bilinear,
+            Border::Constant(black),
+        );
+        assert_eq!(p, RGB32::from([0.5, 0.0, 0.5]));
+
+        let p = sample(
+            &img,
+            0.4,
+            0.0,
+            Interpolation::Nearest,
+            Border::Constant(black),
+        );
+        assert_eq!(p.r, 0.0);
+    }
+
+    #[test]
+    fn sample_outside() {
+        let img = Image::new(2, 2, RGB8::from([255, 255, 255])).unwrap();
+        let red = RGB8::from([255, 0, 0]);
+        assert_eq!(
+            sample(
+                &img,
+                -1.0,
+                0.0,
+                Interpolation::Nearest,
+                Border::Constant(red)
+            ),
+            red
+        );
+        assert_eq!(
+            sample(
+                &img,
+                f64::NAN,
+                0.0,
+                Interpolation::Bicubic,
+                Border::Constant(red)
+            ),
+            red
+        );
+
+        let p = sample(
+            &img,
+            -0.5,
+            0.0,
+            Interpolation::Bilinear,
+            Border::Constant(red),
+        );
+        assert_eq!(p, RGB8::from([255, 128, 128]));
+    }
+
+    #[test]
+    fn border_index() {
+        let index = |border: Border<u8>| -> Vec<u32> {
+            (-4..8).map(|i| border.index(i, 4).unwrap()).collect()
+        };
+        assert_eq!(
+            index(Border::Replicate),
+            [0, 0, 0, 0, 0, 1, 2, 3, 3, 3, 3, 3]
+        );
+        assert_eq!(index(Border::Reflect), [2, 3, 2, 1, 0, 1, 2, 3, 2, 1, 0, 1]);
+        assert_eq!(index(Border::Wrap), [0, 1, 2, 3, 0, 1, 2, 3, 0, 1, 2, 3]);
+        assert_eq!(Border::Constant(0).index(4, 4), None);
+        assert_eq!(Border::<u8>::Reflect.index(-3, 1), Some(0));
+        assert_eq!(Border::<u8>::Wrap.index(0, 0), None);
+    }
+
+    #[test]
+    fn sample_borders() {
+        let img = Image::from_vec(3, 1, vec![10_u8, 20, 30]).unwrap();
+        let nearest = |x: f64, border| sample(&img, x, 0.0, Interpolation::Nearest, border);
+        assert_eq!(nearest(-2.0, Border::Replicate), 10);
+        assert_eq!(nearest(4.0, Border::Reflect), 10);
+        assert_eq!(nearest(4.0, Border::Wrap), 20);
+        assert_eq!(nearest(f64::INFINITY, Border::Wrap), 0);
+
+        // replicated edges keep the value flat past the border
+        let p = sample(&img, 2.5, 0.0, Interpolation::Bilinear, Border::Replicate);
+        assert_eq!(p, 30);
+        let p = sample(&img, -0.5, 0.0, Interpolation::Bilinear, Border::Wrap);
+        assert_eq!(p, 20);
+    }
+
+    #[derive(Debug, Clone, Copy, Default, PartialEq)]
+    struct Five([u8; 5]);
+
+    impl Pixel for Five {
+        type Channel = u8;
+
+        const CHANNELS: usize = 5;
+
+        fn channels(&self) -> &[u8] {
+            &self.0
+        }
+
+        fn channels_mut(&mut self) -> &mut [u8] {
+            &mut self.0
+        }
+
+        fn from_channels(channels: &[u8]) -> Self {
+            let mut x = Self::default();
+            x.0.copy_from_slice(&channels[..5]);
+            x
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "more than 4 channels")]
+    fn sample_channels() {
+        let img = Image::new(2, 2, Five([1, 2, 3, 4, 5])).unwrap();
+        assert_eq!(
+            sample(&img, 0.4, 0.6, Interpolation::Nearest, Border::Replicate),
+            Five([1, 2, 3, 4, 5])
+        );
+        sample(&img, 0.5, 0.5, Interpolation::Bilinear, Border::Replicate);
+    }
+}
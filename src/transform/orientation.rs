@@ -0,0 +1,109 @@
+use crate::display::Image;
+
+/// EXIF orientation tag, the transform that makes the stored image upright
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    /// 1
+    Normal,
+    /// 2
+    MirrorLeftRight,
+    /// 3
+    Rotate180,
+    /// 4
+    MirrorTopBottom,
+    /// 5, mirror left to right then rotate by 270 degrees
+    Transpose,
+    /// 6
+    Rotate90,
+    /// 7, mirror left to right then rotate by 90 degrees
+    Transverse,
+    /// 8
+    Rotate270,
+}
+
+impl Orientation {
+    pub fn from_exif(value: u16) -> Option<Self> {
+        match value {
+            1 => Some(Orientation::Normal),
+            2 => Some(Orientation::MirrorLeftRight),
+            3 => Some(Orientation::Rotate180),
+            4 => Some(Orientation::MirrorTopBottom),
+            5 => Some(Orientation::Transpose),
+            6 => Some(Orientation::Rotate90),
+            7 => Some(Orientation::Transverse),
+            8 => Some(Orientation::Rotate270),
+            _ => None,
+        }
+    }
+
+    pub fn to_exif(self) -> u16 {
+        match self {
+            Orientation::Normal => 1,
+            Orientation::MirrorLeftRight => 2,
+            Orientation::Rotate180 => 3,
+            Orientation::MirrorTopBottom => 4,
+            Orientation::Transpose => 5,
+            Orientation::Rotate90 => 6,
+            Orientation::Transverse => 7,
+            Orientation::Rotate270 => 8,
+        }
+    }
+}
+
+impl<T: Copy> Image<T> {
+    /// Turns an image stored with `orientation` upright
+    pub fn apply_orientation(&mut self, orientation: Orientation) {
+        match orientation {
+            Orientation::Normal => {}
+            Orientation::MirrorLeftRight => self.mirror_left_right(),
+            Orientation::Rotate180 => self.rotate180(),
+            Orientation::MirrorTopBottom => self.mirror_top_bottom(),
+            Orientation::Transpose => self.transpose(),
+            Orientation::Rotate90 => self.rotate90(),
+            Orientation::Transverse => self.transverse(),
+            Orientation::Rotate270 => self.rotate270(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::display::Frame;
+
+    #[test]
+    fn exif() {
+        for value in 1..=8 {
+            assert_eq!(Orientation::from_exif(value).unwrap().to_exif(), value);
+        }
+        assert_eq!(Orientation::from_exif(0), None);
+        assert_eq!(Orientation::from_exif(9), None);
+    }
+
+    #[test]
+    fn upright() {
+        // every pixel is unique so any misplaced pixel shows up
+        let upright = Image::from_fn(3, 2, |x, y| y * 3 + x).unwrap();
+
+        for value in 1..=8 {
+            let orientation = Orientation::from_exif(value).unwrap();
+
+            // store the image the way a camera with this orientation would
+            let mut stored = Image::from_fn(3, 2, |x, y| y * 3 + x).unwrap();
+            match orientation {
+                Orientation::Normal => {}
+                Orientation::MirrorLeftRight => stored.mirror_left_right(),
+                Orientation::Rotate180 => stored.rotate180(),
+                Orientation::MirrorTopBottom => stored.mirror_top_bottom(),
+                Orientation::Transpose => stored.transpose(),
+                Orientation::Rotate90 => stored.rotate270(),
+                Orientation::Transverse => stored.transverse(),
+                Orientation::Rotate270 => stored.rotate90(),
+            }
+
+            stored.apply_orientation(orientation);
+            assert_eq!(stored.width(), 3, "orientation {}", value);
+            assert_eq!(&stored[..], &upright[..], "orientation {}", value);
+        }
+    }
+}
@@ -0,0 +1,201 @@
+use super::{sample, Border, Interpolation};
+use crate::{
+    color::Pixel,
+    display::{Frame, Image},
+};
+
+/// Size of the rotated image
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Canvas {
+    /// Keep the size of the source, corners are cut off
+    Crop,
+    /// Grow to fit the whole rotated source
+    Expand,
+}
+
+/// Maps output coordinates back onto the source
+struct Rotation {
+    cos: f64,
+    sin: f64,
+    src_center: (f64, f64),
+    dst_center: (f64, f64),
+    width: u32,
+    height: u32,
+}
+
+impl Rotation {
+    fn new(width: u32, height: u32, degrees: f64, canvas: Canvas) -> Self {
+        let (sin, cos) = snap(degrees.to_radians().sin_cos());
+        let (dst_width, dst_height) = match canvas {
+            Canvas::Crop => (width, height),
+            Canvas::Expand => {
+                let (w, h) = (width as f64, height as f64);
+                // tolerate rounding noise so 90 degrees does not grow a pixel
+                let fit = |x: f64| (x - 1e-6).ceil().max(0.0) as u32;
+                (
+                    fit(w * cos.abs() + h * sin.abs()),
+                    fit(w * sin.abs() + h * cos.abs()),
+                )
+            }
+        };
+
+        Self {
+            cos,
+            sin,
+            src_center: center(width, height),
+            dst_center: center(dst_width, dst_height),
+            width: dst_width,
+            height: dst_height,
+        }
+    }
+
+    #[inline]
+    fn source(&self, x: u32, y: u32) -> (f64, f64) {
+        let dx = x as f64 - self.dst_center.0;
+        let dy = y as f64 - self.dst_center.1;
+        (
+            self.src_center.0 + dx * self.cos + dy * self.sin,
+            self.src_center.1 - dx * self.sin + dy * self.cos,
+        )
+    }
+}
+
+fn center(width: u32, height: u32) -> (f64, f64) {
+    ((width as f64 - 1.0) / 2.0, (height as f64 - 1.0) / 2.0)
+}
+
+/// Exact values for multiples of 90 degrees
+fn snap((sin, cos): (f64, f64)) -> (f64, f64) {
+    let snap = |x: f64| {
+        if (x - x.round()).abs() < 1e-12 {
+            x.round()
+        } else {
+            x
+        }
+    };
+    (snap(sin), snap(cos))
+}
+
+/// Rotates clockwise by `degrees` around the center of the image, pixels
+/// not covered by the source are `background`
+pub fn rotate<P: Pixel + Default>(
+    image: &Image<P>,
+    degrees: f64,
+    interpolation: Interpolation,
+    canvas: Canvas,
+    background: P,
+) -> Image<P> {
+    let rotation = Rotation::new(image.width(), image.height(), degrees, canvas);
+    Image::from_fn(rotation.width, rotation.height, |x, y| {
+        let (sx, sy) = rotation.source(x, y);
+        sample(image, sx, sy, interpolation, Border::Constant(background))
+    })
+    .unwrap()
+}
+
+/// Parallel `rotate`
+#[cfg(feature = "rayon")]
+pub fn par_rotate<P: Pixel + Default + Send + Sync>(
+    image: &Image<P>,
+    degrees: f64,
+    interpolation: Interpolation,
+    canvas: Canvas,
+    background: P,
+) -> Image<P> {
+    let rotation = Rotation::new(image.width(), image.height(), degrees, canvas);
+    Image::par_from_fn(rotation.width, rotation.height, |x, y| {
+        let (sx, sy) = rotation.source(x, y);
+        sample(image, sx, sy, interpolation, Border::Constant(background))
+    })
+    .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{color::RGB8, display::FrameMut};
+
+    fn image(width: u32, height: u32) -> Image<RGB8> {
+        Image::from_fn(width, height, |x, y| {
+            RGB8::from([(x * 20) as u8, (y * 20) as u8, 100])
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn right_angles() {
+        let black = RGB8::default();
+        for (w, h) in [(5, 3), (4, 4), (6, 1)] {
+            for interpolation in [
+                Interpolation::Nearest,
+                Interpolation::Bilinear,
+                Interpolation::Bicubic,
+            ] {
+                let mut exact = image(w, h);
+                exact.rotate90();
+                let rotated = rotate(&image(w, h), 90.0, interpolation, Canvas::Expand, black);
+                assert_eq!((rotated.width(), rotated.height()), (h, w));
+                assert_eq!(&rotated[..], &exact[..]);
+
+                let mut exact = image(w, h);
+                exact.rotate180();
+                let rotated = rotate(&image(w, h), 180.0, interpolation, Canvas::Crop, black);
+                assert_eq!(&rotated[..], &exact[..]);
+
+                let mut exact = image(w, h);
+                exact.rotate270();
+                let rotated = rotate(&image(w, h), -90.0, interpolation, Canvas::Expand, black);
+                assert_eq!(&rotated[..], &exact[..]);
+            }
+        }
+    }
+
+    #[test]
+    fn identity() {
+        let img = image(7, 5);
+        let same = rotate(
+            &img,
+            360.0,
+            Interpolation::Bicubic,
+            Canvas::Crop,
+            RGB8::default(),
+        );
+        assert_eq!(&same[..], &img[..]);
+    }
+
+    #[test]
+    fn expand() {
+        let img = image(10, 4);
+        let red = RGB8::from([255, 0, 0]);
+        let rotated = rotate(&img, 45.0, Interpolation::Bilinear, Canvas::Expand, red);
+        // 14 / sqrt(2) = 9.9
+        assert_eq!((rotated.width(), rotated.height()), (10, 10));
+        assert_eq!(*rotated.pixel(0, 0), red);
+        assert_ne!(*rotated.pixel(5, 5), red);
+
+        let cropped = rotate(&img, 45.0, Interpolation::Bilinear, Canvas::Crop, red);
+        assert_eq!((cropped.width(), cropped.height()), (10, 4));
+        assert_eq!(*cropped.pixel(9, 0), red);
+        assert_ne!(*cropped.pixel(5, 2), red);
+    }
+
+    #[test]
+    fn center_is_fixed() {
+        let mut img = Image::new(9, 9, 0_u8).unwrap();
+        *img.pixel_mut(4, 4) = 200;
+        for degrees in [10.0, 33.0, 71.5, 200.0] {
+            let rotated = rotate(&img, degrees, Interpolation::Nearest, Canvas::Crop, 0);
+            assert_eq!(*rotated.pixel(4, 4), 200);
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn parallel() {
+        let img = image(31, 17);
+        let black = RGB8::default();
+        let seq = rotate(&img, 23.0, Interpolation::Bicubic, Canvas::Expand, black);
+        let par = par_rotate(&img, 23.0, Interpolation::Bicubic, Canvas::Expand, black);
+        assert_eq!(&seq[..], &par[..]);
+    }
+}
@@ -0,0 +1,384 @@
+use super::{sample, Border, Interpolation};
+use crate::{
+    color::Pixel,
+    display::{Frame, Image},
+    geometry::Vec2,
+};
+
+/// Homography taking output coordinates back onto the source
+#[derive(Clone, Copy)]
+struct Inverse([[f64; 3]; 3]);
+
+impl Inverse {
+    /// A singular `matrix` maps everything outside of the source
+    fn new(matrix: [[f64; 3]; 3]) -> Self {
+        let m = matrix;
+        let cofactor = |r0: usize, r1: usize, c0: usize, c1: usize| {
+            m[r0][c0] * m[r1][c1] - m[r0][c1] * m[r1][c0]
+        };
+        let adjugate = [
+            [
+                cofactor(1, 2, 1, 2),
+                -cofactor(0, 2, 1, 2),
+                cofactor(0, 1, 1, 2),
+            ],
+            [
+                -cofactor(1, 2, 0, 2),
+                cofactor(0, 2, 0, 2),
+                -cofactor(0, 1, 0, 2),
+            ],
+            [
+                cofactor(1, 2, 0, 1),
+                -cofactor(0, 2, 0, 1),
+                cofactor(0, 1, 0, 1),
+            ],
+        ];
+        let det = m[0][0] * adjugate[0][0] + m[0][1] * adjugate[1][0] + m[0][2] * adjugate[2][0];
+
+        let mut inverse = adjugate;
+        inverse.iter_mut().flatten().for_each(|x| *x /= det);
+        Self(inverse)
+    }
+
+    #[inline]
+    fn source(&self, x: u32, y: u32) -> (f64, f64) {
+        let [a, b, c] = self.0;
+        let (x, y) = (x as f64, y as f64);
+        let w = c[0] * x + c[1] * y + c[2];
+        (
+            (a[0] * x + a[1] * y + a[2]) / w,
+            (b[0] * x + b[1] * y + b[2]) / w,
+        )
+    }
+}
+
+fn homography([a, b]: [[f64; 3]; 2]) -> [[f64; 3]; 3] {
+    [a, b, [0.0, 0.0, 1.0]]
+}
+
+/// Maps `frame` through the affine `matrix`, a source pixel at `(x, y)` lands
+/// on `matrix * [x, y, 1]` of the `width` x `height` output. Pixel centers are
+/// at integer coordinates, a singular matrix gives only border pixels.
+pub fn warp_affine<F>(
+    frame: &F,
+    matrix: [[f64; 3]; 2],
+    width: u32,
+    height: u32,
+    interpolation: Interpolation,
+    border: Border<F::Pixel>,
+) -> Image<F::Pixel>
+where
+    F: Frame,
+    F::Pixel: Pixel + Default,
+{
+    warp_perspective(
+        frame,
+        homography(matrix),
+        width,
+        height,
+        interpolation,
+        border,
+    )
+}
+
+/// Maps `frame` through the homography `matrix`, a source pixel at `(x, y)`
+/// lands on `matrix * [x, y, 1]` divided by its third component
+pub fn warp_perspective<F>(
+    frame: &F,
+    matrix: [[f64; 3]; 3],
+    width: u32,
+    height: u32,
+    interpolation: Interpolation,
+    border: Border<F::Pixel>,
+) -> Image<F::Pixel>
+where
+    F: Frame,
+    F::Pixel: Pixel + Default,
+{
+    let inverse = Inverse::new(matrix);
+    Image::from_fn(width, height, |x, y| {
+        let (sx, sy) = inverse.source(x, y);
+        sample(frame, sx, sy, interpolation, border)
+    })
+    .unwrap()
+}
+
+/// Parallel `warp_affine`
+#[cfg(feature = "rayon")]
+pub fn par_warp_affine<F>(
+    frame: &F,
+    matrix: [[f64; 3]; 2],
+    width: u32,
+    height: u32,
+    interpolation: Interpolation,
+    border: Border<F::Pixel>,
+) -> Image<F::Pixel>
+where
+    F: Frame + Sync,
+    F::Pixel: Pixel + Default + Send + Sync,
+{
+    par_warp_perspective(
+        frame,
+        homography(matrix),
+        width,
+        height,
+        interpolation,
+        border,
+    )
+}
+
+/// Parallel `warp_perspective`
+#[cfg(feature = "rayon")]
+pub fn par_warp_perspective<F>(
+    frame: &F,
+    matrix: [[f64; 3]; 3],
+    width: u32,
+    height: u32,
+    interpolation: Interpolation,
+    border: Border<F::Pixel>,
+) -> Image<F::Pixel>
+where
+    F: Frame + Sync,
+    F::Pixel: Pixel + Default + Send + Sync,
+{
+    let inverse = Inverse::new(matrix);
+    Image::par_from_fn(width, height, |x, y| {
+        let (sx, sy) = inverse.source(x, y);
+        sample(frame, sx, sy, interpolation, border)
+    })
+    .unwrap()
+}
+
+/// Solves `a * x = b` with partial pivoting, `None` when `a` is singular
+fn solve<const N: usize>(mut a: [[f64; N]; N], mut b: [f64; N]) -> Option<[f64; N]> {
+    let scale = a.iter().flatten().fold(0.0_f64, |m, x| m.max(x.abs()));
+    let epsilon = scale * 1e-12;
+
+    for col in 0..N {
+        let pivot = (col..N).max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))?;
+        if a[pivot][col].abs() <= epsilon {
+            return None;
+        }
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+
+        let top = a[col];
+        for row in col + 1..N {
+            let f = a[row][col] / top[col];
+            for (x, t) in a[row][col..].iter_mut().zip(&top[col..]) {
+                *x -= f * t;
+            }
+            b[row] -= f * b[col];
+        }
+    }
+
+    let mut x = [0.0; N];
+    for row in (0..N).rev() {
+        let rest: f64 = (row + 1..N).map(|k| a[row][k] * x[k]).sum();
+        x[row] = (b[row] - rest) / a[row][row];
+    }
+    Some(x)
+}
+
+/// Affine matrix taking each of `src` onto the matching `dst`, `None` when
+/// the source points are collinear
+pub fn affine_from_points(src: [Vec2<f64>; 3], dst: [Vec2<f64>; 3]) -> Option<[[f64; 3]; 2]> {
+    let a = src.map(|p| [p.x, p.y, 1.0]);
+    let x = solve(a, dst.map(|p| p.x))?;
+    let y = solve(a, dst.map(|p| p.y))?;
+    Some([x, y])
+}
+
+/// Homography taking each of `src` onto the matching `dst`, `None` when
+/// three of the points are collinear
+pub fn perspective_from_points(src: [Vec2<f64>; 4], dst: [Vec2<f64>; 4]) -> Option<[[f64; 3]; 3]> {
+    // u = (h0 x + h1 y + h2) / (h6 x + h7 y + 1), likewise for v
+    let mut a = [[0.0; 8]; 8];
+    let mut b = [0.0; 8];
+    for (i, (s, d)) in src.iter().zip(&dst).enumerate() {
+        a[2 * i] = [s.x, s.y, 1.0, 0.0, 0.0, 0.0, -s.x * d.x, -s.y * d.x];
+        a[2 * i + 1] = [0.0, 0.0, 0.0, s.x, s.y, 1.0, -s.x * d.y, -s.y * d.y];
+        b[2 * i] = d.x;
+        b[2 * i + 1] = d.y;
+    }
+
+    let h = solve(a, b)?;
+    let matrix = [[h[0], h[1], h[2]], [h[3], h[4], h[5]], [h[6], h[7], 1.0]];
+    let collinear = |p: Vec2<f64>, q: Vec2<f64>, r: Vec2<f64>| {
+        ((q.x - p.x) * (r.y - p.y) - (q.y - p.y) * (r.x - p.x)).abs() < 1e-9
+    };
+    // the solve also succeeds for some degenerate quads
+    for points in [src, dst] {
+        for skip in 0..4 {
+            let mut rest = (0..4).filter(|&i| i != skip).map(|i| points[i]);
+            let (p, q, r) = (rest.next()?, rest.next()?, rest.next()?);
+            if collinear(p, q, r) {
+                return None;
+            }
+        }
+    }
+    Some(matrix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::RGB8;
+
+    fn image(width: u32, height: u32) -> Image<RGB8> {
+        Image::from_fn(width, height, |x, y| {
+            RGB8::from([(x * 20) as u8, (y * 20) as u8, 100])
+        })
+        .unwrap()
+    }
+
+    fn v(x: f64, y: f64) -> Vec2<f64> {
+        Vec2 { x, y }
+    }
+
+    const IDENTITY: [[f64; 3]; 2] = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0]];
+
+    #[test]
+    fn identity() {
+        let img = image(6, 4);
+        for interpolation in [
+            Interpolation::Nearest,
+            Interpolation::Bilinear,
+            Interpolation::Bicubic,
+        ] {
+            let out = warp_affine(&img, IDENTITY, 6, 4, interpolation, Border::Replicate);
+            assert_eq!(&out[..], &img[..]);
+        }
+    }
+
+    #[test]
+    fn translate() {
+        let img = image(5, 5);
+        let red = RGB8::from([255, 0, 0]);
+        let matrix = [[1.0, 0.0, 2.0], [0.0, 1.0, 1.0]];
+        let out = warp_affine(
+            &img,
+            matrix,
+            5,
+            5,
+            Interpolation::Bilinear,
+            Border::Constant(red),
+        );
+        assert_eq!(*out.pixel(0, 0), red);
+        assert_eq!(*out.pixel(1, 4), red);
+        assert_eq!(out.pixel(3, 2), img.pixel(1, 1));
+
+        let out = warp_affine(&img, matrix, 5, 5, Interpolation::Nearest, Border::Wrap);
+        assert_eq!(out.pixel(0, 0), img.pixel(3, 4));
+    }
+
+    #[test]
+    fn matches_rotate90() {
+        // clockwise by 90 degrees: (x, y) -> (h - 1 - y, x)
+        let img = image(5, 3);
+        let mut exact = image(5, 3);
+        exact.rotate90();
+        let matrix = [[0.0, -1.0, 2.0], [1.0, 0.0, 0.0]];
+        let out = warp_affine(&img, matrix, 3, 5, Interpolation::Bicubic, Border::Reflect);
+        assert_eq!(&out[..], &exact[..]);
+    }
+
+    #[test]
+    fn singular() {
+        let img = image(4, 4);
+        let red = RGB8::from([255, 0, 0]);
+        let matrix = [[1.0, 2.0, 0.0], [2.0, 4.0, 0.0]];
+        let out = warp_affine(
+            &img,
+            matrix,
+            3,
+            3,
+            Interpolation::Bilinear,
+            Border::Constant(red),
+        );
+        assert!(out.iter().all(|p| *p == red));
+    }
+
+    #[test]
+    fn affine_points() {
+        let matrix = [[2.0, 0.5, 3.0], [-1.0, 1.5, 7.0]];
+        let map = |p: Vec2<f64>| {
+            v(
+                matrix[0][0] * p.x + matrix[0][1] * p.y + matrix[0][2],
+                matrix[1][0] * p.x + matrix[1][1] * p.y + matrix[1][2],
+            )
+        };
+        let src = [v(0.0, 0.0), v(10.0, 1.0), v(3.0, 8.0)];
+        let found = affine_from_points(src, src.map(map)).unwrap();
+        for (row, expected) in found.iter().zip(&matrix) {
+            for (a, b) in row.iter().zip(expected) {
+                assert!((a - b).abs() < 1e-9);
+            }
+        }
+
+        let line = [v(0.0, 0.0), v(1.0, 1.0), v(2.0, 2.0)];
+        assert_eq!(affine_from_points(line, src), None);
+    }
+
+    #[test]
+    fn perspective_points() {
+        let square = [v(0.0, 0.0), v(1.0, 0.0), v(1.0, 1.0), v(0.0, 1.0)];
+        let quad = [v(10.0, 5.0), v(30.0, 8.0), v(25.0, 40.0), v(2.0, 30.0)];
+        let m = perspective_from_points(square, quad).unwrap();
+        for (s, d) in square.iter().zip(&quad) {
+            let w = m[2][0] * s.x + m[2][1] * s.y + m[2][2];
+            let x = (m[0][0] * s.x + m[0][1] * s.y + m[0][2]) / w;
+            let y = (m[1][0] * s.x + m[1][1] * s.y + m[1][2]) / w;
+            assert!((x - d.x).abs() < 1e-9 && (y - d.y).abs() < 1e-9);
+        }
+
+        let degenerate = [v(0.0, 0.0), v(1.0, 0.0), v(2.0, 0.0), v(0.0, 1.0)];
+        assert_eq!(perspective_from_points(degenerate, quad), None);
+    }
+
+    #[test]
+    fn perspective_round_trip() {
+        // warping onto a quad and back restores the middle of the image
+        let img = image(12, 12);
+        let corners = [v(0.0, 0.0), v(11.0, 0.0), v(11.0, 11.0), v(0.0, 11.0)];
+        let same = perspective_from_points(corners, corners).unwrap();
+        let out = warp_perspective(
+            &img,
+            same,
+            12,
+            12,
+            Interpolation::Nearest,
+            Border::Replicate,
+        );
+        assert_eq!(&out[..], &img[..]);
+
+        let affine = [[1.0, 0.0, 2.0], [0.0, 1.0, -1.0]];
+        let a = warp_affine(&img, affine, 12, 12, Interpolation::Bicubic, Border::Wrap);
+        let p = warp_perspective(
+            &img,
+            homography(affine),
+            12,
+            12,
+            Interpolation::Bicubic,
+            Border::Wrap,
+        );
+        assert_eq!(&a[..], &p[..]);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn parallel() {
+        let img = image(20, 15);
+        let quad = [v(1.0, 2.0), v(18.0, 0.0), v(19.0, 14.0), v(0.0, 12.0)];
+        let corners = [v(0.0, 0.0), v(19.0, 0.0), v(19.0, 14.0), v(0.0, 14.0)];
+        let m = perspective_from_points(corners, quad).unwrap();
+        let seq = warp_perspective(&img, m, 20, 15, Interpolation::Bicubic, Border::Reflect);
+        let par = par_warp_perspective(&img, m, 20, 15, Interpolation::Bicubic, Border::Reflect);
+        assert_eq!(&seq[..], &par[..]);
+
+        let affine = [[0.9, 0.1, 1.0], [-0.1, 0.9, 2.0]];
+        let seq = warp_affine(&img, affine, 20, 15, Interpolation::Bilinear, Border::Wrap);
+        let par = par_warp_affine(&img, affine, 20, 15, Interpolation::Bilinear, Border::Wrap);
+        assert_eq!(&seq[..], &par[..]);
+    }
+}